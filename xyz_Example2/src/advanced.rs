@@ -2,336 +2,396 @@
 // Ejemplo de Cifrado Avanzado: Cifrado de Archivos con Múltiples Algoritmos
 
 use std::fs;
-use std::io::{self, Write};
+use std::io::{BufRead, Write};
 
-/// Advanced encryption manager with multiple cipher options
-/// Gestor de cifrado avanzado con múltiples opciones de cifrado
-pub struct EncryptionManager {
-    algorithm: CipherType,
-    key: Vec<u8>,
+use xyz_example2::alphabet::Alphabet;
+use xyz_example2::bench::{self, BenchConfig};
+use xyz_example2::cipher::{Cipher, CipherFactory, CipherParams, CipherRegistry, ParamKind, ParamSpec};
+use xyz_example2::classical::{EnigmaCipher, EnigmaSettings};
+use xyz_example2::cryptanalysis::vigenere::MAX_KEY_LEN;
+use xyz_example2::cryptanalysis::xor::MAX_KEY_SIZE;
+use xyz_example2::cryptanalysis::{crack_caesar, crack_repeating_xor, crack_vigenere, parse_ciphertext, Language};
+use xyz_example2::encoding::{bytes_to_hex, encodings};
+use xyz_example2::hash::{hash_file, Digest, Sha224, Sha256, Sha384, Sha512};
+use xyz_example2::random::SecureRandom;
+use xyz_example2::{CipherError, CipherType, EncryptionManager, SecretKey};
+
+use crate::cli::{bench_header, bench_row};
+use crate::console::Console;
+
+/// Catalog text for a registered algorithm, else its library description
+/// Texto del catálogo para un algoritmo registrado, si no su descripción de la librería
+fn algorithm_label<R, W>(console: &Console<R, W>, name: &str, description: &str) -> String {
+    console.messages().lookup(&format!("algorithm.{}", name)).unwrap_or(description).to_string()
 }
 
-#[derive(Clone, Debug)]
-pub enum CipherType {
-    Caesar(u8),      // Caesar cipher with shift
-    XOR,             // XOR cipher
-    Vigenere,        // Vigenère cipher
+/// Prompt for a parameter: `param.<algorithm>.<name>`, then `param.<name>`, then the library prompt
+/// Pregunta de un parámetro: `param.<algoritmo>.<nombre>`, luego `param.<nombre>`, luego la de la librería
+fn param_prompt<R, W>(console: &Console<R, W>, algorithm: &str, spec: &ParamSpec) -> String {
+    let messages = console.messages();
+    messages
+        .lookup(&format!("param.{}.{}", algorithm, spec.name))
+        .or_else(|| messages.lookup(&format!("param.{}", spec.name)))
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{}:", spec.prompt))
 }
 
-impl EncryptionManager {
-    /// Create a new encryption manager
-    /// Crear un nuevo gestor de cifrado
-    pub fn new(algorithm: CipherType, key: &str) -> Self {
-        Self {
-            algorithm,
-            key: key.as_bytes().to_vec(),
-        }
-    }
-    
-    /// Encrypt data using the selected algorithm
-    /// Cifrar datos usando el algoritmo seleccionado
-    pub fn encrypt(&self, data: &[u8]) -> Vec<u8> {
-        match &self.algorithm {
-            CipherType::Caesar(shift) => self.caesar_encrypt(data, *shift),
-            CipherType::XOR => self.xor_encrypt(data),
-            CipherType::Vigenere => self.vigenere_encrypt(data),
-        }
-    }
-    
-    /// Decrypt data using the selected algorithm
-    /// Descifrar datos usando el algoritmo seleccionado
-    pub fn decrypt(&self, data: &[u8]) -> Vec<u8> {
-        match &self.algorithm {
-            CipherType::Caesar(shift) => self.caesar_decrypt(data, *shift),
-            CipherType::XOR => self.xor_decrypt(data),
-            CipherType::Vigenere => self.vigenere_decrypt(data),
-        }
-    }
-    
-    /// Encrypt a file and save to a new file
-    /// Cifrar un archivo y guardarlo en un nuevo archivo
-    pub fn encrypt_file(&self, input_path: &str, output_path: &str) -> io::Result<()> {
-        let data = fs::read(input_path)?;
-        let encrypted = self.encrypt(&data);
-        fs::write(output_path, encrypted)?;
-        println!("File encrypted: {} -> {}", input_path, output_path);
-        Ok(())
-    }
-    
-    /// Decrypt a file and save to a new file
-    /// Descifrar un archivo y guardarlo en un nuevo archivo
-    pub fn decrypt_file(&self, input_path: &str, output_path: &str) -> io::Result<()> {
-        let data = fs::read(input_path)?;
-        let decrypted = self.decrypt(&data);
-        fs::write(output_path, decrypted)?;
-        println!("File decrypted: {} -> {}", input_path, output_path);
-        Ok(())
-    }
-    
-    // Private implementation methods / Métodos de implementación privados
-    
-    fn caesar_encrypt(&self, data: &[u8], shift: u8) -> Vec<u8> {
-        data.iter()
-            .map(|&b| {
-                if b.is_ascii_alphabetic() {
-                    let base = if b.is_ascii_lowercase() { b'a' } else { b'A' };
-                    (((b - base) + shift) % 26) + base
-                } else {
-                    b
-                }
-            })
-            .collect()
-    }
-    
-    fn caesar_decrypt(&self, data: &[u8], shift: u8) -> Vec<u8> {
-        self.caesar_encrypt(data, 26 - shift)
-    }
-    
-    fn xor_encrypt(&self, data: &[u8]) -> Vec<u8> {
-        data.iter()
-            .enumerate()
-            .map(|(i, &b)| b ^ self.key[i % self.key.len()])
-            .collect()
-    }
-    
-    fn xor_decrypt(&self, data: &[u8]) -> Vec<u8> {
-        // XOR is its own inverse / XOR es su propia inversa
-        self.xor_encrypt(data)
-    }
-    
-    fn vigenere_encrypt(&self, data: &[u8]) -> Vec<u8> {
-        data.iter()
-            .enumerate()
-            .map(|(i, &b)| {
-                if b.is_ascii_alphabetic() {
-                    let key_char = self.key[i % self.key.len()];
-                    let key_shift = if key_char.is_ascii_alphabetic() {
-                        (key_char.to_ascii_uppercase() - b'A') as u8
-                    } else {
-                        key_char % 26
-                    };
-                    
-                    let base = if b.is_ascii_lowercase() { b'a' } else { b'A' };
-                    (((b - base) + key_shift) % 26) + base
-                } else {
-                    b
-                }
-            })
-            .collect()
-    }
-    
-    fn vigenere_decrypt(&self, data: &[u8]) -> Vec<u8> {
-        data.iter()
-            .enumerate()
-            .map(|(i, &b)| {
-                if b.is_ascii_alphabetic() {
-                    let key_char = self.key[i % self.key.len()];
-                    let key_shift = if key_char.is_ascii_alphabetic() {
-                        (key_char.to_ascii_uppercase() - b'A') as u8
-                    } else {
-                        key_char % 26
-                    };
-                    
-                    let base = if b.is_ascii_lowercase() { b'a' } else { b'A' };
-                    (((b - base + 26) - key_shift) % 26) + base
-                } else {
-                    b
-                }
-            })
-            .collect()
-    }
+/// Name of a reference language in the catalog / Nombre de un idioma de referencia en el catálogo
+fn language_name<R, W>(console: &Console<R, W>, language: Language) -> String {
+    let key = format!("language.{:?}", language);
+    console.messages().lookup(&key).unwrap_or(language.name()).to_string()
 }
 
 /// Demo function showing advanced encryption features
 /// Función de demostración que muestra características de cifrado avanzado
-pub fn advanced_encryption_demo() {
-    println!("=== ADVANCED ENCRYPTION DEMO / DEMO DE CIFRADO AVANZADO ===\n");
+pub fn advanced_encryption_demo<R, W: Write>(console: &mut Console<R, W>) -> Result<(), CipherError> {
+    console.say("demo.title", &[])?;
+    writeln!(console)?;
     
     // Test different algorithms / Probar diferentes algoritmos
     let test_message = "This is a secret message for advanced encryption!";
     
     // Caesar cipher demo / Demo de cifrado César
-    println!("1. Caesar Cipher (shift 7) / Cifrado César (desplazamiento 7):");
-    let caesar_manager = EncryptionManager::new(CipherType::Caesar(7), "");
-    let caesar_encrypted = caesar_manager.encrypt(test_message.as_bytes());
-    let caesar_decrypted = caesar_manager.decrypt(&caesar_encrypted);
+    console.say("demo.caesar", &[])?;
+    let caesar_manager = EncryptionManager::new(CipherType::Caesar(7), "")?;
+    let caesar_encrypted = caesar_manager.encrypt(test_message.as_bytes())?;
+    let caesar_decrypted = caesar_manager.decrypt(&caesar_encrypted)?;
     
-    println!("Original: {}", test_message);
-    println!("Encrypted: {}", String::from_utf8_lossy(&caesar_encrypted));
-    println!("Decrypted: {}", String::from_utf8_lossy(&caesar_decrypted));
-    println!();
+    console.say("common.original", &[("text", &test_message)])?;
+    console.say("common.encrypted", &[("text", &String::from_utf8_lossy(&caesar_encrypted))])?;
+    console.say("common.decrypted", &[("text", &String::from_utf8_lossy(&caesar_decrypted))])?;
+    writeln!(console)?;
     
     // XOR cipher demo / Demo de cifrado XOR
-    println!("2. XOR Cipher / Cifrado XOR:");
-    let xor_manager = EncryptionManager::new(CipherType::XOR, "SECRETKEY");
-    let xor_encrypted = xor_manager.encrypt(test_message.as_bytes());
-    let xor_decrypted = xor_manager.decrypt(&xor_encrypted);
+    console.say("demo.xor", &[])?;
+    let xor_manager = EncryptionManager::new(CipherType::XOR, "SECRETKEY")?;
+    let xor_encrypted = xor_manager.encrypt(test_message.as_bytes())?;
+    let xor_decrypted = xor_manager.decrypt(&xor_encrypted)?;
     
-    println!("Original: {}", test_message);
-    println!("Encrypted (hex): {}", bytes_to_hex(&xor_encrypted));
-    println!("Decrypted: {}", String::from_utf8_lossy(&xor_decrypted));
-    println!();
+    console.say("common.original", &[("text", &test_message)])?;
+    console.say("common.encrypted_hex", &[("text", &bytes_to_hex(&xor_encrypted))])?;
+    console.say("common.decrypted", &[("text", &String::from_utf8_lossy(&xor_decrypted))])?;
+    writeln!(console)?;
     
     // Vigenère cipher demo / Demo de cifrado Vigenère
-    println!("3. Vigenère Cipher / Cifrado Vigenère:");
-    let vigenere_manager = EncryptionManager::new(CipherType::Vigenere, "RUST");
-    let vigenere_encrypted = vigenere_manager.encrypt(test_message.as_bytes());
-    let vigenere_decrypted = vigenere_manager.decrypt(&vigenere_encrypted);
+    console.say("demo.vigenere", &[])?;
+    let vigenere_manager = EncryptionManager::new(CipherType::Vigenere, "RUST")?;
+    let vigenere_encrypted = vigenere_manager.encrypt(test_message.as_bytes())?;
+    let vigenere_decrypted = vigenere_manager.decrypt(&vigenere_encrypted)?;
+    
+    console.say("common.original", &[("text", &test_message)])?;
+    console.say("common.key", &[("key", &"RUST")])?;
+    console.say("common.encrypted", &[("text", &String::from_utf8_lossy(&vigenere_encrypted))])?;
+    console.say("common.decrypted", &[("text", &String::from_utf8_lossy(&vigenere_decrypted))])?;
+    writeln!(console)?;
     
-    println!("Original: {}", test_message);
-    println!("Key: RUST");
-    println!("Encrypted: {}", String::from_utf8_lossy(&vigenere_encrypted));
-    println!("Decrypted: {}", String::from_utf8_lossy(&vigenere_decrypted));
-    println!();
+    // ChaCha20-Poly1305 demo / Demo de ChaCha20-Poly1305
+    // Fixed demo key and nonce; real use needs a fresh nonce per message
+    // Clave y nonce fijos de demo; el uso real necesita un nonce nuevo por mensaje
+    console.say("demo.chacha", &[])?;
+    let aead_key = SecretKey::from((0..32).collect::<Vec<u8>>());
+    let aead_manager = EncryptionManager::new(CipherType::ChaCha20Poly1305 { key: aead_key.duplicate(), nonce: [0; 12] }, "")?;
+    let mut aead_encrypted = aead_manager.encrypt(test_message.as_bytes())?;
+    let aead_decrypted = aead_manager.decrypt(&aead_encrypted)?;
+    
+    console.say("common.original", &[("text", &test_message)])?;
+    console.say("demo.encrypted_tag", &[("text", &bytes_to_hex(&aead_encrypted))])?;
+    console.say("common.decrypted", &[("text", &String::from_utf8_lossy(&aead_decrypted))])?;
+    aead_encrypted[0] ^= 1;
+    match aead_manager.decrypt(&aead_encrypted) {
+        Ok(_) => console.say("demo.tamper_accepted", &[])?,
+        Err(e) => console.say("demo.tamper_rejected", &[("error", &e)])?,
+    }
+    writeln!(console)?;
+    
+    // AES-GCM demo / Demo de AES-GCM
+    console.say("demo.gcm", &[])?;
+    let gcm_manager = EncryptionManager::new(CipherType::AesGcm { key: aead_key, nonce: [0; 12] }, "")?;
+    let gcm_encrypted = gcm_manager.encrypt(test_message.as_bytes())?;
+    let gcm_decrypted = gcm_manager.decrypt(&gcm_encrypted)?;
+    
+    console.say("common.original", &[("text", &test_message)])?;
+    console.say("demo.encrypted_tag", &[("text", &bytes_to_hex(&gcm_encrypted))])?;
+    console.say("common.decrypted", &[("text", &String::from_utf8_lossy(&gcm_decrypted))])?;
+    writeln!(console)?;
     
     // File encryption demo / Demo de cifrado de archivos
-    println!("4. File Encryption Demo / Demo de Cifrado de Archivos:");
+    console.say("demo.file", &[])?;
     
     // Create a sample file / Crear un archivo de muestra
+    let dir = std::env::temp_dir();
+    let [sample, encrypted, decrypted] = ["sample.txt", "sample_encrypted.txt", "sample_decrypted.txt"]
+        .map(|name| dir.join(format!("xyz_example2_{}", name)).to_string_lossy().into_owned());
     let sample_content = "This is a sample file content for encryption testing.\nLine 2 of the file.\nEnd of file.";
-    if let Err(e) = fs::write("sample.txt", sample_content) {
-        println!("Error creating sample file: {}", e);
-        return;
-    }
+    fs::write(&sample, sample_content)?;
     
     // Encrypt the file / Cifrar el archivo
-    let file_manager = EncryptionManager::new(CipherType::XOR, "FILEKEY123");
-    match file_manager.encrypt_file("sample.txt", "sample_encrypted.txt") {
-        Ok(_) => println!("File encryption successful / Cifrado de archivo exitoso"),
-        Err(e) => println!("File encryption failed: {}", e),
+    let file_manager = EncryptionManager::new(CipherType::XOR, "FILEKEY123")?;
+    match file_manager.encrypt_file(&sample, &encrypted) {
+        Ok(_) => console.say("demo.file_encrypted", &[("input", &sample), ("output", &encrypted)])?,
+        Err(e) => console.say("demo.file_encrypt_failed", &[("error", &e)])?,
     }
     
     // Decrypt the file / Descifrar el archivo
-    match file_manager.decrypt_file("sample_encrypted.txt", "sample_decrypted.txt") {
-        Ok(_) => println!("File decryption successful / Descifrado de archivo exitoso"),
-        Err(e) => println!("File decryption failed: {}", e),
+    match file_manager.decrypt_file(&encrypted, &decrypted) {
+        Ok(_) => console.say("demo.file_decrypted", &[("input", &encrypted), ("output", &decrypted)])?,
+        Err(e) => console.say("demo.file_decrypt_failed", &[("error", &e)])?,
     }
     
     // Verify the decryption / Verificar el descifrado
-    if let Ok(decrypted_content) = fs::read_to_string("sample_decrypted.txt") {
-        println!("Decrypted file content matches original: {}", 
-                 decrypted_content == sample_content);
+    if let Ok(decrypted_content) = fs::read_to_string(&decrypted) {
+        console.say("demo.file_matches", &[("matches", &(decrypted_content == sample_content))])?;
     }
     
     // Clean up demo files / Limpiar archivos de demo
-    let _ = fs::remove_file("sample.txt");
-    let _ = fs::remove_file("sample_encrypted.txt");
-    let _ = fs::remove_file("sample_decrypted.txt");
+    for path in [sample, encrypted, decrypted] {
+        let _ = fs::remove_file(path);
+    }
+    writeln!(console)?;
+    
+    // Enigma demo: the opening of a real message from 7 July 1941
+    // Demo de Enigma: el comienzo de un mensaje real del 7 de julio de 1941
+    console.say("demo.enigma", &[])?;
+    let settings: EnigmaSettings = "II IV V B BUL BLA AV BS CG DL FU HZ IN KM OW RX".parse()?;
+    let enigma = EnigmaCipher::new(settings.clone())?;
+    let intercepted = "EDPUD NRGYS ZRCXN UYTPO MRMBO";
+    
+    console.say("demo.enigma_settings", &[("settings", &settings)])?;
+    console.say("demo.enigma_intercepted", &[("text", &intercepted)])?;
+    console.say("common.decrypted", &[("text", &String::from_utf8_lossy(&enigma.decrypt(intercepted.as_bytes())?))])?;
+    console.say("demo.enigma_trace", &[])?;
+    for step in enigma.trace(&intercepted.as_bytes()[..3]) {
+        writeln!(console, "  {}", step)?;
+    }
+    Ok(())
+}
+
+/// Ask until the answer is a number from 1 to `count`, returning it zero-based; end of input stops asking
+/// Preguntar hasta que la respuesta sea un número de 1 a `count`, devolviéndolo desde cero; el fin de la entrada
+/// deja de preguntar
+fn ask_choice<R: BufRead, W: Write>(
+    console: &mut Console<R, W>,
+    key: &str,
+    invalid_key: &str,
+    count: usize,
+) -> Result<usize, CipherError> {
+    loop {
+        match console.ask(key, &[("count", &count)])?.parse::<usize>() {
+            Ok(n) if (1..=count).contains(&n) => return Ok(n - 1),
+            _ => console.say(invalid_key, &[])?,
+        }
+    }
 }
 
 /// Interactive CLI for encryption operations
 /// CLI interactivo para operaciones de cifrado
-pub fn interactive_encryption_cli() {
-    println!("=== INTERACTIVE ENCRYPTION CLI / CLI DE CIFRADO INTERACTIVO ===");
-    println!("Available algorithms / Algoritmos disponibles:");
-    println!("1. Caesar Cipher / Cifrado César");
-    println!("2. XOR Cipher / Cifrado XOR");
-    println!("3. Vigenère Cipher / Cifrado Vigenère");
-    println!();
-    
-    print!("Select algorithm (1-3) / Selecciona algoritmo (1-3): ");
-    io::stdout().flush().unwrap();
-    
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    
-    let algorithm = match input.trim() {
-        "1" => {
-            print!("Enter shift value (1-25) / Ingresa valor de desplazamiento (1-25): ");
-            io::stdout().flush().unwrap();
-            input.clear();
-            io::stdin().read_line(&mut input).unwrap();
-            let shift: u8 = input.trim().parse().unwrap_or(3);
-            CipherType::Caesar(shift)
-        },
-        "2" => CipherType::XOR,
-        "3" => CipherType::Vigenere,
-        _ => {
-            println!("Invalid selection, using Caesar cipher / Selección inválida, usando cifrado César");
-            CipherType::Caesar(3)
+pub fn interactive_encryption_cli<R: BufRead, W: Write>(console: &mut Console<R, W>) -> Result<(), CipherError> {
+    let registry = CipherRegistry::with_defaults();
+    let entries = registry.entries();
+    
+    console.say("encrypt.title", &[])?;
+    console.say("encrypt.algorithms", &[])?;
+    for (i, entry) in entries.iter().enumerate() {
+        let label = algorithm_label(console, entry.name, entry.description);
+        writeln!(console, "{}. {}", i + 1, label)?;
+    }
+    writeln!(console)?;
+    
+    let entry = &entries[ask_choice(console, "encrypt.select_algorithm", "encrypt.invalid_algorithm", entries.len())?];
+    
+    // Offer generated key material instead of a typed key
+    // Ofrecer material de clave generado en lugar de una clave escrita
+    let mut rng = None;
+    if entry.schema.iter().any(|spec| spec.kind != ParamKind::Data) && console.ask_yes_no("encrypt.random_key")? {
+        rng = Some(SecureRandom::new()?);
+    }
+    
+    let mut params = CipherParams::new();
+    for spec in entry.schema {
+        // A shift is drawn for the alphabet answered before it / El desplazamiento se elige para el alfabeto respondido antes
+        let generated = rng.as_mut().and_then(|rng| match (spec.kind, params.get("alphabet").map(Alphabet::parse)) {
+            (ParamKind::Shift, Some(Ok(alphabet))) => Some(rng.shift_below(alphabet.len() as u32).to_string()),
+            _ => rng.param_value(spec.kind),
+        });
+        let value = match generated {
+            Some(value) => {
+                console.say("encrypt.generated", &[("name", &spec.name), ("value", &value)])?;
+                value
+            }
+            None => {
+                let prompt = param_prompt(console, entry.name, spec);
+                console.prompt(&format!("{} ", prompt))?
+            }
+        };
+        params.set(spec.name, &value);
+    }
+    // Building checks the shift against the chosen alphabet before asking for the message
+    // Construir valida el desplazamiento con el alfabeto elegido antes de pedir el mensaje
+    let cipher = registry.build(entry.name, &params)?;
+    
+    let message = console.ask("encrypt.message", &[])?;
+    
+    // Ciphertext is binary, so show it through a text encoding
+    // El texto cifrado es binario, así que se muestra con una codificación de texto
+    let encodings = encodings();
+    console.say("encrypt.encodings", &[])?;
+    for (i, encoding) in encodings.iter().enumerate() {
+        writeln!(console, "{}. {}", i + 1, encoding.name())?;
+    }
+    let encoding = encodings[ask_choice(console, "encrypt.select_encoding", "encrypt.invalid_encoding", encodings.len())?];
+    
+    let manager = EncryptionManager::with_cipher(cipher);
+    let encrypted = manager.encrypt(message.as_bytes())?;
+    let encoded = encoding.encode(&encrypted)?;
+    
+    // Decrypt from the printed text to show it parses back
+    // Descifrar desde el texto impreso para mostrar que se puede leer de vuelta
+    let decrypted = manager.decrypt(&encoding.decode(&encoded)?)?;
+    
+    writeln!(console)?;
+    console.say("encrypt.results", &[("algorithm", &manager.algorithm_name())])?;
+    console.say("common.original", &[("text", &message)])?;
+    console.say("encrypt.encrypted", &[("encoding", &encoding.name()), ("text", &encoded)])?;
+    console.say("common.decrypted", &[("text", &String::from_utf8_lossy(&decrypted))])?;
+    
+    if entry.name == EnigmaCipher::NAME && console.ask_yes_no("encrypt.trace")? {
+        for step in EnigmaCipher::from_params(&params)?.trace(message.as_bytes()) {
+            writeln!(console, "  {}", step)?;
         }
+    }
+    Ok(())
+}
+
+/// Hash a file chosen interactively, streaming it from disk
+/// Calcular el hash de un archivo elegido interactivamente, leyéndolo por partes
+pub fn hash_file_cli<R: BufRead, W: Write>(console: &mut Console<R, W>) -> Result<(), CipherError> {
+    console.say("hash.title", &[])?;
+    writeln!(console, "1. {}", Sha224::NAME)?;
+    writeln!(console, "2. {}", Sha256::NAME)?;
+    writeln!(console, "3. {}", Sha384::NAME)?;
+    writeln!(console, "4. {}", Sha512::NAME)?;
+    writeln!(console)?;
+    
+    let choice = ask_choice(console, "hash.select", "hash.invalid", 4)?;
+    let path = console.ask("hash.path", &[])?;
+    
+    let (name, digest) = match choice {
+        0 => (Sha224::NAME, hash_file::<Sha224>(&path)?),
+        1 => (Sha256::NAME, hash_file::<Sha256>(&path)?),
+        2 => (Sha384::NAME, hash_file::<Sha384>(&path)?),
+        _ => (Sha512::NAME, hash_file::<Sha512>(&path)?),
     };
     
-    print!("Enter encryption key / Ingresa clave de cifrado: ");
-    io::stdout().flush().unwrap();
-    input.clear();
-    io::stdin().read_line(&mut input).unwrap();
-    let key = input.trim().to_string();
-    
-    print!("Enter message to encrypt / Ingresa mensaje a cifrar: ");
-    io::stdout().flush().unwrap();
-    input.clear();
-    io::stdin().read_line(&mut input).unwrap();
-    let message = input.trim().to_string();
-    
-    let manager = EncryptionManager::new(algorithm, &key);
-    let encrypted = manager.encrypt(message.as_bytes());
-    let decrypted = manager.decrypt(&encrypted);
-    
-    println!("\nResults / Resultados:");
-    println!("Original: {}", message);
-    println!("Encrypted: {}", String::from_utf8_lossy(&encrypted));
-    println!("Decrypted: {}", String::from_utf8_lossy(&decrypted));
+    writeln!(console, "{} ({}): {}", name, path, bytes_to_hex(&digest))?;
+    Ok(())
 }
 
-fn bytes_to_hex(bytes: &[u8]) -> String {
-    bytes.iter()
-        .map(|b| format!("{:02x}", b))
-        .collect::<Vec<String>>()
-        .join("")
+/// Break a classical cipher without its key / Romper un cifrado clásico sin su clave
+pub fn cryptanalysis_cli<R: BufRead, W: Write>(console: &mut Console<R, W>) -> Result<(), CipherError> {
+    console.say("crack.title", &[])?;
+    for (i, key) in ["crack.caesar", "crack.vigenere", "crack.xor"].iter().enumerate() {
+        let label = console.text(key, &[]);
+        writeln!(console, "{}. {}", i + 1, label)?;
+    }
+    writeln!(console)?;
+    
+    match console.ask("crack.select", &[])?.as_str() {
+        "1" => crack_caesar_cli(console),
+        "2" => crack_vigenere_cli(console),
+        "3" => crack_xor_cli(console),
+        _ => console.say("crack.invalid", &[]),
+    }
 }
 
-#[cfg(test)]
-mod advanced_tests {
-    use super::*;
-
-    #[test]
-    fn test_encryption_manager_caesar() {
-        let manager = EncryptionManager::new(CipherType::Caesar(5), "");
-        let original = b"Hello World";
-        let encrypted = manager.encrypt(original);
-        let decrypted = manager.decrypt(&encrypted);
-        assert_eq!(original, &decrypted[..]);
+// Rank every shift by frequency analysis / Clasificar cada desplazamiento por análisis de frecuencias
+fn crack_caesar_cli<R: BufRead, W: Write>(console: &mut Console<R, W>) -> Result<(), CipherError> {
+    let ciphertext = console.ask("crack.ciphertext", &[])?;
+    
+    let candidates = crack_caesar(&ciphertext, 5)?;
+    writeln!(console)?;
+    for (rank, candidate) in candidates.iter().enumerate() {
+        let language = language_name(console, candidate.language);
+        console.say(
+            "crack.candidate",
+            &[
+                ("rank", &(rank + 1)),
+                ("shift", &candidate.shift),
+                ("language", &language),
+                ("chi", &format!("{:.2}", candidate.chi_squared)),
+                ("confidence", &format!("{:.1}", candidate.confidence * 100.0)),
+            ],
+        )?;
+        writeln!(console, "   {}", candidate.plaintext)?;
     }
+    Ok(())
+}
 
-    #[test]
-    fn test_encryption_manager_xor() {
-        let manager = EncryptionManager::new(CipherType::XOR, "testkey");
-        let original = b"Secret message for testing";
-        let encrypted = manager.encrypt(original);
-        let decrypted = manager.decrypt(&encrypted);
-        assert_eq!(original, &decrypted[..]);
+// Recover the key length, then each key letter / Recuperar la longitud de la clave y luego cada letra
+fn crack_vigenere_cli<R: BufRead, W: Write>(console: &mut Console<R, W>) -> Result<(), CipherError> {
+    let ciphertext = console.ask("crack.ciphertext", &[])?;
+    
+    let solution = crack_vigenere(&ciphertext, MAX_KEY_LEN)?;
+    writeln!(console)?;
+    console.say("crack.key_lengths", &[])?;
+    for candidate in solution.key_lengths.iter().take(3) {
+        let line = console.text(
+            "crack.key_length",
+            &[
+                ("length", &format!("{:>2}", candidate.length)),
+                ("ic", &format!("{:.4}", candidate.index_of_coincidence)),
+                ("votes", &candidate.kasiski_votes),
+            ],
+        );
+        writeln!(console, "  {}", line)?;
     }
+    let language = language_name(console, solution.language);
+    console.say("common.language", &[("language", &language)])?;
+    console.say("common.key", &[("key", &solution.key)])?;
+    console.say("common.plaintext", &[("text", &solution.plaintext)])?;
+    Ok(())
+}
 
-    #[test]
-    fn test_encryption_manager_vigenere() {
-        let manager = EncryptionManager::new(CipherType::Vigenere, "KEY");
-        let original = b"Hello World";
-        let encrypted = manager.encrypt(original);
-        let decrypted = manager.decrypt(&encrypted);
-        assert_eq!(original, &decrypted[..]);
+// Guess the key size, then solve each key byte / Adivinar el tamaño de clave y resolver cada byte
+fn crack_xor_cli<R: BufRead, W: Write>(console: &mut Console<R, W>) -> Result<(), CipherError> {
+    let input = console.ask("crack.ciphertext_binary", &[])?;
+    let ciphertext = parse_ciphertext(&input)?;
+    
+    let solution = crack_repeating_xor(&ciphertext, MAX_KEY_SIZE)?;
+    writeln!(console)?;
+    console.say("crack.key_sizes", &[])?;
+    for candidate in solution.key_sizes.iter().take(3) {
+        let line = console.text(
+            "crack.key_size",
+            &[("size", &format!("{:>2}", candidate.size)), ("bits", &format!("{:.3}", candidate.distance))],
+        );
+        writeln!(console, "  {}", line)?;
     }
+    let language = language_name(console, solution.language);
+    console.say("common.language", &[("language", &language)])?;
+    console.say("common.key_hex", &[("key", &bytes_to_hex(&solution.key))])?;
+    console.say("common.key", &[("key", &String::from_utf8_lossy(&solution.key))])?;
+    console.say("common.plaintext", &[("text", &String::from_utf8_lossy(&solution.plaintext))])?;
+    Ok(())
+}
 
-    #[test]
-    fn test_different_algorithms_produce_different_results() {
-        let original = b"Test message";
-        
-        let caesar = EncryptionManager::new(CipherType::Caesar(3), "");
-        let xor = EncryptionManager::new(CipherType::XOR, "key");
-        let vigenere = EncryptionManager::new(CipherType::Vigenere, "KEY");
-        
-        let caesar_encrypted = caesar.encrypt(original);
-        let xor_encrypted = xor.encrypt(original);
-        let vigenere_encrypted = vigenere.encrypt(original);
-        
-        // All should be different from original
-        assert_ne!(original, &caesar_encrypted[..]);
-        assert_ne!(original, &xor_encrypted[..]);
-        assert_ne!(original, &vigenere_encrypted[..]);
-        
-        // All should be different from each other
-        assert_ne!(caesar_encrypted, xor_encrypted);
-        assert_ne!(caesar_encrypted, vigenere_encrypted);
-        assert_ne!(xor_encrypted, vigenere_encrypted);
+/// Time every cipher and encoding, or the chosen ones, printing each row as it is measured
+/// Medir cada cifrado y codificación, o los elegidos, mostrando cada fila al medirla
+pub fn benchmark_cli<R: BufRead, W: Write>(console: &mut Console<R, W>) -> Result<(), CipherError> {
+    console.say("bench.title", &[])?;
+    let sizes = console.ask("bench.sizes", &[])?;
+    let names = console.ask("bench.targets", &[])?;
+    
+    let mut config = BenchConfig::default();
+    if !sizes.is_empty() {
+        config.sizes = bench::parse_sizes(&sizes)?;
     }
-}
\ No newline at end of file
+    let targets = bench::select(bench::targets()?, &names)?;
+    writeln!(console)?;
+    let header = bench_header(console.messages());
+    writeln!(console, "{}", header)?;
+    bench::run(&targets, &config, |measurement| {
+        let row = bench_row(console.messages(), measurement);
+        Ok(writeln!(console, "{}", row)?)
+    })?;
+    Ok(())
+}
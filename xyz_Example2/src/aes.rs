@@ -0,0 +1,306 @@
+// AES Block Cipher (FIPS-197) with 128, 192 and 256-bit Keys
+// Cifrado por Bloques AES (FIPS-197) con Claves de 128, 192 y 256 bits
+
+use crate::error::CipherError;
+use crate::secret::zeroize;
+
+pub const BLOCK_LEN: usize = 16;
+
+// === S-BOX GENERATION / GENERACIÓN DE S-BOX ===
+
+/// Multiply in GF(2^8) modulo x^8 + x^4 + x^3 + x + 1
+/// Multiplicar en GF(2^8) módulo x^8 + x^4 + x^3 + x + 1
+const fn gmul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80;
+        a <<= 1;
+        if carry != 0 {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// S-box built from the multiplicative inverse plus the affine transform
+/// S-box construida con el inverso multiplicativo más la transformación afín
+const fn build_sbox() -> [u8; 256] {
+    let mut sbox = [0u8; 256];
+    let mut x = 0usize;
+    while x < 256 {
+        // Brute-force inverse; 0 maps to 0 / Inverso por fuerza bruta; 0 va a 0
+        let mut inverse = 0u8;
+        let mut y = 1usize;
+        while y < 256 {
+            if gmul(x as u8, y as u8) == 1 {
+                inverse = y as u8;
+            }
+            y += 1;
+        }
+        let b = inverse;
+        sbox[x] = b ^ b.rotate_left(1) ^ b.rotate_left(2) ^ b.rotate_left(3) ^ b.rotate_left(4) ^ 0x63;
+        x += 1;
+    }
+    sbox
+}
+
+const fn invert_sbox(sbox: &[u8; 256]) -> [u8; 256] {
+    let mut inverse = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        inverse[sbox[i] as usize] = i as u8;
+        i += 1;
+    }
+    inverse
+}
+
+const SBOX: [u8; 256] = build_sbox();
+const INV_SBOX: [u8; 256] = invert_sbox(&SBOX);
+
+// === KEY SCHEDULE AND ROUNDS / PROGRAMA DE CLAVES Y RONDAS ===
+
+/// AES with an expanded key schedule; the key size picks AES-128/192/256
+/// AES con el programa de claves expandido; el tamaño de la clave elige AES-128/192/256
+///
+/// Round keys live inline and are zeroed on drop; copy only through `duplicate`.
+/// Las claves de ronda viven en línea y se ponen a cero al liberarse; copiar solo con `duplicate`.
+pub struct Aes {
+    round_keys: [[u8; BLOCK_LEN]; MAX_ROUNDS + 1],
+    rounds: usize,
+}
+
+/// AES-256 uses 14 rounds / AES-256 usa 14 rondas
+const MAX_ROUNDS: usize = 14;
+
+impl Aes {
+    /// Key must be 16, 24 or 32 bytes / La clave debe tener 16, 24 o 32 bytes
+    pub fn new(key: &[u8]) -> Result<Self, CipherError> {
+        let nk = match key.len() {
+            16 | 24 | 32 => key.len() / 4,
+            0 => return Err(CipherError::EmptyKey),
+            n => {
+                return Err(CipherError::InvalidKey(format!(
+                    "AES key must be 16, 24 or 32 bytes, got {}",
+                    n
+                )))
+            }
+        };
+        let rounds = nk + 6;
+
+        // Sized up front so `push` never leaves a stale copy behind / Reservado de antemano para que `push` no deje copias viejas
+        let mut words: Vec<[u8; 4]> = Vec::with_capacity(4 * (rounds + 1));
+        words.extend(key.chunks(4).map(|w| [w[0], w[1], w[2], w[3]]));
+        let mut rcon = 1u8;
+        for i in nk..4 * (rounds + 1) {
+            let mut temp = words[i - 1];
+            if i % nk == 0 {
+                temp.rotate_left(1);
+                temp = temp.map(|b| SBOX[b as usize]);
+                temp[0] ^= rcon;
+                rcon = gmul(rcon, 2);
+            } else if nk > 6 && i % nk == 4 {
+                temp = temp.map(|b| SBOX[b as usize]);
+            }
+            let prev = words[i - nk];
+            words.push([prev[0] ^ temp[0], prev[1] ^ temp[1], prev[2] ^ temp[2], prev[3] ^ temp[3]]);
+        }
+
+        let mut aes = Self { round_keys: [[0u8; BLOCK_LEN]; MAX_ROUNDS + 1], rounds };
+        for (rk, w) in aes.round_keys.iter_mut().zip(words.chunks(4)) {
+            for (i, word) in w.iter().enumerate() {
+                rk[4 * i..4 * i + 4].copy_from_slice(word);
+            }
+        }
+        // The expanded words are the key too / Las palabras expandidas también son la clave
+        zeroize(words.as_flattened_mut());
+        Ok(aes)
+    }
+
+    /// Number of rounds (10, 12 or 14) / Número de rondas (10, 12 o 14)
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// A second copy of the key schedule; deliberately not `Clone`
+    /// Una segunda copia del programa de claves; a propósito no es `Clone`
+    pub fn duplicate(&self) -> Self {
+        Self { round_keys: self.round_keys, rounds: self.rounds }
+    }
+
+    /// Encrypt one 16-byte block in place / Cifrar un bloque de 16 bytes en su lugar
+    pub fn encrypt_block(&self, block: &mut [u8; BLOCK_LEN]) {
+        add_round_key(block, &self.round_keys[0]);
+        for round in 1..self.rounds() {
+            sub_bytes(block, &SBOX);
+            shift_rows(block);
+            mix_columns(block);
+            add_round_key(block, &self.round_keys[round]);
+        }
+        sub_bytes(block, &SBOX);
+        shift_rows(block);
+        add_round_key(block, &self.round_keys[self.rounds()]);
+    }
+
+    /// Decrypt one 16-byte block in place / Descifrar un bloque de 16 bytes en su lugar
+    pub fn decrypt_block(&self, block: &mut [u8; BLOCK_LEN]) {
+        add_round_key(block, &self.round_keys[self.rounds()]);
+        for round in (1..self.rounds()).rev() {
+            inv_shift_rows(block);
+            sub_bytes(block, &INV_SBOX);
+            add_round_key(block, &self.round_keys[round]);
+            inv_mix_columns(block);
+        }
+        inv_shift_rows(block);
+        sub_bytes(block, &INV_SBOX);
+        add_round_key(block, &self.round_keys[0]);
+    }
+}
+
+impl Drop for Aes {
+    fn drop(&mut self) {
+        zeroize(self.round_keys.as_flattened_mut());
+    }
+}
+
+// The state is stored column by column, as in FIPS-197
+// El estado se guarda columna por columna, como en FIPS-197
+
+fn add_round_key(block: &mut [u8; BLOCK_LEN], key: &[u8; BLOCK_LEN]) {
+    for (b, k) in block.iter_mut().zip(key) {
+        *b ^= k;
+    }
+}
+
+fn sub_bytes(block: &mut [u8; BLOCK_LEN], table: &[u8; 256]) {
+    for b in block.iter_mut() {
+        *b = table[*b as usize];
+    }
+}
+
+fn shift_rows(block: &mut [u8; BLOCK_LEN]) {
+    let s = *block;
+    for col in 0..4 {
+        for row in 0..4 {
+            block[4 * col + row] = s[4 * ((col + row) % 4) + row];
+        }
+    }
+}
+
+fn inv_shift_rows(block: &mut [u8; BLOCK_LEN]) {
+    let s = *block;
+    for col in 0..4 {
+        for row in 0..4 {
+            block[4 * ((col + row) % 4) + row] = s[4 * col + row];
+        }
+    }
+}
+
+fn mix_columns(block: &mut [u8; BLOCK_LEN]) {
+    for col in block.chunks_exact_mut(4) {
+        let [a0, a1, a2, a3] = [col[0], col[1], col[2], col[3]];
+        col[0] = gmul(a0, 2) ^ gmul(a1, 3) ^ a2 ^ a3;
+        col[1] = a0 ^ gmul(a1, 2) ^ gmul(a2, 3) ^ a3;
+        col[2] = a0 ^ a1 ^ gmul(a2, 2) ^ gmul(a3, 3);
+        col[3] = gmul(a0, 3) ^ a1 ^ a2 ^ gmul(a3, 2);
+    }
+}
+
+fn inv_mix_columns(block: &mut [u8; BLOCK_LEN]) {
+    for col in block.chunks_exact_mut(4) {
+        let [a0, a1, a2, a3] = [col[0], col[1], col[2], col[3]];
+        col[0] = gmul(a0, 14) ^ gmul(a1, 11) ^ gmul(a2, 13) ^ gmul(a3, 9);
+        col[1] = gmul(a0, 9) ^ gmul(a1, 14) ^ gmul(a2, 11) ^ gmul(a3, 13);
+        col[2] = gmul(a0, 13) ^ gmul(a1, 9) ^ gmul(a2, 14) ^ gmul(a3, 11);
+        col[3] = gmul(a0, 11) ^ gmul(a1, 13) ^ gmul(a2, 9) ^ gmul(a3, 14);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex_to_bytes;
+
+    fn block(hex: &str) -> [u8; BLOCK_LEN] {
+        hex_to_bytes(hex).unwrap().try_into().unwrap()
+    }
+
+    fn check_known_answer(key: &str, plaintext: &str, ciphertext: &str) {
+        let aes = Aes::new(&hex_to_bytes(key).unwrap()).unwrap();
+        let mut state = block(plaintext);
+        aes.encrypt_block(&mut state);
+        assert_eq!(state, block(ciphertext));
+        aes.decrypt_block(&mut state);
+        assert_eq!(state, block(plaintext));
+    }
+
+    #[test]
+    fn test_drop_wipes_round_keys() {
+        let mut slot = std::mem::MaybeUninit::new(Aes::new(&[0x5a; 32]).unwrap());
+        // SAFETY: the slot was initialized above and is dropped exactly once; the round
+        // keys are plain bytes, so reading them after the drop reads initialized memory.
+        // SEGURIDAD: el hueco se inicializó arriba y se libera una sola vez; las claves de
+        // ronda son bytes simples, así que leerlas tras liberar lee memoria inicializada.
+        let round_keys = unsafe {
+            slot.assume_init_drop();
+            std::ptr::addr_of!((*slot.as_ptr()).round_keys).read()
+        };
+        assert!(round_keys.as_flattened().iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_sbox_spot_values() {
+        assert_eq!(SBOX[0x00], 0x63);
+        assert_eq!(SBOX[0x53], 0xed);
+        assert_eq!(SBOX[0xff], 0x16);
+        assert_eq!(INV_SBOX[0x63], 0x00);
+    }
+
+    #[test]
+    fn test_fips197_appendix_b() {
+        check_known_answer(
+            "2b7e151628aed2a6abf7158809cf4f3c",
+            "3243f6a8885a308d313198a2e0370734",
+            "3925841d02dc09fbdc118597196a0b32",
+        );
+    }
+
+    #[test]
+    fn test_fips197_appendix_c_aes128() {
+        check_known_answer(
+            "000102030405060708090a0b0c0d0e0f",
+            "00112233445566778899aabbccddeeff",
+            "69c4e0d86a7b0430d8cdb78070b4c55a",
+        );
+    }
+
+    #[test]
+    fn test_fips197_appendix_c_aes192() {
+        check_known_answer(
+            "000102030405060708090a0b0c0d0e0f1011121314151617",
+            "00112233445566778899aabbccddeeff",
+            "dda97ca4864cdfe06eaf70a0ec0d7191",
+        );
+    }
+
+    #[test]
+    fn test_fips197_appendix_c_aes256() {
+        check_known_answer(
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+            "00112233445566778899aabbccddeeff",
+            "8ea2b7ca516745bfeafc49904b496089",
+        );
+    }
+
+    #[test]
+    fn test_round_counts_and_key_validation() {
+        assert_eq!(Aes::new(&[0; 16]).unwrap().rounds(), 10);
+        assert_eq!(Aes::new(&[0; 24]).unwrap().rounds(), 12);
+        assert_eq!(Aes::new(&[0; 32]).unwrap().rounds(), 14);
+        assert!(matches!(Aes::new(&[0; 20]), Err(CipherError::InvalidKey(_))));
+        assert!(matches!(Aes::new(&[]), Err(CipherError::EmptyKey)));
+    }
+}
@@ -0,0 +1,191 @@
+// Alphabets for the Classical Ciphers: ASCII, Spanish, Greek and Custom
+// Alfabetos para los Cifrados Clásicos: ASCII, Español, Griego y Personalizado
+
+use crate::error::CipherError;
+
+const ASCII_LETTERS: &str = "abcdefghijklmnopqrstuvwxyz";
+const SPANISH_LETTERS: &str = "abcdefghijklmnñopqrstuvwxyz";
+// Final sigma (ς) is left out, so it passes through unchanged / La sigma final (ς) se omite y no cambia
+const GREEK_LETTERS: &str = "αβγδεζηθικλμνξοπρστυφχψω";
+
+/// Ordered set of letters that shift ciphers rotate through
+/// Conjunto ordenado de letras por el que rotan los cifrados de desplazamiento
+///
+/// Letters are stored in lowercase. When every letter has its own single-character uppercase form,
+/// that form is accepted too and the case is kept; characters outside the alphabet pass through.
+/// Las letras se guardan en minúsculas. Si cada letra tiene su propia forma mayúscula de un solo
+/// carácter, también se acepta y se conserva el caso; los caracteres fuera del alfabeto no cambian.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alphabet {
+    name: String,
+    letters: Vec<char>,
+    fold_case: bool, // Uppercase forms map onto the letters / Las mayúsculas se asignan a las letras
+}
+
+// Uppercase form of a letter, if it is a single different character
+// Forma mayúscula de una letra, si es un único carácter distinto
+fn upper_form(c: char) -> Option<char> {
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(u), None) if u != c => Some(u),
+        _ => None,
+    }
+}
+
+impl Alphabet {
+    fn from_letters(name: &str, letters: &str) -> Self {
+        let letters: Vec<char> = letters.chars().collect();
+        // Case folding must be unambiguous: uppercase forms may not collide with each other or
+        // with the letters themselves. Otherwise the alphabet is case-sensitive.
+        // El plegado de mayúsculas debe ser inequívoco: las formas mayúsculas no pueden chocar
+        // entre sí ni con las propias letras. Si no, el alfabeto distingue mayúsculas.
+        let uppers: Vec<char> = letters.iter().filter_map(|&c| upper_form(c)).collect();
+        let fold_case = uppers.iter().enumerate().all(|(i, u)| !letters.contains(u) && !uppers[..i].contains(u));
+        Self { name: name.to_string(), letters, fold_case }
+    }
+
+    /// The 26 English letters / Las 26 letras inglesas
+    pub fn ascii() -> Self {
+        Self::from_letters("ASCII", ASCII_LETTERS)
+    }
+
+    /// The 27 letters of Spanish, with Ñ after N; accented vowels pass through
+    /// Las 27 letras del español, con Ñ tras la N; las vocales acentuadas no cambian
+    pub fn spanish() -> Self {
+        Self::from_letters("Spanish / Español", SPANISH_LETTERS)
+    }
+
+    /// The 24 letters of modern Greek / Las 24 letras del griego moderno
+    pub fn greek() -> Self {
+        Self::from_letters("Greek / Griego", GREEK_LETTERS)
+    }
+
+    /// Any set of distinct characters, in order / Cualquier conjunto de caracteres distintos, en orden
+    pub fn custom(letters: &str) -> Result<Self, CipherError> {
+        let invalid = || CipherError::InvalidParam { name: "alphabet".to_string(), value: letters.to_string() };
+        let chars: Vec<char> = letters.chars().collect();
+        if chars.len() < 2 || chars.iter().enumerate().any(|(i, c)| chars[..i].contains(c)) {
+            return Err(invalid());
+        }
+        Ok(Self::from_letters("Custom / Personalizado", letters))
+    }
+
+    /// Built-in name (`ascii`, `spanish`, `greek`, empty for ASCII) or the letters of a custom one
+    /// Nombre incluido (`ascii`, `spanish`, `greek`, vacío para ASCII) o las letras de uno personalizado
+    pub fn parse(spec: &str) -> Result<Self, CipherError> {
+        match spec.trim().to_lowercase().as_str() {
+            "" | "ascii" | "english" | "inglés" | "ingles" => Ok(Self::ascii()),
+            "spanish" | "español" | "espanol" => Ok(Self::spanish()),
+            "greek" | "griego" => Ok(Self::greek()),
+            _ => Self::custom(spec.trim()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn len(&self) -> usize {
+        self.letters.len()
+    }
+
+    /// Always false: every alphabet has at least two letters / Siempre falso: todo alfabeto tiene al menos dos letras
+    pub fn is_empty(&self) -> bool {
+        self.letters.is_empty()
+    }
+
+    /// Exactly `a`-`z`, which the byte-level ciphers already handle
+    /// Exactamente `a`-`z`, que los cifrados a nivel de byte ya manejan
+    pub fn is_ascii(&self) -> bool {
+        self.letters.iter().copied().eq(ASCII_LETTERS.chars())
+    }
+
+    /// Position of `c` and whether it was the uppercase form
+    /// Posición de `c` y si estaba en su forma mayúscula
+    pub fn index_of(&self, c: char) -> Option<(usize, bool)> {
+        if let Some(i) = self.letters.iter().position(|&l| l == c) {
+            return Some((i, false));
+        }
+        if !self.fold_case {
+            return None;
+        }
+        self.letters.iter().position(|&l| upper_form(l) == Some(c)).map(|i| (i, true))
+    }
+
+    /// Letter at `index`, in uppercase if asked and available
+    /// Letra en `index`, en mayúscula si se pide y existe
+    pub fn letter(&self, index: usize, upper: bool) -> char {
+        let c = self.letters[index % self.letters.len()];
+        if upper {
+            upper_form(c).unwrap_or(c)
+        } else {
+            c
+        }
+    }
+
+    /// Move `c` forward `shift` places, keeping its case; other characters are returned as-is
+    /// Avanzar `c` `shift` posiciones, conservando su caso; los demás caracteres se devuelven igual
+    pub fn shift_char(&self, c: char, shift: usize) -> char {
+        match self.index_of(c) {
+            Some((i, upper)) => self.letter(i + shift % self.len(), upper),
+            None => c,
+        }
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::ascii()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_sizes() {
+        assert_eq!(Alphabet::ascii().len(), 26);
+        assert_eq!(Alphabet::spanish().len(), 27);
+        assert_eq!(Alphabet::greek().len(), 24);
+        assert!(Alphabet::ascii().is_ascii());
+        assert!(!Alphabet::spanish().is_ascii());
+    }
+
+    #[test]
+    fn test_shift_keeps_case_and_skips_others() {
+        let spanish = Alphabet::spanish();
+        assert_eq!(spanish.shift_char('n', 1), 'ñ');
+        assert_eq!(spanish.shift_char('N', 1), 'Ñ');
+        assert_eq!(spanish.shift_char('z', 1), 'a');
+        assert_eq!(spanish.shift_char('á', 5), 'á');
+
+        let greek = Alphabet::greek();
+        assert_eq!(greek.shift_char('λ', 1), 'μ');
+        assert_eq!(greek.shift_char('Ω', 1), 'Α');
+        assert_eq!(greek.shift_char('ς', 3), 'ς');
+    }
+
+    #[test]
+    fn test_custom_alphabets() {
+        let digits = Alphabet::custom("0123456789").unwrap();
+        assert_eq!(digits.shift_char('9', 3), '2');
+        assert_eq!(digits.shift_char('a', 3), 'a');
+
+        // Both cases listed: case-sensitive, no folding / Ambos casos: distingue mayúsculas
+        let mixed = Alphabet::custom("aAbB").unwrap();
+        assert_eq!(mixed.shift_char('A', 1), 'b');
+        assert_eq!(mixed.index_of('B'), Some((3, false)));
+
+        assert!(matches!(Alphabet::custom("abca"), Err(CipherError::InvalidParam { .. })));
+        assert!(Alphabet::custom("a").is_err());
+    }
+
+    #[test]
+    fn test_parse_names() {
+        assert_eq!(Alphabet::parse("").unwrap(), Alphabet::ascii());
+        assert_eq!(Alphabet::parse(" Español ").unwrap(), Alphabet::spanish());
+        assert_eq!(Alphabet::parse("GREEK").unwrap(), Alphabet::greek());
+        assert_eq!(Alphabet::parse("xyz").unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,435 @@
+// Throughput Benchmarks: MB/s for Every Cipher and Encoding
+// Pruebas de Rendimiento: MB/s de Cada Cifrado y Codificación
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::cipher::Cipher;
+use crate::encoding::{encodings, simple_base64_decode, simple_base64_encode, Encoding};
+use crate::error::CipherError;
+use crate::file::CipherType;
+use crate::random::SecureRandom;
+
+pub const KIB: usize = 1024;
+pub const MIB: usize = 1024 * KIB;
+pub const GIB: usize = 1024 * MIB;
+
+/// Buffer sizes measured unless others are given / Tamaños de búfer medidos si no se indican otros
+pub const DEFAULT_SIZES: [usize; 3] = [KIB, 64 * KIB, MIB];
+
+/// Largest accepted buffer; input and output both live in memory / Mayor búfer aceptado; entrada y salida viven en memoria
+pub const MAX_SIZE: usize = GIB;
+
+/// Name of the original Base64 pair, kept to compare against `Base64` / Nombre del par Base64 original, para comparar con `Base64`
+pub const SIMPLE_BASE64: &str = "simple_base64";
+
+/// Header row matching `Measurement::to_csv` / Fila de cabecera que corresponde a `Measurement::to_csv`
+pub const CSV_HEADER: &str = "target,kind,operation,bytes,repeats,median_ns,best_ns,mb_per_s";
+
+/// How much to measure / Cuánto medir
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchConfig {
+    pub sizes: Vec<usize>,
+    /// Untimed runs before measuring / Ejecuciones sin medir antes de medir
+    pub warmup: usize,
+    /// Timed runs per size; the median is reported / Ejecuciones medidas por tamaño; se informa la mediana
+    pub repeats: usize,
+    /// A run slower than this ends the repeats; larger sizes are skipped once a run is expected to exceed it
+    /// Una ejecución más lenta que esto termina las repeticiones; los tamaños mayores se omiten si se espera superarlo
+    pub limit: Option<Duration>,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self { sizes: DEFAULT_SIZES.to_vec(), warmup: 1, repeats: 5, limit: Some(Duration::from_secs(1)) }
+    }
+}
+
+/// What a target runs / Lo que ejecuta un objetivo
+enum Subject {
+    Cipher(Box<dyn Cipher>),
+    Encoding(&'static dyn Encoding),
+    SimpleBase64,
+}
+
+/// One cipher or encoding to time in both directions / Un cifrado o codificación a medir en ambos sentidos
+pub struct Target {
+    /// Algorithm name, with the key size for AES: "AES-GCM-256" / Nombre del algoritmo, con el tamaño de clave en AES
+    pub name: String,
+    /// Name without the key size: "AES-GCM" / Nombre sin el tamaño de clave: "AES-GCM"
+    pub family: &'static str,
+    subject: Subject,
+}
+
+impl Target {
+    pub fn kind(&self) -> &'static str {
+        match self.subject {
+            Subject::Cipher(_) => "cipher",
+            Subject::Encoding(_) | Subject::SimpleBase64 => "encoding",
+        }
+    }
+
+    /// Forward and backward operation names / Nombres de la operación de ida y de vuelta
+    pub fn operations(&self) -> [&'static str; 2] {
+        match self.subject {
+            Subject::Cipher(_) => ["encrypt", "decrypt"],
+            Subject::Encoding(_) | Subject::SimpleBase64 => ["encode", "decode"],
+        }
+    }
+
+    /// `name` is the full name or the family, ignoring case / `name` es el nombre completo o la familia, sin distinguir mayúsculas
+    pub fn matches(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name) || self.family.eq_ignore_ascii_case(name)
+    }
+
+    /// Timed runs of both directions over `data` / Ejecuciones medidas de ambos sentidos sobre `data`
+    fn measure(&self, data: &[u8], config: &BenchConfig) -> Result<[Samples; 2], CipherError> {
+        Ok(match &self.subject {
+            Subject::Cipher(cipher) => {
+                let ciphertext = cipher.encrypt(data)?;
+                [time(config, || cipher.encrypt(data))?, time(config, || cipher.decrypt(&ciphertext))?]
+            }
+            Subject::Encoding(encoding) => {
+                let text = encoding.encode(data)?;
+                [time(config, || encoding.encode(data))?, time(config, || encoding.decode(&text))?]
+            }
+            Subject::SimpleBase64 => {
+                let text = simple_base64_encode(data)?;
+                [time(config, || simple_base64_encode(data))?, time(config, || simple_base64_decode(&text))?]
+            }
+        })
+    }
+}
+
+/// Every `CipherType` with a fixed random key (AES at 128 and 256 bits), every encoding and `simple_base64`
+/// Cada `CipherType` con una clave aleatoria fija (AES a 128 y 256 bits), cada codificación y `simple_base64`
+pub fn targets() -> Result<Vec<Target>, CipherError> {
+    // Seeded so every run times the same keys / Con semilla, así cada ejecución mide las mismas claves
+    let mut rng = SecureRandom::from_seed([0x5a; 32]);
+    let mut targets = Vec::new();
+    for (short, long) in CipherType::variants(16).into_iter().zip(CipherType::variants(32)) {
+        let variants = match long.aes_key_bits() {
+            Some(_) => vec![short, long],
+            None => vec![long],
+        };
+        for variant in variants {
+            let (algorithm, key) = variant.with_random_key(&mut rng);
+            let bits = algorithm.aes_key_bits();
+            let cipher = algorithm.into_cipher(key.as_bytes())?;
+            let family = cipher.name();
+            let name = match bits {
+                Some(bits) => format!("{}-{}", family, bits),
+                None => family.to_string(),
+            };
+            targets.push(Target { name, family, subject: Subject::Cipher(cipher) });
+        }
+    }
+    for encoding in encodings() {
+        targets.push(Target { name: encoding.name().to_string(), family: encoding.name(), subject: Subject::Encoding(encoding) });
+    }
+    targets.push(Target { name: SIMPLE_BASE64.to_string(), family: SIMPLE_BASE64, subject: Subject::SimpleBase64 });
+    Ok(targets)
+}
+
+/// Keep the targets matching any comma-separated name; an empty list keeps them all
+/// Conservar los objetivos que coinciden con algún nombre separado por comas; una lista vacía los conserva todos
+pub fn select(mut targets: Vec<Target>, names: &str) -> Result<Vec<Target>, CipherError> {
+    let names: Vec<&str> = names.split(',').map(str::trim).filter(|name| !name.is_empty()).collect();
+    if names.is_empty() {
+        return Ok(targets);
+    }
+    if let Some(unknown) = names.iter().find(|name| !targets.iter().any(|t| t.matches(name))) {
+        return Err(CipherError::UnknownAlgorithm(unknown.to_string()));
+    }
+    targets.retain(|t| names.iter().any(|name| t.matches(name)));
+    Ok(targets)
+}
+
+/// Timings of one operation on one buffer / Tiempos de una operación sobre un búfer
+#[derive(Clone, Debug, PartialEq)]
+pub struct Measurement {
+    pub target: String,
+    pub kind: &'static str,
+    pub operation: &'static str,
+    pub bytes: usize,
+    /// Timed runs actually made; fewer than asked when the limit cut them short
+    /// Ejecuciones medidas de verdad; menos de las pedidas si el límite las cortó
+    pub repeats: usize,
+    pub median: Duration,
+    pub best: Duration,
+}
+
+impl Measurement {
+    /// Throughput of the median run in MB/s (10^6 bytes) / Rendimiento de la ejecución mediana en MB/s (10^6 bytes)
+    pub fn mb_per_s(&self) -> f64 {
+        throughput(self.bytes, self.median)
+    }
+
+    /// Throughput of the fastest run / Rendimiento de la ejecución más rápida
+    pub fn best_mb_per_s(&self) -> f64 {
+        throughput(self.bytes, self.best)
+    }
+
+    /// One CSV row under `CSV_HEADER` / Una fila CSV bajo `CSV_HEADER`
+    pub fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{:.3}",
+            csv_field(&self.target),
+            self.kind,
+            self.operation,
+            self.bytes,
+            self.repeats,
+            self.median.as_nanos(),
+            self.best.as_nanos(),
+            self.mb_per_s()
+        )
+    }
+
+    /// One JSON object with the same fields as the CSV row / Un objeto JSON con los mismos campos que la fila CSV
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"target\": {}, \"kind\": \"{}\", \"operation\": \"{}\", \"bytes\": {}, \"repeats\": {}, \"median_ns\": {}, \"best_ns\": {}, \"mb_per_s\": {:.3}}}",
+            json_string(&self.target),
+            self.kind,
+            self.operation,
+            self.bytes,
+            self.repeats,
+            self.median.as_nanos(),
+            self.best.as_nanos(),
+            self.mb_per_s()
+        )
+    }
+}
+
+/// Time every target at every size, smallest first, passing each result to `report` as it is measured
+/// Medir cada objetivo en cada tamaño, del menor al mayor, pasando cada resultado a `report` al medirlo
+pub fn run(
+    targets: &[Target],
+    config: &BenchConfig,
+    mut report: impl FnMut(&Measurement) -> Result<(), CipherError>,
+) -> Result<Vec<Measurement>, CipherError> {
+    if config.repeats == 0 {
+        return Err(CipherError::InvalidParam { name: "repeats".to_string(), value: "0".to_string() });
+    }
+    let mut sizes = config.sizes.clone();
+    sizes.sort_unstable();
+    sizes.dedup();
+    if let Some(&size) = sizes.iter().find(|&&size| size == 0 || size > MAX_SIZE) {
+        return Err(CipherError::InvalidParam { name: "size".to_string(), value: size.to_string() });
+    }
+    let data = sample(sizes.last().copied().unwrap_or(0));
+
+    let mut results = Vec::new();
+    for target in targets {
+        for (i, &size) in sizes.iter().enumerate() {
+            let samples = target.measure(&data[..size], config)?;
+            let mut slowest = Duration::ZERO;
+            for (operation, samples) in target.operations().into_iter().zip(&samples) {
+                let measurement = samples.summary(target, operation, size);
+                slowest = slowest.max(measurement.median);
+                report(&measurement)?;
+                results.push(measurement);
+            }
+            // Scale linearly to the next size; quadratic encodings such as Base58 only get slower
+            // Escalar linealmente al siguiente tamaño; codificaciones cuadráticas como Base58 solo empeoran
+            let expected = sizes.get(i + 1).map(|&next| slowest.as_secs_f64() * next as f64 / size as f64);
+            let too_slow = match (config.limit, expected) {
+                (Some(limit), Some(expected)) => expected > limit.as_secs_f64(),
+                _ => false,
+            };
+            if too_slow || samples.iter().any(|s| s.over_limit) {
+                break;
+            }
+        }
+    }
+    Ok(results)
+}
+
+/// Parse "4096", "64K", "1MiB" or "1 GB"; units are powers of 1024
+/// Analizar "4096", "64K", "1MiB" o "1 GB"; las unidades son potencias de 1024
+pub fn parse_size(text: &str) -> Result<usize, CipherError> {
+    let invalid = || CipherError::InvalidParam { name: "size".to_string(), value: text.to_string() };
+    let text = text.trim();
+    let digits = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let number: usize = text[..digits].parse().map_err(|_| invalid())?;
+    let unit = match text[digits..].trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => KIB,
+        "M" | "MB" | "MIB" => MIB,
+        "G" | "GB" | "GIB" => GIB,
+        _ => return Err(invalid()),
+    };
+    number.checked_mul(unit).ok_or_else(invalid)
+}
+
+/// Comma-separated sizes such as "1K,64K,1M" / Tamaños separados por comas como "1K,64K,1M"
+pub fn parse_sizes(list: &str) -> Result<Vec<usize>, CipherError> {
+    list.split(',').map(parse_size).collect()
+}
+
+/// "64 KiB", "1 GiB" or "1000 B" / "64 KiB", "1 GiB" o "1000 B"
+pub fn format_size(bytes: usize) -> String {
+    match [(GIB, "GiB"), (MIB, "MiB"), (KIB, "KiB")].iter().find(|(unit, _)| bytes >= *unit && bytes.is_multiple_of(*unit)) {
+        Some((unit, name)) => format!("{} {}", bytes / unit, name),
+        None => format!("{} B", bytes),
+    }
+}
+
+/// Timed runs of one operation / Ejecuciones medidas de una operación
+struct Samples {
+    runs: Vec<Duration>,
+    over_limit: bool,
+}
+
+impl Samples {
+    fn summary(&self, target: &Target, operation: &'static str, bytes: usize) -> Measurement {
+        let mut runs = self.runs.clone();
+        runs.sort_unstable();
+        Measurement {
+            target: target.name.clone(),
+            kind: target.kind(),
+            operation,
+            bytes,
+            repeats: runs.len(),
+            median: runs[runs.len() / 2],
+            best: runs[0],
+        }
+    }
+}
+
+/// Warm up, then time `repeats` runs; one run over the limit ends it, and still counts if nothing was timed
+/// Calentar y medir `repeats` ejecuciones; una por encima del límite lo termina, y cuenta si no se midió nada
+fn time<T>(config: &BenchConfig, mut operation: impl FnMut() -> Result<T, CipherError>) -> Result<Samples, CipherError> {
+    let mut samples = Samples { runs: Vec::with_capacity(config.repeats), over_limit: false };
+    for run in 0..config.warmup + config.repeats {
+        let start = Instant::now();
+        black_box(operation()?);
+        let elapsed = start.elapsed();
+        if run >= config.warmup {
+            samples.runs.push(elapsed);
+        }
+        if config.limit.is_some_and(|limit| elapsed > limit) {
+            samples.over_limit = true;
+            if samples.runs.is_empty() {
+                samples.runs.push(elapsed);
+            }
+            break;
+        }
+    }
+    Ok(samples)
+}
+
+fn throughput(bytes: usize, elapsed: Duration) -> f64 {
+    // A clock tick of 0 ns would divide by zero / Un tic de reloj de 0 ns dividiría por cero
+    bytes as f64 / 1e6 / elapsed.as_secs_f64().max(1e-9)
+}
+
+/// Text with the letters, digits and punctuation classical ciphers treat differently
+/// Texto con las letras, dígitos y signos que los cifrados clásicos tratan de forma distinta
+fn sample(len: usize) -> Vec<u8> {
+    b"The quick brown fox jumps over the lazy dog, 0123456789 times!\n".iter().copied().cycle().take(len).collect()
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sizes_parse_and_format() {
+        for (text, bytes) in [("4096", 4096), ("1K", KIB), ("64 KiB", 64 * KIB), ("1mb", MIB), ("1G", GIB)] {
+            assert_eq!(parse_size(text).unwrap(), bytes, "{}", text);
+        }
+        for text in ["", "K", "12X", "-1K", "99999999999999999999G"] {
+            assert!(matches!(parse_size(text), Err(CipherError::InvalidParam { .. })), "{}", text);
+        }
+        assert_eq!([KIB, 64 * KIB, GIB, 1000].map(format_size), ["1 KiB", "64 KiB", "1 GiB", "1000 B"]);
+        assert_eq!(parse_sizes("1K, 16").unwrap(), [KIB, 16]);
+    }
+
+    #[test]
+    fn test_targets_cover_every_cipher_and_encoding() {
+        let targets = targets().unwrap();
+        let names: Vec<&str> = targets.iter().map(|t| t.name.as_str()).collect();
+        // 11 ciphers without AES, 4 AES modes at two key sizes, the encodings and simple_base64
+        // 11 cifrados sin AES, 4 modos AES con dos tamaños de clave, las codificaciones y simple_base64
+        assert_eq!(targets.len(), 11 + 8 + encodings().len() + 1);
+        for name in ["Caesar", "Enigma", "ChaCha20-Poly1305", "AES-CTR-128", "AES-GCM-256", "Ascii85", SIMPLE_BASE64] {
+            assert!(names.contains(&name), "{} missing from {:?}", name, names);
+        }
+        assert_eq!(targets.iter().filter(|t| t.matches("aes-gcm")).count(), 2);
+        let chosen = select(super::targets().unwrap(), "aes-gcm, Hex,AES-CTR-128").unwrap();
+        assert_eq!(chosen.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), ["AES-CTR-128", "AES-GCM-128", "AES-GCM-256", "Hex"]);
+        assert!(matches!(select(super::targets().unwrap(), "Caesar,Rot47"), Err(CipherError::UnknownAlgorithm(name)) if name == "Rot47"));
+        assert_eq!(select(super::targets().unwrap(), " ").unwrap().len(), targets.len());
+        assert_eq!(targets.iter().find(|t| t.name == "Base64").unwrap().operations(), ["encode", "decode"]);
+    }
+
+    #[test]
+    fn test_run_reports_every_operation_and_size() {
+        let targets = select(targets().unwrap(), "AES-CBC,Hex").unwrap();
+        let config = BenchConfig { sizes: vec![KIB, 16], warmup: 0, repeats: 3, limit: None };
+        let mut reported = 0;
+        let results = run(&targets, &config, |_| {
+            reported += 1;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!((results.len(), reported), (3 * 2 * 2, 12));
+        // Sizes run smallest first / Los tamaños se ejecutan del menor al mayor
+        assert_eq!((results[0].target.as_str(), results[0].operation, results[0].bytes), ("AES-CBC-128", "encrypt", 16));
+        assert_eq!(results[11].operation, "decode");
+        for m in &results {
+            assert!(m.repeats == 3 && m.best <= m.median && m.best_mb_per_s() >= m.mb_per_s() && m.mb_per_s() > 0.0);
+        }
+
+        // A zero limit stops after one timed run and skips the larger size / Un límite cero para tras una ejecución y omite el tamaño mayor
+        let limited = BenchConfig { limit: Some(Duration::ZERO), ..config.clone() };
+        let results = run(&targets[..1], &limited, |_| Ok(())).unwrap();
+        assert_eq!(results.iter().map(|m| (m.bytes, m.repeats)).collect::<Vec<_>>(), [(16, 1), (16, 1)]);
+
+        assert!(run(&targets, &BenchConfig { repeats: 0, ..config.clone() }, |_| Ok(())).is_err());
+        assert!(run(&targets, &BenchConfig { sizes: vec![MAX_SIZE + 1], ..config }, |_| Ok(())).is_err());
+    }
+
+    #[test]
+    fn test_csv_and_json_rows() {
+        let m = Measurement {
+            target: "Say \"hi\", Bob".to_string(),
+            kind: "cipher",
+            operation: "encrypt",
+            bytes: 2_000_000,
+            repeats: 5,
+            median: Duration::from_millis(500),
+            best: Duration::from_millis(250),
+        };
+        assert_eq!(m.to_csv(), "\"Say \"\"hi\"\", Bob\",cipher,encrypt,2000000,5,500000000,250000000,4.000");
+        assert_eq!(
+            m.to_json(),
+            "{\"target\": \"Say \\\"hi\\\", Bob\", \"kind\": \"cipher\", \"operation\": \"encrypt\", \"bytes\": 2000000, \
+             \"repeats\": 5, \"median_ns\": 500000000, \"best_ns\": 250000000, \"mb_per_s\": 4.000}"
+        );
+        assert_eq!(m.best_mb_per_s(), 8.0);
+    }
+}
@@ -0,0 +1,168 @@
+// BLAKE2b Hash (RFC 7693), the Building Block of Argon2
+// Hash BLAKE2b (RFC 7693), la Pieza Base de Argon2
+
+use crate::hash::Digest;
+
+// Same IV as SHA-512 / Mismo IV que SHA-512
+const IV: [u64; 8] = [
+    0x6a09_e667_f3bc_c908, 0xbb67_ae85_84ca_a73b, 0x3c6e_f372_fe94_f82b, 0xa54f_f53a_5f1d_36f1,
+    0x510e_527f_ade6_82d1, 0x9b05_688c_2b3e_6c1f, 0x1f83_d9ab_fb41_bd6b, 0x5be0_cd19_137e_2179,
+];
+
+const SIGMA: [[usize; 16]; 12] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+];
+
+pub const MAX_OUTPUT_LEN: usize = 64;
+
+/// Unkeyed BLAKE2b with a configurable digest size (1-64 bytes)
+/// BLAKE2b sin clave con tamaño de resumen configurable (1-64 bytes)
+#[derive(Clone)]
+pub struct Blake2b {
+    state: [u64; 8],
+    buffer: [u8; 128],
+    buffered: usize,
+    length: u128,
+    output_len: usize,
+}
+
+impl Blake2b {
+    /// Panics if `output_len` is not in 1..=64 / Entra en pánico si `output_len` no está en 1..=64
+    pub fn with_output_len(output_len: usize) -> Self {
+        assert!((1..=MAX_OUTPUT_LEN).contains(&output_len), "BLAKE2b output must be 1-64 bytes");
+        let mut state = IV;
+        // Parameter block: digest length, no key, fanout 1, depth 1
+        // Bloque de parámetros: longitud del resumen, sin clave, fanout 1, profundidad 1
+        state[0] ^= 0x0101_0000 ^ output_len as u64;
+        Self { state, buffer: [0; 128], buffered: 0, length: 0, output_len }
+    }
+
+    fn compress(&mut self, last: bool) {
+        let mut m = [0u64; 16];
+        for (i, word) in self.buffer.chunks_exact(8).enumerate() {
+            m[i] = u64::from_le_bytes(word.try_into().unwrap());
+        }
+
+        let mut v = [0u64; 16];
+        v[..8].copy_from_slice(&self.state);
+        v[8..].copy_from_slice(&IV);
+        v[12] ^= self.length as u64;
+        v[13] ^= (self.length >> 64) as u64;
+        if last {
+            v[14] = !v[14];
+        }
+
+        for s in &SIGMA {
+            mix(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+            mix(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+            mix(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+            mix(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+            mix(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+            mix(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+            mix(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+            mix(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+        }
+
+        for i in 0..8 {
+            self.state[i] ^= v[i] ^ v[i + 8];
+        }
+    }
+
+    /// Digest of `data` with the given size / Resumen de `data` con el tamaño dado
+    pub fn hash(output_len: usize, data: &[u8]) -> Vec<u8> {
+        let mut hasher = Self::with_output_len(output_len);
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+fn mix(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+impl Digest for Blake2b {
+    const NAME: &'static str = "BLAKE2b-512";
+    const OUTPUT_LEN: usize = MAX_OUTPUT_LEN;
+    const BLOCK_LEN: usize = 128;
+
+    fn new() -> Self {
+        Self::with_output_len(MAX_OUTPUT_LEN)
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            // The last block is compressed differently, so only flush once more data arrives
+            // El último bloque se comprime distinto, así que solo se vacía cuando llegan más datos
+            if self.buffered == 128 {
+                self.length = self.length.wrapping_add(128);
+                self.compress(false);
+                self.buffered = 0;
+            }
+            let take = (128 - self.buffered).min(data.len());
+            self.buffer[self.buffered..self.buffered + take].copy_from_slice(&data[..take]);
+            self.buffered += take;
+            data = &data[take..];
+        }
+    }
+
+    fn finalize(mut self) -> Vec<u8> {
+        self.length = self.length.wrapping_add(self.buffered as u128);
+        self.buffer[self.buffered..].fill(0);
+        self.compress(true);
+        self.state.iter().flat_map(|w| w.to_le_bytes()).take(self.output_len).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::digest_hex;
+    use crate::encoding::bytes_to_hex;
+
+    #[test]
+    fn test_blake2b_512_known_answers() {
+        assert_eq!(
+            digest_hex::<Blake2b>(b""),
+            "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419\
+             d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce"
+        );
+        // RFC 7693 appendix A / RFC 7693 apéndice A
+        assert_eq!(
+            digest_hex::<Blake2b>(b"abc"),
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+             7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+        );
+    }
+
+    #[test]
+    fn test_blake2b_short_output_and_incremental() {
+        let data = [b'a'; 300];
+        assert_eq!(bytes_to_hex(&Blake2b::hash(20, &data)), "c9c4a2f8df7d9546fad021510f72ee0ae1b15058");
+
+        // Exactly one block must not be compressed early / Un bloque exacto no debe comprimirse antes de tiempo
+        let mut hasher = Blake2b::with_output_len(20);
+        for chunk in data.chunks(128) {
+            hasher.update(chunk);
+        }
+        assert_eq!(bytes_to_hex(&hasher.finalize()), "c9c4a2f8df7d9546fad021510f72ee0ae1b15058");
+        assert_eq!(Blake2b::hash(64, &[0; 128]), Blake2b::digest(&[0; 128]));
+    }
+}
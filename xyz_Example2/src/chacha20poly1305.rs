@@ -0,0 +1,536 @@
+// ChaCha20-Poly1305 Authenticated Encryption (RFC 8439)
+// Cifrado Autenticado ChaCha20-Poly1305 (RFC 8439)
+
+use crate::cipher::{Cipher, CipherFactory, CipherParams, ParamKind, ParamSpec};
+use crate::error::CipherError;
+use crate::secret::{ct_eq, zeroize};
+
+pub const KEY_LEN: usize = 32;
+pub const NONCE_LEN: usize = 12;
+pub const TAG_LEN: usize = 16;
+
+// "expand 32-byte k" as little-endian words / "expand 32-byte k" como palabras little-endian
+const SIGMA: [u32; 4] = [0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574];
+
+// === CHACHA20 STREAM CIPHER / CIFRADO DE FLUJO CHACHA20 ===
+
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+fn le_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+/// One 64-byte ChaCha20 keystream block / Un bloque de 64 bytes del flujo ChaCha20
+pub fn chacha20_block(key: &[u8; KEY_LEN], counter: u32, nonce: &[u8; NONCE_LEN]) -> [u8; 64] {
+    let mut initial = [0u32; 16];
+    initial[..4].copy_from_slice(&SIGMA);
+    for i in 0..8 {
+        initial[4 + i] = le_u32(&key[4 * i..]);
+    }
+    initial[12] = counter;
+    for i in 0..3 {
+        initial[13 + i] = le_u32(&nonce[4 * i..]);
+    }
+
+    let mut state = initial;
+    for _ in 0..10 {
+        // Column rounds / Rondas de columna
+        quarter_round(&mut state, 0, 4, 8, 12);
+        quarter_round(&mut state, 1, 5, 9, 13);
+        quarter_round(&mut state, 2, 6, 10, 14);
+        quarter_round(&mut state, 3, 7, 11, 15);
+        // Diagonal rounds / Rondas diagonales
+        quarter_round(&mut state, 0, 5, 10, 15);
+        quarter_round(&mut state, 1, 6, 11, 12);
+        quarter_round(&mut state, 2, 7, 8, 13);
+        quarter_round(&mut state, 3, 4, 9, 14);
+    }
+
+    let mut out = [0u8; 64];
+    for (i, word) in state.iter().enumerate() {
+        let sum = word.wrapping_add(initial[i]);
+        out[4 * i..4 * i + 4].copy_from_slice(&sum.to_le_bytes());
+    }
+    out
+}
+
+/// XOR `data` in place with the keystream starting at block `counter`
+/// Aplicar XOR a `data` con el flujo de clave empezando en el bloque `counter`
+pub fn chacha20_xor(key: &[u8; KEY_LEN], counter: u32, nonce: &[u8; NONCE_LEN], data: &mut [u8]) {
+    for (i, chunk) in data.chunks_mut(64).enumerate() {
+        let block = chacha20_block(key, counter.wrapping_add(i as u32), nonce);
+        for (byte, k) in chunk.iter_mut().zip(block.iter()) {
+            *byte ^= k;
+        }
+    }
+}
+
+// === POLY1305 MAC / MAC POLY1305 ===
+
+/// Incremental Poly1305 one-time authenticator using 26-bit limbs
+/// Autenticador de un solo uso Poly1305 incremental con limbs de 26 bits
+pub struct Poly1305 {
+    r: [u32; 5],
+    h: [u32; 5],
+    pad: [u32; 4],
+    buffer: [u8; 16],
+    buffered: usize,
+}
+
+impl Poly1305 {
+    /// The key must never be reused across messages / La clave nunca debe reutilizarse
+    pub fn new(key: &[u8; 32]) -> Self {
+        // Clamp r as required by the spec / Ajustar r como exige la especificación
+        let r = [
+            le_u32(&key[0..]) & 0x03ff_ffff,
+            (le_u32(&key[3..]) >> 2) & 0x03ff_ff03,
+            (le_u32(&key[6..]) >> 4) & 0x03ff_c0ff,
+            (le_u32(&key[9..]) >> 6) & 0x03f0_3fff,
+            (le_u32(&key[12..]) >> 8) & 0x000f_ffff,
+        ];
+        let pad = [
+            le_u32(&key[16..]),
+            le_u32(&key[20..]),
+            le_u32(&key[24..]),
+            le_u32(&key[28..]),
+        ];
+        Self { r, h: [0; 5], pad, buffer: [0; 16], buffered: 0 }
+    }
+
+    fn block(&mut self, m: &[u8; 16], hibit: u32) {
+        const MASK: u32 = 0x03ff_ffff;
+        let [r0, r1, r2, r3, r4] = self.r.map(u64::from);
+        let (s1, s2, s3, s4) = (r1 * 5, r2 * 5, r3 * 5, r4 * 5);
+
+        let h0 = u64::from(self.h[0] + (le_u32(&m[0..]) & MASK));
+        let h1 = u64::from(self.h[1] + ((le_u32(&m[3..]) >> 2) & MASK));
+        let h2 = u64::from(self.h[2] + ((le_u32(&m[6..]) >> 4) & MASK));
+        let h3 = u64::from(self.h[3] + ((le_u32(&m[9..]) >> 6) & MASK));
+        let h4 = u64::from(self.h[4] + ((le_u32(&m[12..]) >> 8) | hibit));
+
+        let d0 = h0 * r0 + h1 * s4 + h2 * s3 + h3 * s2 + h4 * s1;
+        let mut d1 = h0 * r1 + h1 * r0 + h2 * s4 + h3 * s3 + h4 * s2;
+        let mut d2 = h0 * r2 + h1 * r1 + h2 * r0 + h3 * s4 + h4 * s3;
+        let mut d3 = h0 * r3 + h1 * r2 + h2 * r1 + h3 * r0 + h4 * s4;
+        let mut d4 = h0 * r4 + h1 * r3 + h2 * r2 + h3 * r1 + h4 * r0;
+
+        // Partial reduction mod 2^130 - 5 / Reducción parcial mod 2^130 - 5
+        let mut c = d0 >> 26;
+        let mut h0 = (d0 as u32) & MASK;
+        d1 += c;
+        c = d1 >> 26;
+        let h1 = (d1 as u32) & MASK;
+        d2 += c;
+        c = d2 >> 26;
+        let h2 = (d2 as u32) & MASK;
+        d3 += c;
+        c = d3 >> 26;
+        let h3 = (d3 as u32) & MASK;
+        d4 += c;
+        c = d4 >> 26;
+        let h4 = (d4 as u32) & MASK;
+        h0 += (c as u32) * 5;
+        let c = h0 >> 26;
+        h0 &= MASK;
+
+        self.h = [h0, h1 + c, h2, h3, h4];
+    }
+
+    /// Absorb more message bytes / Absorber más bytes del mensaje
+    pub fn update(&mut self, mut data: &[u8]) {
+        if self.buffered > 0 {
+            let take = (16 - self.buffered).min(data.len());
+            self.buffer[self.buffered..self.buffered + take].copy_from_slice(&data[..take]);
+            self.buffered += take;
+            data = &data[take..];
+            if self.buffered < 16 {
+                return;
+            }
+            let block = self.buffer;
+            self.block(&block, 1 << 24);
+            self.buffered = 0;
+        }
+
+        let mut chunks = data.chunks_exact(16);
+        for chunk in &mut chunks {
+            self.block(chunk.try_into().unwrap(), 1 << 24);
+        }
+        let rest = chunks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+    }
+
+    /// Absorb zero bytes up to the next 16-byte boundary (used by the AEAD)
+    /// Absorber ceros hasta el siguiente límite de 16 bytes (usado por el AEAD)
+    pub fn pad_to_block(&mut self) {
+        if self.buffered > 0 {
+            self.update(&[0u8; 16][self.buffered..]);
+        }
+    }
+
+    /// Produce the 16-byte tag / Producir la etiqueta de 16 bytes
+    pub fn finalize(mut self) -> [u8; TAG_LEN] {
+        const MASK: u32 = 0x03ff_ffff;
+
+        if self.buffered > 0 {
+            let mut last = [0u8; 16];
+            last[..self.buffered].copy_from_slice(&self.buffer[..self.buffered]);
+            last[self.buffered] = 1;
+            self.block(&last, 0);
+        }
+
+        // Fully carry h / Propagar completamente el acarreo de h
+        let [mut h0, mut h1, mut h2, mut h3, mut h4] = self.h;
+        let mut c = h1 >> 26;
+        h1 &= MASK;
+        h2 += c;
+        c = h2 >> 26;
+        h2 &= MASK;
+        h3 += c;
+        c = h3 >> 26;
+        h3 &= MASK;
+        h4 += c;
+        c = h4 >> 26;
+        h4 &= MASK;
+        h0 += c * 5;
+        c = h0 >> 26;
+        h0 &= MASK;
+        h1 += c;
+
+        // Compute h - p and select it in constant time if non-negative
+        // Calcular h - p y elegirlo en tiempo constante si no es negativo
+        let mut g0 = h0.wrapping_add(5);
+        c = g0 >> 26;
+        g0 &= MASK;
+        let mut g1 = h1.wrapping_add(c);
+        c = g1 >> 26;
+        g1 &= MASK;
+        let mut g2 = h2.wrapping_add(c);
+        c = g2 >> 26;
+        g2 &= MASK;
+        let mut g3 = h3.wrapping_add(c);
+        c = g3 >> 26;
+        g3 &= MASK;
+        let g4 = h4.wrapping_add(c).wrapping_sub(1 << 26);
+
+        let select = (g4 >> 31).wrapping_sub(1);
+        h0 = (h0 & !select) | (g0 & select);
+        h1 = (h1 & !select) | (g1 & select);
+        h2 = (h2 & !select) | (g2 & select);
+        h3 = (h3 & !select) | (g3 & select);
+        h4 = (h4 & !select) | (g4 & select);
+
+        // h mod 2^128, then add the pad / h mod 2^128, luego sumar el pad
+        let words = [
+            h0 | (h1 << 26),
+            (h1 >> 6) | (h2 << 20),
+            (h2 >> 12) | (h3 << 14),
+            (h3 >> 18) | (h4 << 8),
+        ];
+        let mut tag = [0u8; TAG_LEN];
+        let mut carry = 0u64;
+        for i in 0..4 {
+            let f = u64::from(words[i]) + u64::from(self.pad[i]) + carry;
+            tag[4 * i..4 * i + 4].copy_from_slice(&(f as u32).to_le_bytes());
+            carry = f >> 32;
+        }
+        tag
+    }
+}
+
+/// One-shot Poly1305 / Poly1305 en un solo paso
+pub fn poly1305(key: &[u8; 32], message: &[u8]) -> [u8; TAG_LEN] {
+    let mut mac = Poly1305::new(key);
+    mac.update(message);
+    mac.finalize()
+}
+
+// === AEAD CONSTRUCTION / CONSTRUCCIÓN AEAD ===
+
+/// ChaCha20-Poly1305 AEAD; `encrypt` output is ciphertext followed by a 16-byte tag
+/// AEAD ChaCha20-Poly1305; la salida de `encrypt` es el texto cifrado seguido de una etiqueta de 16 bytes
+///
+/// A (key, nonce) pair must never encrypt two different messages.
+/// Un par (clave, nonce) nunca debe cifrar dos mensajes distintos.
+///
+/// The key is zeroed on drop; copy only through `duplicate`.
+/// La clave se pone a cero al liberarse; copiar solo con `duplicate`.
+pub struct ChaCha20Poly1305 {
+    key: [u8; KEY_LEN],
+    nonce: [u8; NONCE_LEN],
+    aad: Vec<u8>,
+}
+
+impl ChaCha20Poly1305 {
+    pub fn new(key: [u8; KEY_LEN], nonce: [u8; NONCE_LEN]) -> Self {
+        Self { key, nonce, aad: Vec::new() }
+    }
+
+    /// A second copy with the same key, nonce and AAD; deliberately not `Clone`
+    /// Una segunda copia con la misma clave, nonce y AAD; a propósito no es `Clone`
+    pub fn duplicate(&self) -> Self {
+        Self { key: self.key, nonce: self.nonce, aad: self.aad.clone() }
+    }
+
+    /// Associated data authenticated by `encrypt`/`decrypt` but not encrypted
+    /// Datos asociados autenticados por `encrypt`/`decrypt` pero no cifrados
+    pub fn with_aad(mut self, aad: &[u8]) -> Self {
+        self.aad = aad.to_vec();
+        self
+    }
+
+    fn tag(&self, nonce: &[u8; NONCE_LEN], aad: &[u8], ciphertext: &[u8]) -> [u8; TAG_LEN] {
+        let block = chacha20_block(&self.key, 0, nonce);
+        let mut one_time_key = [0u8; 32];
+        one_time_key.copy_from_slice(&block[..32]);
+
+        let mut mac = Poly1305::new(&one_time_key);
+        mac.update(aad);
+        mac.pad_to_block();
+        mac.update(ciphertext);
+        mac.pad_to_block();
+        mac.update(&(aad.len() as u64).to_le_bytes());
+        mac.update(&(ciphertext.len() as u64).to_le_bytes());
+        mac.finalize()
+    }
+
+    fn check_length(len: usize) -> Result<(), CipherError> {
+        // The 32-bit block counter starts at 1 / El contador de bloques de 32 bits empieza en 1
+        if len as u64 > (u32::MAX as u64) * 64 {
+            return Err(CipherError::MalformedInput {
+                position: 0,
+                reason: "message too long for a single nonce".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Encrypt and authenticate with an explicit nonce and associated data
+    /// Cifrar y autenticar con un nonce y datos asociados explícitos
+    pub fn seal(&self, nonce: &[u8; NONCE_LEN], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, CipherError> {
+        Self::check_length(plaintext.len())?;
+        let mut out = plaintext.to_vec();
+        chacha20_xor(&self.key, 1, nonce, &mut out);
+        let tag = self.tag(nonce, aad, &out);
+        out.extend_from_slice(&tag);
+        Ok(out)
+    }
+
+    /// Verify the tag and decrypt; nothing is returned if verification fails
+    /// Verificar la etiqueta y descifrar; no se devuelve nada si la verificación falla
+    pub fn open(&self, nonce: &[u8; NONCE_LEN], aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>, CipherError> {
+        if sealed.len() < TAG_LEN {
+            return Err(CipherError::MalformedInput {
+                position: sealed.len(),
+                reason: "ciphertext shorter than the authentication tag".to_string(),
+            });
+        }
+        let (ciphertext, tag) = sealed.split_at(sealed.len() - TAG_LEN);
+        Self::check_length(ciphertext.len())?;
+        if !ct_eq(&self.tag(nonce, aad, ciphertext), tag) {
+            return Err(CipherError::AuthenticationFailed);
+        }
+        let mut out = ciphertext.to_vec();
+        chacha20_xor(&self.key, 1, nonce, &mut out);
+        Ok(out)
+    }
+}
+
+impl Drop for ChaCha20Poly1305 {
+    fn drop(&mut self) {
+        zeroize(&mut self.key);
+    }
+}
+
+impl Cipher for ChaCha20Poly1305 {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        self.seal(&self.nonce, &self.aad, data)
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        self.open(&self.nonce, &self.aad, data)
+    }
+}
+
+const KEY_PARAM: ParamSpec = ParamSpec {
+    name: "key",
+    prompt: "Enter 256-bit key as 64 hex digits / Ingresa clave de 256 bits como 64 dígitos hex",
+    kind: ParamKind::Hex(32),
+};
+
+const NONCE_PARAM: ParamSpec = ParamSpec {
+    name: "nonce",
+    prompt: "Enter 96-bit nonce as 24 hex digits / Ingresa nonce de 96 bits como 24 dígitos hex",
+    kind: ParamKind::Hex(12),
+};
+
+const AAD_PARAM: ParamSpec = ParamSpec {
+    name: "aad",
+    prompt: "Enter associated data (optional) / Ingresa datos asociados (opcional)",
+    kind: ParamKind::Data,
+};
+
+impl CipherFactory for ChaCha20Poly1305 {
+    const NAME: &'static str = "ChaCha20-Poly1305";
+    const DESCRIPTION: &'static str = "ChaCha20-Poly1305 AEAD / AEAD ChaCha20-Poly1305";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[KEY_PARAM, NONCE_PARAM, AAD_PARAM]
+    }
+
+    fn from_params(params: &CipherParams) -> Result<Self, CipherError> {
+        let cipher = Self::new(params.hex_array(KEY_PARAM.name)?, params.hex_array(NONCE_PARAM.name)?);
+        Ok(cipher.with_aad(params.get(AAD_PARAM.name).unwrap_or("").as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex_to_bytes;
+
+    const SUNSCREEN: &[u8] = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+    fn hex(s: &str) -> Vec<u8> {
+        hex_to_bytes(&s.replace([' ', ':', '\n'], "")).unwrap()
+    }
+
+    fn sequential_key(start: u8) -> [u8; 32] {
+        std::array::from_fn(|i| start + i as u8)
+    }
+
+    #[test]
+    fn test_quarter_round_rfc8439_2_1_1() {
+        let mut state = [0u32; 16];
+        state[..4].copy_from_slice(&[0x1111_1111, 0x0102_0304, 0x9b8d_6f43, 0x0123_4567]);
+        quarter_round(&mut state, 0, 1, 2, 3);
+        assert_eq!(state[..4], [0xea2a_92f4, 0xcb1c_f8ce, 0x4581_472e, 0x5881_c4bb]);
+    }
+
+    #[test]
+    fn test_chacha20_block_rfc8439_2_3_2() {
+        let nonce: [u8; 12] = hex("000000090000004a00000000").try_into().unwrap();
+        let block = chacha20_block(&sequential_key(0), 1, &nonce);
+        let expected = hex(
+            "10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4e\
+             d2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e",
+        );
+        assert_eq!(block.to_vec(), expected);
+    }
+
+    #[test]
+    fn test_chacha20_encryption_rfc8439_2_4_2() {
+        let nonce: [u8; 12] = hex("000000000000004a00000000").try_into().unwrap();
+        let mut data = SUNSCREEN.to_vec();
+        chacha20_xor(&sequential_key(0), 1, &nonce, &mut data);
+        let expected = hex(
+            "6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0b\
+             f91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d8\
+             07ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab7793736\
+             5af90bbf74a35be6b40b8eedf2785e42874d",
+        );
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn test_poly1305_rfc8439_2_5_2() {
+        let key: [u8; 32] = hex(
+            "85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b",
+        )
+        .try_into()
+        .unwrap();
+        let tag = poly1305(&key, b"Cryptographic Forum Research Group");
+        assert_eq!(tag.to_vec(), hex("a8061dc1305136c6c22b8baf0c0127a9"));
+    }
+
+    #[test]
+    fn test_poly1305_incremental_matches_one_shot() {
+        let key = sequential_key(7);
+        let mut mac = Poly1305::new(&key);
+        for piece in SUNSCREEN.chunks(7) {
+            mac.update(piece);
+        }
+        assert_eq!(mac.finalize(), poly1305(&key, SUNSCREEN));
+    }
+
+    #[test]
+    fn test_poly1305_key_generation_rfc8439_2_6_2() {
+        let nonce: [u8; 12] = hex("000000000001020304050607").try_into().unwrap();
+        let block = chacha20_block(&sequential_key(0x80), 0, &nonce);
+        let expected = hex("8ad5a08b905f81cc815040274ab29471a833b637e3fd0da508dbb8e2fdd1a646");
+        assert_eq!(block[..32].to_vec(), expected);
+    }
+
+    #[test]
+    fn test_aead_rfc8439_2_8_2() {
+        let nonce: [u8; 12] = hex("070000004041424344454647").try_into().unwrap();
+        let aad = hex("50515253c0c1c2c3c4c5c6c7");
+        let cipher = ChaCha20Poly1305::new(sequential_key(0x80), nonce).with_aad(&aad);
+
+        let sealed = cipher.encrypt(SUNSCREEN).unwrap();
+        let expected = hex(
+            "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d6\
+             3dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b36\
+             92ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc\
+             3ff4def08e4b7a9de576d26586cec64b6116\
+             1ae10b594f09e26a7e902ecbd0600691",
+        );
+        assert_eq!(sealed, expected);
+        assert_eq!(cipher.decrypt(&sealed).unwrap(), SUNSCREEN);
+    }
+
+    #[test]
+    fn test_aead_rejects_tampering() {
+        let cipher = ChaCha20Poly1305::new(sequential_key(1), [9; 12]).with_aad(b"header");
+        let mut sealed = cipher.encrypt(b"attack at dawn").unwrap();
+
+        sealed[0] ^= 1;
+        assert!(matches!(cipher.decrypt(&sealed), Err(CipherError::AuthenticationFailed)));
+        sealed[0] ^= 1;
+
+        let other_aad = cipher.duplicate().with_aad(b"other");
+        assert!(matches!(other_aad.decrypt(&sealed), Err(CipherError::AuthenticationFailed)));
+
+        let wrong_key = ChaCha20Poly1305::new(sequential_key(2), [9; 12]).with_aad(b"header");
+        assert!(matches!(wrong_key.decrypt(&sealed), Err(CipherError::AuthenticationFailed)));
+
+        assert!(matches!(cipher.decrypt(&sealed[..10]), Err(CipherError::MalformedInput { .. })));
+    }
+
+    #[test]
+    fn test_drop_wipes_key() {
+        let mut slot = std::mem::MaybeUninit::new(ChaCha20Poly1305::new([0x5a; KEY_LEN], [1; NONCE_LEN]));
+        // SAFETY: the slot was initialized above and is dropped exactly once; the key is
+        // a plain byte array, so reading it after the drop reads initialized memory.
+        // SEGURIDAD: el hueco se inicializó arriba y se libera una sola vez; la clave es
+        // un arreglo de bytes simple, así que leerla tras liberar lee memoria inicializada.
+        let key = unsafe {
+            slot.assume_init_drop();
+            std::ptr::addr_of!((*slot.as_ptr()).key).read()
+        };
+        assert_eq!(key, [0; KEY_LEN]);
+    }
+
+    #[test]
+    fn test_from_params_validates_lengths() {
+        let mut params = CipherParams::new();
+        params.set("key", &"00".repeat(32));
+        params.set("nonce", &"00".repeat(11));
+        assert!(matches!(ChaCha20Poly1305::from_params(&params), Err(CipherError::InvalidKey(_))));
+        params.set("nonce", &"00".repeat(12));
+        assert!(ChaCha20Poly1305::from_params(&params).is_ok());
+    }
+}
@@ -0,0 +1,303 @@
+// Pluggable Cipher Trait and Registry
+// Trait de Cifrado Intercambiable y Registro
+
+use std::collections::HashMap;
+
+use crate::encoding::hex_to_bytes;
+use crate::error::CipherError;
+
+/// Common interface implemented by every cipher algorithm
+/// Interfaz común implementada por cada algoritmo de cifrado
+pub trait Cipher {
+    /// Human-readable algorithm name / Nombre legible del algoritmo
+    fn name(&self) -> &'static str;
+
+    /// Encrypt a buffer / Cifrar un búfer
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError>;
+
+    /// Decrypt a buffer / Descifrar un búfer
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError>;
+}
+
+/// Ciphers that can be built from user-supplied parameters and registered by name
+/// Cifrados que se pueden construir a partir de parámetros y registrar por nombre
+pub trait CipherFactory: Cipher + Sized + 'static {
+    /// Registry name (looked up case-insensitively) / Nombre en el registro
+    const NAME: &'static str;
+
+    /// Short description shown in menus / Descripción corta mostrada en menús
+    const DESCRIPTION: &'static str;
+
+    /// Parameters the cipher needs / Parámetros que necesita el cifrado
+    fn schema() -> &'static [ParamSpec];
+
+    /// Build the cipher, failing if a parameter is missing or invalid
+    /// Construir el cifrado, fallando si falta un parámetro o es inválido
+    fn from_params(params: &CipherParams) -> Result<Self, CipherError>;
+}
+
+/// Kind of value a parameter expects / Tipo de valor que espera un parámetro
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamKind {
+    Shift, // Small integer shift / Desplazamiento entero pequeño
+    Range(u32, u32), // Integer in `min..=max`, such as a rail count / Entero en `min..=max`, como un número de rieles
+    Coprime(u32),    // Integer below the modulus and coprime with it / Entero menor que el módulo y coprimo con él
+    Matrix(usize),   // Letters of an invertible n×n matrix mod 26 / Letras de una matriz n×n invertible mod 26
+    Enigma,          // Enigma rotors, reflector, rings, start and plugboard / Rotores, reflector, anillos, inicio y clavijero
+    Text,       // Free-form text key / Clave de texto libre
+    Hex(usize), // Raw bytes written as hex, with the generated length / Bytes en hexadecimal, con la longitud a generar
+    Data,       // Non-secret text such as AAD / Texto no secreto como AAD
+}
+
+/// Description of one cipher parameter / Descripción de un parámetro del cifrado
+#[derive(Clone, Copy, Debug)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub prompt: &'static str,
+    pub kind: ParamKind,
+}
+
+/// Named parameter values passed to `CipherFactory::from_params`
+/// Valores de parámetros con nombre pasados a `CipherFactory::from_params`
+#[derive(Clone, Debug, Default)]
+pub struct CipherParams {
+    values: HashMap<String, String>,
+}
+
+impl CipherParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.values.insert(name.to_string(), value.to_string());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    /// Required parameter / Parámetro obligatorio
+    pub fn require(&self, name: &'static str) -> Result<&str, CipherError> {
+        self.get(name).ok_or(CipherError::MissingParam(name))
+    }
+
+    /// Parameter as raw bytes / Parámetro como bytes
+    pub fn bytes(&self, name: &'static str) -> Result<Vec<u8>, CipherError> {
+        self.require(name).map(|v| v.as_bytes().to_vec())
+    }
+
+    /// Parameter decoded from hex into exactly `N` bytes
+    /// Parámetro decodificado de hex en exactamente `N` bytes
+    pub fn hex_array<const N: usize>(&self, name: &'static str) -> Result<[u8; N], CipherError> {
+        let bytes = hex_to_bytes(self.require(name)?.trim())?;
+        let len = bytes.len();
+        bytes.try_into().map_err(|_| {
+            CipherError::InvalidKey(format!("'{}' must be {} bytes, got {}", name, N, len))
+        })
+    }
+
+    /// Parameter parsed as an integer / Parámetro interpretado como entero
+    pub fn number(&self, name: &'static str) -> Result<i64, CipherError> {
+        let raw = self.require(name)?;
+        raw.trim().parse().map_err(|_| CipherError::InvalidParam {
+            name: name.to_string(),
+            value: raw.to_string(),
+        })
+    }
+
+    /// Parameter parsed as a shift in 0-25 / Parámetro interpretado como desplazamiento en 0-25
+    pub fn shift(&self, name: &'static str) -> Result<u8, CipherError> {
+        self.shift_below(name, 26).map(|s| s as u8)
+    }
+
+    /// Parameter parsed as a shift below `modulus`, the alphabet size
+    /// Parámetro interpretado como desplazamiento menor que `modulus`, el tamaño del alfabeto
+    pub fn shift_below(&self, name: &'static str, modulus: usize) -> Result<usize, CipherError> {
+        let value = self.number(name)?;
+        usize::try_from(value)
+            .ok()
+            .filter(|&s| s < modulus)
+            .ok_or(CipherError::ShiftOutOfRange(value))
+    }
+}
+
+/// One registered algorithm / Un algoritmo registrado
+pub struct CipherEntry {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub schema: &'static [ParamSpec],
+    build: fn(&CipherParams) -> Result<Box<dyn Cipher>, CipherError>,
+}
+
+impl CipherEntry {
+    /// Build a boxed cipher from parameters / Construir un cifrado a partir de parámetros
+    pub fn build(&self, params: &CipherParams) -> Result<Box<dyn Cipher>, CipherError> {
+        (self.build)(params)
+    }
+}
+
+/// Lookup table of available ciphers / Tabla de búsqueda de cifrados disponibles
+#[derive(Default)]
+pub struct CipherRegistry {
+    entries: Vec<CipherEntry>,
+}
+
+impl CipherRegistry {
+    /// Empty registry / Registro vacío
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with every built-in cipher / Registro con todos los cifrados incluidos
+    pub fn with_defaults() -> Self {
+        use crate::chacha20poly1305::ChaCha20Poly1305;
+        use crate::classical::{
+            AffineCipher, AtbashCipher, CaesarCipher, ColumnarCipher, EnigmaCipher, HillCipher, PlayfairCipher,
+            RailFenceCipher, Rot13Cipher, VigenereCipher, XorCipher,
+        };
+        use crate::modes::{AesCbc, AesCtr, AesEcb, AesGcm};
+
+        let mut registry = Self::new();
+        registry.register::<CaesarCipher>();
+        registry.register::<XorCipher>();
+        registry.register::<VigenereCipher>();
+        registry.register::<EnigmaCipher>();
+        registry.register::<Rot13Cipher>();
+        registry.register::<AffineCipher>();
+        registry.register::<AtbashCipher>();
+        registry.register::<PlayfairCipher>();
+        registry.register::<HillCipher>();
+        registry.register::<RailFenceCipher>();
+        registry.register::<ColumnarCipher>();
+        registry.register::<ChaCha20Poly1305>();
+        registry.register::<AesEcb>();
+        registry.register::<AesCbc>();
+        registry.register::<AesCtr>();
+        registry.register::<AesGcm>();
+        registry
+    }
+
+    /// Register a cipher, replacing any entry with the same name
+    /// Registrar un cifrado, reemplazando cualquier entrada con el mismo nombre
+    pub fn register<C: CipherFactory>(&mut self) {
+        fn build<C: CipherFactory>(params: &CipherParams) -> Result<Box<dyn Cipher>, CipherError> {
+            C::from_params(params).map(|c| Box::new(c) as Box<dyn Cipher>)
+        }
+
+        self.entries.retain(|e| !e.name.eq_ignore_ascii_case(C::NAME));
+        self.entries.push(CipherEntry {
+            name: C::NAME,
+            description: C::DESCRIPTION,
+            schema: C::schema(),
+            build: build::<C>,
+        });
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CipherEntry> {
+        self.entries.iter().find(|e| e.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Registered entries in registration order / Entradas en orden de registro
+    pub fn entries(&self) -> &[CipherEntry] {
+        &self.entries
+    }
+
+    /// Look up and build a cipher in one step / Buscar y construir un cifrado en un paso
+    pub fn build(&self, name: &str, params: &CipherParams) -> Result<Box<dyn Cipher>, CipherError> {
+        self.get(name)
+            .ok_or_else(|| CipherError::UnknownAlgorithm(name.to_string()))?
+            .build(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Reverse;
+
+    impl Cipher for Reverse {
+        fn name(&self) -> &'static str {
+            "Reverse"
+        }
+
+        fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+            Ok(data.iter().rev().copied().collect())
+        }
+
+        fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+            self.encrypt(data)
+        }
+    }
+
+    impl CipherFactory for Reverse {
+        const NAME: &'static str = "Reverse";
+        const DESCRIPTION: &'static str = "Reverse the bytes";
+
+        fn schema() -> &'static [ParamSpec] {
+            &[]
+        }
+
+        fn from_params(_: &CipherParams) -> Result<Self, CipherError> {
+            Ok(Reverse)
+        }
+    }
+
+    #[test]
+    fn test_registry_lists_defaults() {
+        let registry = CipherRegistry::with_defaults();
+        let names: Vec<_> = registry.entries().iter().map(|e| e.name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Caesar", "XOR", "Vigenere", "Enigma", "ROT13", "Affine", "Atbash", "Playfair", "Hill", "RailFence",
+                "Columnar", "ChaCha20-Poly1305", "AES-ECB", "AES-CBC", "AES-CTR", "AES-GCM"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_registry_lookup_is_case_insensitive() {
+        let registry = CipherRegistry::with_defaults();
+        let mut params = CipherParams::new();
+        params.set("shift", "3");
+        let cipher = registry.build("caesar", &params).unwrap();
+        assert_eq!(cipher.encrypt(b"abc").unwrap(), b"def");
+    }
+
+    #[test]
+    fn test_registry_rejects_missing_params() {
+        let registry = CipherRegistry::with_defaults();
+        assert!(matches!(
+            registry.build("XOR", &CipherParams::new()),
+            Err(CipherError::MissingParam("key"))
+        ));
+        assert!(matches!(
+            registry.build("Unknown", &CipherParams::new()),
+            Err(CipherError::UnknownAlgorithm(_))
+        ));
+    }
+
+    #[test]
+    fn test_shift_param_validation() {
+        let mut params = CipherParams::new();
+        params.set("shift", "abc");
+        assert!(matches!(params.shift("shift"), Err(CipherError::InvalidParam { .. })));
+        params.set("shift", "30");
+        assert!(matches!(params.shift("shift"), Err(CipherError::ShiftOutOfRange(30))));
+        params.set("shift", "-1");
+        assert!(matches!(params.shift("shift"), Err(CipherError::ShiftOutOfRange(-1))));
+        params.set("shift", " 7 ");
+        assert_eq!(params.shift("shift").unwrap(), 7);
+    }
+
+    #[test]
+    fn test_register_custom_cipher() {
+        let mut registry = CipherRegistry::with_defaults();
+        registry.register::<Reverse>();
+        let cipher = registry.build("reverse", &CipherParams::new()).unwrap();
+        assert_eq!(cipher.encrypt(b"abc").unwrap(), b"cba");
+        assert_eq!(registry.entries().len(), 17);
+    }
+}
@@ -0,0 +1,148 @@
+// Affine and Atbash Ciphers: Letter-by-Letter Substitutions
+// Cifrados Afín y Atbash: Sustituciones Letra a Letra
+
+use std::fmt;
+
+use crate::cipher::{Cipher, CipherFactory, CipherParams, ParamKind, ParamSpec};
+use crate::error::CipherError;
+use crate::secret::Redacted;
+
+use super::{mod_inverse, substitute};
+
+const A_PARAM: ParamSpec = ParamSpec {
+    name: "a",
+    prompt: "Enter multiplier a, coprime with 26 (1, 3, 5, 7, 9, 11, 15, 17, 19, 21, 23, 25) / Ingresa multiplicador a, coprimo con 26",
+    kind: ParamKind::Coprime(26),
+};
+
+const B_PARAM: ParamSpec = ParamSpec {
+    name: "b",
+    prompt: "Enter shift b (0-25) / Ingresa desplazamiento b (0-25)",
+    kind: ParamKind::Shift,
+};
+
+// === AFFINE CIPHER / CIFRADO AFÍN ===
+
+/// Affine cipher: each letter x becomes (a·x + b) mod 26, keeping its case
+/// Cifrado afín: cada letra x se convierte en (a·x + b) mod 26, conservando su caso
+#[derive(Clone)]
+pub struct AffineCipher {
+    a: u8,
+    b: u8,
+    a_inverse: u8, // a⁻¹ mod 26, used to decrypt / a⁻¹ mod 26, usado para descifrar
+}
+
+impl fmt::Debug for AffineCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AffineCipher").field("a", &Redacted).field("b", &Redacted).finish_non_exhaustive()
+    }
+}
+
+impl AffineCipher {
+    /// `a` must be in 1-25 and coprime with 26, or decryption would be ambiguous; `b` must be in 0-25
+    /// `a` debe estar en 1-25 y ser coprimo con 26, o el descifrado sería ambiguo; `b` debe estar en 0-25
+    pub fn new(a: u8, b: u8) -> Result<Self, CipherError> {
+        if b >= 26 {
+            return Err(CipherError::ShiftOutOfRange(b.into()));
+        }
+        let a_inverse = mod_inverse(a.into(), 26).filter(|_| a < 26).ok_or_else(|| {
+            CipherError::InvalidKey(format!("a = {} has no inverse mod 26; it must be odd, not 13, and below 26", a))
+        })?;
+        Ok(Self { a, b, a_inverse: a_inverse as u8 })
+    }
+}
+
+impl Cipher for AffineCipher {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        Ok(substitute(data, |x| ((self.a as u32 * x as u32 + self.b as u32) % 26) as u8))
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        // x = a⁻¹·(y - b) mod 26
+        Ok(substitute(data, |y| ((self.a_inverse as u32 * (y as u32 + 26 - self.b as u32)) % 26) as u8))
+    }
+}
+
+impl CipherFactory for AffineCipher {
+    const NAME: &'static str = "Affine";
+    const DESCRIPTION: &'static str = "Affine Cipher / Cifrado Afín";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[A_PARAM, B_PARAM]
+    }
+
+    fn from_params(params: &CipherParams) -> Result<Self, CipherError> {
+        let a = params.number(A_PARAM.name)?;
+        let a = u8::try_from(a).map_err(|_| CipherError::InvalidKey(format!("a = {} is out of range", a)))?;
+        Self::new(a, params.shift(B_PARAM.name)?)
+    }
+}
+
+// === ATBASH CIPHER / CIFRADO ATBASH ===
+
+/// Atbash: the alphabet reversed (A↔Z, B↔Y, ...), its own inverse
+/// Atbash: el alfabeto invertido (A↔Z, B↔Y, ...), su propia inversa
+#[derive(Clone, Debug, Default)]
+pub struct AtbashCipher;
+
+impl Cipher for AtbashCipher {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        Ok(substitute(data, |x| 25 - x))
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        self.encrypt(data)
+    }
+}
+
+impl CipherFactory for AtbashCipher {
+    const NAME: &'static str = "Atbash";
+    const DESCRIPTION: &'static str = "Atbash Cipher / Cifrado Atbash";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[]
+    }
+
+    fn from_params(_: &CipherParams) -> Result<Self, CipherError> {
+        Ok(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_affine_known_output() {
+        let cipher = AffineCipher::new(5, 8).unwrap();
+        assert_eq!(cipher.encrypt(b"Affine cipher!").unwrap(), b"Ihhwvc swfrcp!");
+        assert_eq!(cipher.decrypt(b"Ihhwvc swfrcp!").unwrap(), b"Affine cipher!");
+    }
+
+    #[test]
+    fn test_affine_requires_invertible_multiplier() {
+        for a in [0, 2, 13, 26, 27] {
+            assert!(matches!(AffineCipher::new(a, 3), Err(CipherError::InvalidKey(_))), "a = {}", a);
+        }
+        assert!(matches!(AffineCipher::new(5, 26), Err(CipherError::ShiftOutOfRange(26))));
+        for a in [1, 3, 5, 7, 9, 11, 15, 17, 19, 21, 23, 25] {
+            let cipher = AffineCipher::new(a, 11).unwrap();
+            let encrypted = cipher.encrypt(b"The Quick Brown Fox").unwrap();
+            assert_eq!(cipher.decrypt(&encrypted).unwrap(), b"The Quick Brown Fox");
+        }
+    }
+
+    #[test]
+    fn test_atbash_is_self_inverse() {
+        assert_eq!(AtbashCipher.encrypt(b"Hello, World").unwrap(), b"Svool, Dliow");
+        assert_eq!(AtbashCipher.decrypt(b"Svool, Dliow").unwrap(), b"Hello, World");
+    }
+}
@@ -0,0 +1,530 @@
+// Enigma I / M3: the Three-Rotor Wehrmacht and Kriegsmarine Machine
+// Enigma I / M3: la Máquina de Tres Rotores de la Wehrmacht y la Kriegsmarine
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::cipher::{Cipher, CipherFactory, CipherParams, ParamKind, ParamSpec};
+use crate::error::CipherError;
+use crate::random::SecureRandom;
+use crate::secret::Redacted;
+
+const SETTINGS_PARAM: ParamSpec = ParamSpec {
+    name: "settings",
+    prompt: "Rotors, reflector, rings, start and plugboard (e.g. II IV V B BUL BLA AV BS CG) / Rotores, reflector, anillos, inicio y clavijero",
+    kind: ParamKind::Enigma,
+};
+
+/// Plugboard cables used by random settings, as in the wartime key sheets
+/// Cables del clavijero en la configuración aleatoria, como en las hojas de claves de la guerra
+pub const PLUGBOARD_CABLES: usize = 10;
+
+// === ROTORS AND REFLECTORS / ROTORES Y REFLECTORES ===
+
+/// The five rotors shared by the Enigma I and the M3 / Los cinco rotores comunes a la Enigma I y la M3
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rotor {
+    I,
+    II,
+    III,
+    IV,
+    V,
+}
+
+impl Rotor {
+    pub const ALL: [Rotor; 5] = [Rotor::I, Rotor::II, Rotor::III, Rotor::IV, Rotor::V];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Rotor::I => "I",
+            Rotor::II => "II",
+            Rotor::III => "III",
+            Rotor::IV => "IV",
+            Rotor::V => "V",
+        }
+    }
+
+    /// Where each contact A-Z is wired to / A dónde está cableado cada contacto A-Z
+    fn wiring(self) -> &'static [u8; 26] {
+        match self {
+            Rotor::I => b"EKMFLGDQVZNTOWYHXUSPAIBRCJ",
+            Rotor::II => b"AJDKSIRUXBLHWTMCQGZNPYFVOE",
+            Rotor::III => b"BDFHJLCPRTXVZNYEIWGAKMUSQO",
+            Rotor::IV => b"ESOVPZJAYQUIRHXLNFTGKDCMWB",
+            Rotor::V => b"VZBRGITYUPSDNHLXAWMJQOFECK",
+        }
+    }
+
+    /// Window letter from which the next step also turns the rotor on the left (0 = A)
+    /// Letra de la ventana desde la que el siguiente paso también gira el rotor de la izquierda (0 = A)
+    fn notch(self) -> u8 {
+        let letter = match self {
+            Rotor::I => b'Q',
+            Rotor::II => b'E',
+            Rotor::III => b'V',
+            Rotor::IV => b'J',
+            Rotor::V => b'Z',
+        };
+        letter - b'A'
+    }
+}
+
+impl FromStr for Rotor {
+    type Err = CipherError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rotor::ALL
+            .into_iter()
+            .find(|rotor| rotor.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| CipherError::InvalidKey(format!("unknown Enigma rotor '{}', expected I-V", s)))
+    }
+}
+
+/// Reflector (Umkehrwalze) / Reflector (Umkehrwalze)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reflector {
+    B,
+    C,
+}
+
+impl Reflector {
+    pub fn name(self) -> &'static str {
+        match self {
+            Reflector::B => "B",
+            Reflector::C => "C",
+        }
+    }
+
+    fn wiring(self) -> &'static [u8; 26] {
+        match self {
+            Reflector::B => b"YRUHQSLDPXNGOKMIEBFZCWVJAT",
+            Reflector::C => b"FVPJIAOYEDRZXWGCTKUQSBNMHL",
+        }
+    }
+
+    /// Label used in signal traces / Etiqueta usada en las trazas de la señal
+    fn label(self) -> &'static str {
+        match self {
+            Reflector::B => "UKW-B",
+            Reflector::C => "UKW-C",
+        }
+    }
+}
+
+impl FromStr for Reflector {
+    type Err = CipherError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().trim_start_matches("UKW-") {
+            "B" => Ok(Reflector::B),
+            "C" => Ok(Reflector::C),
+            _ => Err(CipherError::InvalidKey(format!("unknown Enigma reflector '{}', expected B or C", s))),
+        }
+    }
+}
+
+// === SETTINGS / CONFIGURACIÓN ===
+
+/// A full machine setting; letters are stored as 0-25 and every triple runs left to right
+/// Una configuración completa; las letras se guardan como 0-25 y cada terna va de izquierda a derecha
+///
+/// As text it reads like a key sheet line, e.g. `II IV V B BUL BLA AV BS CG DL FU HZ IN KM OW RX`:
+/// rotor order, reflector, ring settings (`BUL` or `02-21-12`), start positions, then plugboard pairs.
+/// Como texto se lee como una línea de hoja de claves, p. ej. `II IV V B BUL BLA AV BS CG DL FU HZ IN KM OW RX`:
+/// orden de rotores, reflector, anillos (`BUL` o `02-21-12`), posiciones iniciales y pares del clavijero.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnigmaSettings {
+    pub rotors: [Rotor; 3],
+    pub reflector: Reflector,
+    pub rings: [u8; 3],           // Ringstellung / Posición de los anillos
+    pub positions: [u8; 3],       // Letters in the windows at the start / Letras en las ventanas al empezar
+    pub plugboard: Vec<(u8, u8)>, // Swapped letter pairs / Pares de letras intercambiadas
+}
+
+impl Default for EnigmaSettings {
+    /// Rotors I II III, reflector B, rings and start at AAA, no plugs
+    /// Rotores I II III, reflector B, anillos e inicio en AAA, sin clavijas
+    fn default() -> Self {
+        Self {
+            rotors: [Rotor::I, Rotor::II, Rotor::III],
+            reflector: Reflector::B,
+            rings: [0; 3],
+            positions: [0; 3],
+            plugboard: Vec::new(),
+        }
+    }
+}
+
+impl EnigmaSettings {
+    /// Check what the physical machine would not allow / Comprobar lo que la máquina física no permitiría
+    pub fn validate(&self) -> Result<(), CipherError> {
+        let [left, middle, right] = self.rotors;
+        if left == middle || left == right || middle == right {
+            return Err(CipherError::InvalidKey("each Enigma rotor can only be used once".to_string()));
+        }
+        if self.rings.iter().chain(&self.positions).any(|&p| p >= 26) {
+            return Err(CipherError::InvalidKey("Enigma rings and positions must be A-Z".to_string()));
+        }
+        let mut used = [false; 26];
+        for &(a, b) in &self.plugboard {
+            for letter in [a, b] {
+                if letter >= 26 || a == b || std::mem::replace(&mut used[letter as usize], true) {
+                    return Err(CipherError::InvalidKey(format!(
+                        "invalid plugboard pair {}{}: letters must differ and appear once",
+                        letter_char(a),
+                        letter_char(b)
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Random rotor order, reflector, rings, start and ten plugboard cables
+    /// Orden de rotores, reflector, anillos, inicio y diez cables de clavijero aleatorios
+    pub fn random(rng: &mut SecureRandom) -> Self {
+        // Partial Fisher-Yates shuffles / Barajados Fisher-Yates parciales
+        let mut rotors = Rotor::ALL;
+        for i in 0..3 {
+            rotors.swap(i, i + rng.below((Rotor::ALL.len() - i) as u32) as usize);
+        }
+        let mut letters: [u8; 26] = std::array::from_fn(|i| i as u8);
+        for i in 0..2 * PLUGBOARD_CABLES {
+            letters.swap(i, i + rng.below((26 - i) as u32) as usize);
+        }
+        let reflector = if rng.below(2) == 0 { Reflector::B } else { Reflector::C };
+        let mut letter = || rng.below(26) as u8;
+        Self {
+            rotors: [rotors[0], rotors[1], rotors[2]],
+            reflector,
+            rings: [letter(), letter(), letter()],
+            positions: [letter(), letter(), letter()],
+            plugboard: letters[..2 * PLUGBOARD_CABLES].chunks(2).map(|pair| (pair[0], pair[1])).collect(),
+        }
+    }
+}
+
+fn letter_char(index: u8) -> char {
+    (b'A' + index % 26) as char
+}
+
+/// Three letters (`BUL`) or three numbers 1-26 joined by dashes (`02-21-12`)
+/// Tres letras (`BUL`) o tres números 1-26 unidos por guiones (`02-21-12`)
+fn parse_triple(s: &str, what: &str) -> Result<[u8; 3], CipherError> {
+    let invalid = || CipherError::InvalidKey(format!("Enigma {} '{}' must be three letters or 01-26 numbers", what, s));
+    let values: Vec<u8> = if s.contains('-') {
+        s.split('-')
+            .map(|n| n.parse::<u8>().ok().filter(|n| (1..=26).contains(n)).map(|n| n - 1))
+            .collect::<Option<_>>()
+            .ok_or_else(invalid)?
+    } else if s.bytes().all(|b| b.is_ascii_alphabetic()) {
+        s.bytes().map(|b| b.to_ascii_uppercase() - b'A').collect()
+    } else {
+        return Err(invalid());
+    };
+    values.try_into().map_err(|_| invalid())
+}
+
+impl FromStr for EnigmaSettings {
+    type Err = CipherError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        if words.len() < 6 {
+            return Err(CipherError::InvalidKey(
+                "Enigma settings need rotors, reflector, rings and start, e.g. 'I II III B AAA AAA'".to_string(),
+            ));
+        }
+        let plugboard = words[6..]
+            .iter()
+            .map(|pair| match pair.as_bytes() {
+                [a, b] if a.is_ascii_alphabetic() && b.is_ascii_alphabetic() => {
+                    Ok((a.to_ascii_uppercase() - b'A', b.to_ascii_uppercase() - b'A'))
+                }
+                _ => Err(CipherError::InvalidKey(format!("plugboard pair '{}' must be two letters", pair))),
+            })
+            .collect::<Result<_, _>>()?;
+        let settings = Self {
+            rotors: [words[0].parse()?, words[1].parse()?, words[2].parse()?],
+            reflector: words[3].parse()?,
+            rings: parse_triple(words[4], "rings")?,
+            positions: parse_triple(words[5], "start")?,
+            plugboard,
+        };
+        settings.validate()?;
+        Ok(settings)
+    }
+}
+
+impl fmt::Display for EnigmaSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [left, middle, right] = self.rotors;
+        let triple = |t: [u8; 3]| t.iter().map(|&p| letter_char(p)).collect::<String>();
+        write!(f, "{} {} {} {} {} {}", left.name(), middle.name(), right.name(), self.reflector.name(),
+            triple(self.rings), triple(self.positions))?;
+        for &(a, b) in &self.plugboard {
+            write!(f, " {}{}", letter_char(a), letter_char(b))?;
+        }
+        Ok(())
+    }
+}
+
+// === MACHINE / MÁQUINA ===
+
+/// One key press seen from inside the machine / Una pulsación de tecla vista desde dentro de la máquina
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceStep {
+    pub window: [char; 3],                 // Rotor letters after stepping / Letras de los rotores tras el paso
+    pub input: char,                       // Key pressed / Tecla pulsada
+    pub path: Vec<(&'static str, char)>,   // Each stage and the letter leaving it / Cada etapa y la letra que sale
+}
+
+impl TraceStep {
+    /// Lamp that lights up, the last letter of the path / Lámpara que se enciende, la última letra del camino
+    pub fn output(&self) -> char {
+        self.path.last().map_or(self.input, |&(_, c)| c)
+    }
+}
+
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.window.iter().collect::<String>(), self.input)?;
+        for (stage, letter) in &self.path {
+            write!(f, " > {} {}", stage, letter)?;
+        }
+        write!(f, " = {}", self.output())
+    }
+}
+
+/// Enigma I / M3 simulator with double-stepping and plugboard
+/// Simulador de Enigma I / M3 con doble paso y clavijero
+///
+/// Only ASCII letters go through the rotors and step them; case is kept and other bytes pass
+/// unchanged. Every message starts from the configured positions, and since the machine is its own
+/// inverse, decrypting is the same operation as encrypting.
+/// Solo las letras ASCII pasan por los rotores y los hacen avanzar; se conserva el caso y los demás
+/// bytes pasan sin cambios. Cada mensaje empieza en las posiciones configuradas y, como la máquina es
+/// su propia inversa, descifrar es la misma operación que cifrar.
+#[derive(Clone)]
+pub struct EnigmaCipher {
+    settings: EnigmaSettings,
+    forward: [[u8; 26]; 3],  // Rotor wirings right-to-left entry / Cableado de los rotores a la ida
+    backward: [[u8; 26]; 3], // Inverse wirings for the way back / Cableado inverso para la vuelta
+    plugboard: [u8; 26],
+}
+
+impl fmt::Debug for EnigmaCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EnigmaCipher").field("settings", &Redacted).finish_non_exhaustive()
+    }
+}
+
+impl EnigmaCipher {
+    pub fn new(settings: EnigmaSettings) -> Result<Self, CipherError> {
+        settings.validate()?;
+        let mut forward = [[0u8; 26]; 3];
+        let mut backward = [[0u8; 26]; 3];
+        for (slot, rotor) in settings.rotors.iter().enumerate() {
+            for (contact, &wire) in rotor.wiring().iter().enumerate() {
+                forward[slot][contact] = wire - b'A';
+                backward[slot][(wire - b'A') as usize] = contact as u8;
+            }
+        }
+        let mut plugboard: [u8; 26] = std::array::from_fn(|i| i as u8);
+        for &(a, b) in &settings.plugboard {
+            plugboard.swap(a as usize, b as usize);
+        }
+        Ok(Self { settings, forward, backward, plugboard })
+    }
+
+    pub fn settings(&self) -> &EnigmaSettings {
+        &self.settings
+    }
+
+    /// Advance the rotors before a key press. The middle rotor at its notch turns itself and the left
+    /// rotor (the double step); otherwise the right rotor at its notch turns the middle one.
+    /// Avanzar los rotores antes de una pulsación. El rotor central en su muesca gira él mismo y el de la
+    /// izquierda (el doble paso); si no, el rotor derecho en su muesca gira el central.
+    fn step(&self, window: &mut [u8; 3]) {
+        let [_, middle, right] = self.settings.rotors;
+        if window[1] == middle.notch() {
+            window[0] = (window[0] + 1) % 26;
+            window[1] = (window[1] + 1) % 26;
+        } else if window[2] == right.notch() {
+            window[1] = (window[1] + 1) % 26;
+        }
+        window[2] = (window[2] + 1) % 26;
+    }
+
+    /// Signal through the rotor in `slot`, shifted by its position minus its ring setting
+    /// Señal a través del rotor en `slot`, desplazada por su posición menos su anillo
+    fn through(&self, slot: usize, window: &[u8; 3], letter: u8, backward: bool) -> u8 {
+        let offset = (window[slot] + 26 - self.settings.rings[slot]) % 26;
+        let wiring = if backward { &self.backward[slot] } else { &self.forward[slot] };
+        (wiring[((letter + offset) % 26) as usize] + 26 - offset) % 26
+    }
+
+    /// Step, then send one letter (0-25) through the machine, recording each stage when tracing
+    /// Avanzar y luego enviar una letra (0-25) por la máquina, anotando cada etapa al trazar
+    fn press(&self, window: &mut [u8; 3], letter: u8, mut path: Option<&mut Vec<(&'static str, char)>>) -> u8 {
+        self.step(window);
+        let mut mark = |stage: &'static str, x: u8| {
+            if let Some(path) = path.as_deref_mut() {
+                path.push((stage, letter_char(x)));
+            }
+        };
+        let mut x = self.plugboard[letter as usize];
+        mark("plugboard", x);
+        for slot in (0..3).rev() {
+            x = self.through(slot, window, x, false);
+            mark(self.settings.rotors[slot].name(), x);
+        }
+        x = self.settings.reflector.wiring()[x as usize] - b'A';
+        mark(self.settings.reflector.label(), x);
+        for slot in 0..3 {
+            x = self.through(slot, window, x, true);
+            mark(self.settings.rotors[slot].name(), x);
+        }
+        x = self.plugboard[x as usize];
+        mark("plugboard", x);
+        x
+    }
+
+    /// The signal path of every letter in `text`, starting from the configured positions
+    /// El camino de la señal de cada letra de `text`, empezando en las posiciones configuradas
+    pub fn trace(&self, text: &[u8]) -> Vec<TraceStep> {
+        let mut window = self.settings.positions;
+        text.iter()
+            .filter(|b| b.is_ascii_alphabetic())
+            .map(|&b| {
+                let mut path = Vec::with_capacity(9);
+                self.press(&mut window, b.to_ascii_uppercase() - b'A', Some(&mut path));
+                TraceStep { window: window.map(letter_char), input: b.to_ascii_uppercase() as char, path }
+            })
+            .collect()
+    }
+}
+
+impl Cipher for EnigmaCipher {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let mut window = self.settings.positions;
+        Ok(data
+            .iter()
+            .map(|&b| {
+                if !b.is_ascii_alphabetic() {
+                    return b;
+                }
+                let base = if b.is_ascii_lowercase() { b'a' } else { b'A' };
+                base + self.press(&mut window, b.to_ascii_uppercase() - b'A', None)
+            })
+            .collect())
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        self.encrypt(data)
+    }
+}
+
+impl CipherFactory for EnigmaCipher {
+    const NAME: &'static str = "Enigma";
+    const DESCRIPTION: &'static str = "Enigma I / M3 Machine / Máquina Enigma I / M3";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[SETTINGS_PARAM]
+    }
+
+    fn from_params(params: &CipherParams) -> Result<Self, CipherError> {
+        Self::new(params.require(SETTINGS_PARAM.name)?.parse()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn machine(settings: &str) -> EnigmaCipher {
+        EnigmaCipher::new(settings.parse().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_default_machine() {
+        let cipher = EnigmaCipher::new(EnigmaSettings::default()).unwrap();
+        assert_eq!(cipher.encrypt(b"AAAAA").unwrap(), b"BDZGO");
+        assert_eq!(cipher.decrypt(b"bdzgo").unwrap(), b"aaaaa");
+    }
+
+    #[test]
+    fn test_double_stepping() {
+        // ADU → ADV → AEW → BFX → BFY: the middle rotor steps twice in a row
+        // ADU → ADV → AEW → BFX → BFY: el rotor central avanza dos veces seguidas
+        let windows: Vec<String> = machine("I II III B AAA ADU")
+            .trace(b"AAAA")
+            .iter()
+            .map(|step| step.window.iter().collect())
+            .collect();
+        assert_eq!(windows, ["ADV", "AEW", "BFX", "BFY"]);
+    }
+
+    #[test]
+    fn test_operation_barbarossa_message() {
+        // Sent on 7 July 1941: the indicator KCH read at WXC gives the message key BLA
+        // Enviado el 7 de julio de 1941: el indicador KCH leído en WXC da la clave de mensaje BLA
+        let daily_key = "II IV V B 02-21-12 WXC AV BS CG DL FU HZ IN KM OW RX";
+        assert_eq!(machine(daily_key).decrypt(b"KCH").unwrap(), b"BLA");
+
+        let ciphertext = "EDPUD NRGYS ZRCXN UYTPO MRMBO FKTBZ REZKM LXLVE FGUEY SIOZV EQMIK UBPMM YLKLT TDEIS \
+                          MDICA GYKUA CTCDO MOHWX MUUIA UBSTS LRNBZ SZWNR FXWFY SSXJZ VIJHI DISHP RKLKA YUPAD \
+                          TXQSP INQMA TLPIF SVKDA SCTAC DPBOP VHJK";
+        let plaintext = machine("II IV V B BUL BLA AV BS CG DL FU HZ IN KM OW RX").decrypt(ciphertext.as_bytes()).unwrap();
+        assert_eq!(
+            String::from_utf8(plaintext).unwrap(),
+            "AUFKL XABTE ILUNG XVONX KURTI NOWAX KURTI NOWAX NORDW ESTLX SEBEZ XSEBE ZXUAF FLIEG ERSTR ASZER IQTUN \
+             GXDUB ROWKI XDUBR OWKIX OPOTS CHKAX OPOTS CHKAX UMXEI NSAQT DREIN ULLXU HRANG ETRET ENXAN GRIFF XINFX \
+             RGTX"
+        );
+    }
+
+    #[test]
+    fn test_settings_text_and_validation() {
+        let text = "II IV V B BUL BLA AV BS CG DL FU HZ IN KM OW RX";
+        let settings: EnigmaSettings = text.parse().unwrap();
+        assert_eq!(settings.to_string(), text);
+        assert_eq!(settings.rings, [1, 20, 11]);
+        assert_eq!("ii iv v ukw-b 02-21-12 bla av bs".parse::<EnigmaSettings>().unwrap().rings, [1, 20, 11]);
+
+        for bad in ["I I III B AAA AAA", "I II VI B AAA AAA", "I II III A AAA AAA", "I II III B AA AAA",
+            "I II III B 00-01-01 AAA", "I II III B AAA AAA AB BC", "I II III B AAA AAA AA", "I II III B AAA"] {
+            assert!(matches!(bad.parse::<EnigmaSettings>(), Err(CipherError::InvalidKey(_))), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_trace_follows_the_signal() {
+        let cipher = machine("I II III B AAA AAA");
+        let steps = cipher.trace(b"a!");
+        assert_eq!(steps.len(), 1);
+        let names: Vec<&str> = steps[0].path.iter().map(|&(stage, _)| stage).collect();
+        assert_eq!(names, ["plugboard", "III", "II", "I", "UKW-B", "I", "II", "III", "plugboard"]);
+        assert_eq!(steps[0].output(), 'B');
+        assert_eq!(steps[0].to_string(), "[AAB] A > plugboard A > III C > II D > I F > UKW-B S > I S > II E > III B > plugboard B = B");
+    }
+
+    #[test]
+    fn test_random_settings_round_trip() {
+        let mut rng = SecureRandom::from_seed([19; 32]);
+        for _ in 0..10 {
+            let settings = EnigmaSettings::random(&mut rng);
+            assert_eq!(settings.plugboard.len(), PLUGBOARD_CABLES);
+            assert_eq!(settings.to_string().parse::<EnigmaSettings>().unwrap(), settings);
+            let cipher = EnigmaCipher::new(settings).unwrap();
+            let encrypted = cipher.encrypt(b"Keine besonderen Ereignisse, 0800 Uhr").unwrap();
+            assert_eq!(cipher.decrypt(&encrypted).unwrap(), b"Keine besonderen Ereignisse, 0800 Uhr");
+            // A letter never encrypts to itself / Una letra nunca se cifra como sí misma
+            assert!(cipher.encrypt(&[b'E'; 100]).unwrap().iter().all(|&b| b != b'E'));
+        }
+    }
+}
@@ -0,0 +1,217 @@
+// Hill Cipher: Blocks of Letters Multiplied by a Key Matrix mod 26
+// Cifrado de Hill: Bloques de Letras Multiplicados por una Matriz Clave mod 26
+
+use std::fmt;
+
+use crate::cipher::{Cipher, CipherFactory, CipherParams, ParamKind, ParamSpec};
+use crate::error::CipherError;
+use crate::random::SecureRandom;
+use crate::secret::Redacted;
+
+use super::mod_inverse;
+
+/// Largest matrix accepted (25 key letters) / Matriz más grande aceptada (25 letras de clave)
+pub const MAX_SIZE: usize = 5;
+
+const KEY_PARAM: ParamSpec = ParamSpec {
+    name: "key",
+    prompt: "Enter key letters, 4, 9, 16 or 25 of them (e.g. GYBNQKURP) / Ingresa las letras de la clave, 4, 9, 16 o 25 (p. ej. GYBNQKURP)",
+    kind: ParamKind::Matrix(3),
+};
+
+/// Hill cipher: each block of n letters is multiplied by an n×n key matrix mod 26
+/// Cifrado de Hill: cada bloque de n letras se multiplica por una matriz clave n×n mod 26
+///
+/// Only ASCII letters are used and case is kept. When the letter count is not a multiple of n, X is
+/// appended until the last block is full, and decryption keeps that padding.
+/// Solo se usan letras ASCII y se conserva el caso. Si el número de letras no es múltiplo de n, se
+/// añaden X al final hasta llenar el último bloque, y el descifrado conserva ese relleno.
+#[derive(Clone)]
+pub struct HillCipher {
+    size: usize,
+    matrix: Vec<i64>,  // Row by row / Fila por fila
+    inverse: Vec<i64>, // Inverse mod 26 / Inversa mod 26
+}
+
+impl fmt::Debug for HillCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HillCipher").field("size", &self.size).field("matrix", &Redacted).finish_non_exhaustive()
+    }
+}
+
+/// Determinant by cofactor expansion along the first row; matrices are at most 5x5
+/// Determinante por expansión de cofactores en la primera fila; las matrices son de 5x5 como mucho
+fn determinant(matrix: &[i64], size: usize) -> i64 {
+    if size == 1 {
+        return matrix[0];
+    }
+    (0..size)
+        .map(|col| {
+            let sign = if col % 2 == 0 { 1 } else { -1 };
+            sign * matrix[col] * determinant(&minor(matrix, size, 0, col), size - 1)
+        })
+        .sum()
+}
+
+/// Matrix without row `skip_row` and column `skip_col` / Matriz sin la fila `skip_row` ni la columna `skip_col`
+fn minor(matrix: &[i64], size: usize, skip_row: usize, skip_col: usize) -> Vec<i64> {
+    (0..size * size)
+        .filter(|&i| i / size != skip_row && i % size != skip_col)
+        .map(|i| matrix[i])
+        .collect()
+}
+
+impl HillCipher {
+    /// Key letters fill the matrix row by row (A = 0); their count must be a square from 4 to 25, and the
+    /// determinant coprime with 26 so the matrix can be inverted
+    /// Las letras de la clave llenan la matriz fila por fila (A = 0); su número debe ser un cuadrado de 4
+    /// a 25, y el determinante coprimo con 26 para poder invertir la matriz
+    pub fn new(key: &[u8]) -> Result<Self, CipherError> {
+        if key.is_empty() {
+            return Err(CipherError::EmptyKey);
+        }
+        if !key.iter().all(u8::is_ascii_alphabetic) {
+            return Err(CipherError::InvalidKey("Hill key must contain only letters".to_string()));
+        }
+        let size = (2..=MAX_SIZE).find(|n| n * n == key.len()).ok_or_else(|| {
+            CipherError::InvalidKey(format!("Hill key needs 4, 9, 16 or 25 letters, got {}", key.len()))
+        })?;
+        let matrix: Vec<i64> = key.iter().map(|k| (k.to_ascii_uppercase() - b'A') as i64).collect();
+
+        let det = determinant(&matrix, size).rem_euclid(26);
+        let det_inverse = mod_inverse(det, 26).ok_or_else(|| {
+            CipherError::InvalidKey(format!("Hill matrix is not invertible mod 26 (determinant {})", det))
+        })?;
+        // Inverse = det⁻¹ · adjugate, where adjugate[i][j] is the (j, i) cofactor
+        // Inversa = det⁻¹ · adjunta, donde adjunta[i][j] es el cofactor (j, i)
+        let inverse = (0..size * size)
+            .map(|i| {
+                let (row, col) = (i / size, i % size);
+                let sign = if (row + col) % 2 == 0 { 1 } else { -1 };
+                let cofactor = sign * determinant(&minor(&matrix, size, col, row), size - 1);
+                (det_inverse * cofactor).rem_euclid(26)
+            })
+            .collect();
+        Ok(Self { size, matrix, inverse })
+    }
+
+    /// Random key of `size`² letters whose matrix is invertible / Clave aleatoria de `size`² letras con matriz invertible
+    pub fn random_key(rng: &mut SecureRandom, size: usize) -> String {
+        loop {
+            let key = rng.text_key(size * size);
+            if Self::new(key.as_bytes()).is_ok() {
+                return key;
+            }
+        }
+    }
+
+    /// Multiply the letters at `positions` by `matrix` / Multiplicar las letras en `positions` por `matrix`
+    fn apply_block(&self, data: &mut [u8], positions: &[usize], matrix: &[i64]) {
+        let block: Vec<i64> = positions.iter().map(|&p| (data[p].to_ascii_uppercase() - b'A') as i64).collect();
+        for (row, &p) in positions.iter().enumerate() {
+            let value = (0..self.size).map(|col| matrix[row * self.size + col] * block[col]).sum::<i64>() % 26;
+            let base = if data[p].is_ascii_lowercase() { b'a' } else { b'A' };
+            data[p] = base + value as u8;
+        }
+    }
+
+    fn letter_positions(data: &[u8]) -> Vec<usize> {
+        (0..data.len()).filter(|&i| data[i].is_ascii_alphabetic()).collect()
+    }
+
+    /// `data` with X appended until its letters fill whole blocks of `size`; also what decryption gives back
+    /// `data` con X añadidas hasta que sus letras llenen bloques enteros de `size`; también lo que devuelve el descifrado
+    pub fn pad(data: &[u8], size: usize) -> Vec<u8> {
+        let letters = data.iter().filter(|b| b.is_ascii_alphabetic()).count();
+        let mut out = data.to_vec();
+        out.resize(data.len() + (size - letters % size) % size, b'X');
+        out
+    }
+}
+
+impl Cipher for HillCipher {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let mut out = Self::pad(data, self.size);
+        let letters = Self::letter_positions(&out);
+        for block in letters.chunks_exact(self.size) {
+            self.apply_block(&mut out, block, &self.matrix);
+        }
+        Ok(out)
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let mut out = data.to_vec();
+        let letters = Self::letter_positions(data);
+        if !letters.len().is_multiple_of(self.size) {
+            return Err(CipherError::MalformedInput {
+                position: data.len(),
+                reason: format!("Hill ciphertext needs a multiple of {} letters, got {}", self.size, letters.len()),
+            });
+        }
+        for block in letters.chunks_exact(self.size) {
+            self.apply_block(&mut out, block, &self.inverse);
+        }
+        Ok(out)
+    }
+}
+
+impl CipherFactory for HillCipher {
+    const NAME: &'static str = "Hill";
+    const DESCRIPTION: &'static str = "Hill Cipher / Cifrado de Hill";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[KEY_PARAM]
+    }
+
+    fn from_params(params: &CipherParams) -> Result<Self, CipherError> {
+        Self::new(params.require(KEY_PARAM.name)?.trim().as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_vectors() {
+        let cipher = HillCipher::new(b"GYBNQKURP").unwrap();
+        assert_eq!(cipher.encrypt(b"ACT").unwrap(), b"POH");
+        assert_eq!(cipher.decrypt(b"POH").unwrap(), b"ACT");
+
+        let cipher = HillCipher::new(b"hill").unwrap();
+        assert_eq!(cipher.encrypt(b"short example").unwrap(), b"apadj tftwlfj");
+        assert_eq!(cipher.decrypt(b"apadj tftwlfj").unwrap(), b"short example");
+    }
+
+    #[test]
+    fn test_rejects_bad_keys() {
+        assert!(matches!(HillCipher::new(b""), Err(CipherError::EmptyKey)));
+        assert!(matches!(HillCipher::new(b"ABCDE"), Err(CipherError::InvalidKey(_))));
+        assert!(matches!(HillCipher::new(b"AB1D"), Err(CipherError::InvalidKey(_))));
+        // Determinant 0, then 2: neither has an inverse mod 26 / Determinante 0 y luego 2: sin inversa mod 26
+        assert!(matches!(HillCipher::new(b"AAAA"), Err(CipherError::InvalidKey(_))));
+        assert!(matches!(HillCipher::new(b"DBBB"), Err(CipherError::InvalidKey(_))));
+    }
+
+    #[test]
+    fn test_partial_blocks_are_padded_with_x() {
+        let cipher = HillCipher::new(b"GYBNQKURP").unwrap();
+        assert_eq!(cipher.encrypt(b"AC").unwrap(), cipher.encrypt(b"ACX").unwrap());
+        assert_ne!(cipher.encrypt(b"a").unwrap(), b"a");
+        for text in [&b"Attack at dawn!"[..], b"a", b"ab", b"abcd", b"Four letters: done", b"\x00\xffbytes", b""] {
+            let encrypted = cipher.encrypt(text).unwrap();
+            assert_eq!(encrypted.len(), HillCipher::pad(text, 3).len());
+            assert_eq!(cipher.decrypt(&encrypted).unwrap(), HillCipher::pad(text, 3));
+        }
+        assert_eq!(HillCipher::pad(b"ab, cd", 3), b"ab, cdXX".to_vec());
+        assert!(matches!(cipher.decrypt(b"ABCD"), Err(CipherError::MalformedInput { position: 4, .. })));
+        let mut rng = SecureRandom::from_seed([4; 32]);
+        let key = HillCipher::random_key(&mut rng, 4);
+        assert_eq!(key.len(), 16);
+        assert!(HillCipher::new(key.as_bytes()).is_ok());
+    }
+}
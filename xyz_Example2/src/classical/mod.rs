@@ -0,0 +1,653 @@
+// Classical Ciphers: Caesar, ROT13, XOR, Vigenère and the Submodules Below
+// Cifrados Clásicos: César, ROT13, XOR, Vigenère y los Submódulos de Abajo
+
+pub mod affine;
+pub mod enigma;
+pub mod hill;
+pub mod playfair;
+pub mod transposition;
+
+pub use affine::{AffineCipher, AtbashCipher};
+pub use enigma::{EnigmaCipher, EnigmaSettings};
+pub use hill::HillCipher;
+pub use playfair::PlayfairCipher;
+pub use transposition::{ColumnarCipher, RailFenceCipher};
+
+use std::fmt;
+
+use crate::alphabet::Alphabet;
+use crate::cipher::{Cipher, CipherFactory, CipherParams, ParamKind, ParamSpec};
+use crate::error::CipherError;
+use crate::kdf::KeyDerivation;
+use crate::secret::{Redacted, SecretKey};
+
+const SHIFT_PARAM: ParamSpec = ParamSpec {
+    name: "shift",
+    prompt: "Enter shift value (1-25) / Ingresa valor de desplazamiento (1-25)",
+    kind: ParamKind::Shift,
+};
+
+const KEY_PARAM: ParamSpec = ParamSpec {
+    name: "key",
+    prompt: "Enter encryption key / Ingresa clave de cifrado",
+    kind: ParamKind::Text,
+};
+
+const ALPHABET_PARAM: ParamSpec = ParamSpec {
+    name: "alphabet",
+    prompt: "Alphabet (ascii/spanish/greek or its letters) [ascii] / Alfabeto (ascii/spanish/greek o sus letras) [ascii]",
+    kind: ParamKind::Data,
+};
+
+/// Shift every ASCII letter by `shift` positions, leaving other bytes alone
+/// Desplazar cada letra ASCII `shift` posiciones, dejando los demás bytes intactos
+fn shift_letter(b: u8, shift: u8) -> u8 {
+    if b.is_ascii_alphabetic() {
+        let base = if b.is_ascii_lowercase() { b'a' } else { b'A' };
+        (((b - base) + shift) % 26) + base
+    } else {
+        b
+    }
+}
+
+/// Apply `f` to the 0-25 index of every ASCII letter, keeping its case; other bytes pass through
+/// Aplicar `f` al índice 0-25 de cada letra ASCII, conservando su caso; los demás bytes no cambian
+fn substitute(data: &[u8], f: impl Fn(u8) -> u8) -> Vec<u8> {
+    data.iter()
+        .map(|&b| match b {
+            b'a'..=b'z' => b'a' + f(b - b'a'),
+            b'A'..=b'Z' => b'A' + f(b - b'A'),
+            _ => b,
+        })
+        .collect()
+}
+
+/// Multiplicative inverse of `value` modulo `modulus`, if they are coprime (extended Euclid)
+/// Inverso multiplicativo de `value` módulo `modulus`, si son coprimos (Euclides extendido)
+fn mod_inverse(value: i64, modulus: i64) -> Option<i64> {
+    let (mut r0, mut r1) = (value.rem_euclid(modulus), modulus);
+    let (mut s0, mut s1) = (1i64, 0i64);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (s0, s1) = (s1, s0 - q * s1);
+    }
+    (r0 == 1).then(|| s0.rem_euclid(modulus))
+}
+
+// Optional alphabet parameter; missing or empty means ASCII
+// Parámetro de alfabeto opcional; ausente o vacío significa ASCII
+fn alphabet_param(params: &CipherParams) -> Result<Alphabet, CipherError> {
+    Alphabet::parse(params.get(ALPHABET_PARAM.name).unwrap_or(""))
+}
+
+/// Shift every letter of `alphabet` in UTF-8 `data` by `shift_at(char index)` places
+/// Desplazar cada letra de `alphabet` en los datos UTF-8 `shift_at(índice de carácter)` posiciones
+fn shift_chars(data: &[u8], alphabet: &Alphabet, shift_at: impl Fn(usize) -> usize) -> Result<Vec<u8>, CipherError> {
+    let text = std::str::from_utf8(data).map_err(|e| CipherError::MalformedInput {
+        position: e.valid_up_to(),
+        reason: "invalid UTF-8".to_string(),
+    })?;
+    let shifted: String = text
+        .chars()
+        .enumerate()
+        .map(|(i, c)| alphabet.shift_char(c, shift_at(i)))
+        .collect();
+    Ok(shifted.into_bytes())
+}
+
+// === CAESAR CIPHER / CIFRADO CÉSAR ===
+
+/// Caesar cipher with a fixed shift over an alphabet (ASCII by default)
+/// Cifrado César con desplazamiento fijo sobre un alfabeto (ASCII por defecto)
+#[derive(Clone)]
+pub struct CaesarCipher {
+    shift: usize,
+    alphabet: Alphabet,
+}
+
+impl fmt::Debug for CaesarCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CaesarCipher").field("shift", &Redacted).field("alphabet", &self.alphabet).finish()
+    }
+}
+
+impl CaesarCipher {
+    /// Shift must be in 0-25 / El desplazamiento debe estar en 0-25
+    pub fn new(shift: u8) -> Result<Self, CipherError> {
+        Self::with_alphabet(shift.into(), Alphabet::ascii())
+    }
+
+    /// Shift must be below the alphabet size; non-ASCII alphabets need UTF-8 input
+    /// El desplazamiento debe ser menor que el tamaño del alfabeto; los alfabetos no ASCII requieren UTF-8
+    pub fn with_alphabet(shift: usize, alphabet: Alphabet) -> Result<Self, CipherError> {
+        if shift >= alphabet.len() {
+            return Err(CipherError::ShiftOutOfRange(shift as i64));
+        }
+        Ok(Self { shift, alphabet })
+    }
+
+    /// ROT over any alphabet: half its size, so ROT13 for ASCII; decrypt undoes it even for odd sizes
+    /// ROT sobre cualquier alfabeto: la mitad de su tamaño, ROT13 en ASCII; descifrar lo deshace aunque sea impar
+    pub fn rot(alphabet: Alphabet) -> Self {
+        Self { shift: alphabet.len() / 2, alphabet }
+    }
+}
+
+impl Cipher for CaesarCipher {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        if !self.alphabet.is_ascii() {
+            return shift_chars(data, &self.alphabet, |_| self.shift);
+        }
+        Ok(data.iter().map(|&b| shift_letter(b, self.shift as u8)).collect())
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        // Shift forward by the complement / Desplazar hacia adelante por el complemento
+        let back = self.alphabet.len() - self.shift;
+        if !self.alphabet.is_ascii() {
+            return shift_chars(data, &self.alphabet, |_| back);
+        }
+        Ok(data.iter().map(|&b| shift_letter(b, back as u8)).collect())
+    }
+}
+
+impl CipherFactory for CaesarCipher {
+    const NAME: &'static str = "Caesar";
+    const DESCRIPTION: &'static str = "Caesar Cipher / Cifrado César";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[ALPHABET_PARAM, SHIFT_PARAM]
+    }
+
+    fn from_params(params: &CipherParams) -> Result<Self, CipherError> {
+        let alphabet = alphabet_param(params)?;
+        Self::with_alphabet(params.shift_below(SHIFT_PARAM.name, alphabet.len())?, alphabet)
+    }
+}
+
+// === ROT13 CIPHER / CIFRADO ROT13 ===
+
+/// ROT13: Caesar with shift 13, its own inverse / César con desplazamiento 13, su propia inversa
+#[derive(Clone, Debug, Default)]
+pub struct Rot13Cipher;
+
+impl Cipher for Rot13Cipher {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        Ok(data.iter().map(|&b| shift_letter(b, 13)).collect())
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        self.encrypt(data)
+    }
+}
+
+impl CipherFactory for Rot13Cipher {
+    const NAME: &'static str = "ROT13";
+    const DESCRIPTION: &'static str = "ROT13 / ROT13";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[]
+    }
+
+    fn from_params(_: &CipherParams) -> Result<Self, CipherError> {
+        Ok(Self)
+    }
+}
+
+// === XOR CIPHER / CIFRADO XOR ===
+
+/// Repeating-key XOR cipher / Cifrado XOR con clave repetida
+#[derive(Debug)]
+pub struct XorCipher {
+    key: SecretKey,
+    start: u64, // Stream position of the first byte / Posición en el flujo del primer byte
+}
+
+impl XorCipher {
+    /// Key must not be empty / La clave no debe estar vacía
+    pub fn new(key: &[u8]) -> Result<Self, CipherError> {
+        if key.is_empty() {
+            return Err(CipherError::EmptyKey);
+        }
+        Ok(Self { key: SecretKey::new(key), start: 0 })
+    }
+
+    /// Continue the keystream from `position`, so chunks match a one-shot pass
+    /// Continuar el flujo de clave desde `position`, para que los fragmentos coincidan con una pasada única
+    pub fn starting_at(mut self, position: u64) -> Self {
+        self.start = position;
+        self
+    }
+
+    fn key_byte(&self, i: usize) -> u8 {
+        self.key.expose()[((self.start + i as u64) % self.key.len() as u64) as usize]
+    }
+}
+
+// The key is copied on purpose, not through a derive / La clave se copia a propósito, no mediante un derive
+impl Clone for XorCipher {
+    fn clone(&self) -> Self {
+        Self { key: self.key.duplicate(), start: self.start }
+    }
+}
+
+impl Cipher for XorCipher {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        Ok(data.iter()
+            .enumerate()
+            .map(|(i, &b)| b ^ self.key_byte(i))
+            .collect())
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        // XOR is its own inverse / XOR es su propia inversa
+        self.encrypt(data)
+    }
+}
+
+impl CipherFactory for XorCipher {
+    const NAME: &'static str = "XOR";
+    const DESCRIPTION: &'static str = "XOR Cipher / Cifrado XOR";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[KEY_PARAM]
+    }
+
+    fn from_params(params: &CipherParams) -> Result<Self, CipherError> {
+        Self::new(&params.bytes(KEY_PARAM.name)?)
+    }
+}
+
+// === VIGENÈRE CIPHER / CIFRADO VIGENÈRE ===
+
+/// Vigenère cipher; the key position advances on every byte (every character for non-ASCII alphabets),
+/// not just letters
+/// Cifrado Vigenère; la posición de la clave avanza en cada byte (cada carácter en alfabetos no ASCII),
+/// no solo en letras
+#[derive(Clone)]
+pub struct VigenereCipher {
+    shifts: Vec<usize>, // Shift for each key position / Desplazamiento de cada posición de la clave
+    start: u64,         // Stream position of the first byte / Posición en el flujo del primer byte
+    alphabet: Alphabet,
+}
+
+impl fmt::Debug for VigenereCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VigenereCipher")
+            .field("shifts", &Redacted)
+            .field("start", &self.start)
+            .field("alphabet", &self.alphabet)
+            .finish()
+    }
+}
+
+impl VigenereCipher {
+    /// Key must not be empty / La clave no debe estar vacía
+    pub fn new(key: &[u8]) -> Result<Self, CipherError> {
+        if key.is_empty() {
+            return Err(CipherError::EmptyKey);
+        }
+        let shifts = key
+            .iter()
+            .map(|&k| if k.is_ascii_alphabetic() { (k.to_ascii_uppercase() - b'A') as usize } else { (k % 26) as usize })
+            .collect();
+        Ok(Self { shifts, start: 0, alphabet: Alphabet::ascii() })
+    }
+
+    /// Key letters shift by their index in `alphabet`; other key characters by their code point modulo its size
+    /// Las letras de la clave desplazan según su índice en `alphabet`; los demás caracteres según su código
+    /// módulo su tamaño
+    pub fn with_alphabet(key: &str, alphabet: Alphabet) -> Result<Self, CipherError> {
+        if alphabet.is_ascii() {
+            return Self::new(key.as_bytes());
+        }
+        if key.is_empty() {
+            return Err(CipherError::EmptyKey);
+        }
+        let shifts = key
+            .chars()
+            .map(|c| alphabet.index_of(c).map_or(c as usize % alphabet.len(), |(i, _)| i))
+            .collect();
+        Ok(Self { shifts, start: 0, alphabet })
+    }
+
+    /// Continue the key from `position`, so chunks match a one-shot pass
+    /// Continuar la clave desde `position`, para que los fragmentos coincidan con una pasada única
+    pub fn starting_at(mut self, position: u64) -> Self {
+        self.start = position;
+        self
+    }
+
+    fn key_shift(&self, i: usize) -> usize {
+        self.shifts[((self.start + i as u64) % self.shifts.len() as u64) as usize]
+    }
+}
+
+impl Cipher for VigenereCipher {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        if !self.alphabet.is_ascii() {
+            return shift_chars(data, &self.alphabet, |i| self.key_shift(i));
+        }
+        Ok(data.iter()
+            .enumerate()
+            .map(|(i, &b)| shift_letter(b, self.key_shift(i) as u8))
+            .collect())
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let len = self.alphabet.len();
+        if !self.alphabet.is_ascii() {
+            return shift_chars(data, &self.alphabet, |i| len - self.key_shift(i));
+        }
+        Ok(data.iter()
+            .enumerate()
+            .map(|(i, &b)| shift_letter(b, (len - self.key_shift(i)) as u8))
+            .collect())
+    }
+}
+
+impl CipherFactory for VigenereCipher {
+    const NAME: &'static str = "Vigenere";
+    const DESCRIPTION: &'static str = "Vigenère Cipher / Cifrado Vigenère";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[ALPHABET_PARAM, KEY_PARAM]
+    }
+
+    fn from_params(params: &CipherParams) -> Result<Self, CipherError> {
+        Self::with_alphabet(params.require(KEY_PARAM.name)?, alphabet_param(params)?)
+    }
+}
+
+// === CONVENIENCE FUNCTIONS / FUNCIONES DE CONVENIENCIA ===
+
+pub fn caesar_encrypt(text: &str, shift: u8) -> Result<String, CipherError> {
+    // Only ASCII letters change, so the output stays valid UTF-8
+    // Solo cambian letras ASCII, así que la salida sigue siendo UTF-8 válido
+    let encrypted = CaesarCipher::new(shift)?.encrypt(text.as_bytes())?;
+    Ok(String::from_utf8_lossy(&encrypted).into_owned())
+}
+
+pub fn caesar_decrypt(text: &str, shift: u8) -> Result<String, CipherError> {
+    let decrypted = CaesarCipher::new(shift)?.decrypt(text.as_bytes())?;
+    Ok(String::from_utf8_lossy(&decrypted).into_owned())
+}
+
+pub fn xor_encrypt(data: &[u8], key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    XorCipher::new(key)?.encrypt(data)
+}
+
+pub fn xor_decrypt(encrypted_data: &[u8], key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    // XOR decryption is the same as encryption
+    // El descifrado XOR es igual que el cifrado
+    xor_encrypt(encrypted_data, key)
+}
+
+pub fn rot13(text: &str) -> Result<String, CipherError> {
+    let encoded = Rot13Cipher.encrypt(text.as_bytes())?;
+    Ok(String::from_utf8_lossy(&encoded).into_owned())
+}
+
+// Text versions over any alphabet; the input is a `&str`, so the output is always valid UTF-8
+// Versiones de texto sobre cualquier alfabeto; la entrada es `&str`, así que la salida siempre es UTF-8 válido
+
+pub fn caesar_encrypt_with(text: &str, shift: usize, alphabet: &Alphabet) -> Result<String, CipherError> {
+    let encrypted = CaesarCipher::with_alphabet(shift, alphabet.clone())?.encrypt(text.as_bytes())?;
+    Ok(String::from_utf8_lossy(&encrypted).into_owned())
+}
+
+pub fn caesar_decrypt_with(text: &str, shift: usize, alphabet: &Alphabet) -> Result<String, CipherError> {
+    let decrypted = CaesarCipher::with_alphabet(shift, alphabet.clone())?.decrypt(text.as_bytes())?;
+    Ok(String::from_utf8_lossy(&decrypted).into_owned())
+}
+
+pub fn rot_encrypt(text: &str, alphabet: &Alphabet) -> Result<String, CipherError> {
+    let encrypted = CaesarCipher::rot(alphabet.clone()).encrypt(text.as_bytes())?;
+    Ok(String::from_utf8_lossy(&encrypted).into_owned())
+}
+
+pub fn rot_decrypt(text: &str, alphabet: &Alphabet) -> Result<String, CipherError> {
+    let decrypted = CaesarCipher::rot(alphabet.clone()).decrypt(text.as_bytes())?;
+    Ok(String::from_utf8_lossy(&decrypted).into_owned())
+}
+
+pub fn vigenere_encrypt_with(text: &str, key: &str, alphabet: &Alphabet) -> Result<String, CipherError> {
+    let encrypted = VigenereCipher::with_alphabet(key, alphabet.clone())?.encrypt(text.as_bytes())?;
+    Ok(String::from_utf8_lossy(&encrypted).into_owned())
+}
+
+pub fn vigenere_decrypt_with(text: &str, key: &str, alphabet: &Alphabet) -> Result<String, CipherError> {
+    let decrypted = VigenereCipher::with_alphabet(key, alphabet.clone())?.decrypt(text.as_bytes())?;
+    Ok(String::from_utf8_lossy(&decrypted).into_owned())
+}
+
+// === SIMPLE ENCRYPTION STRUCT / ESTRUCTURA DE CIFRADO SIMPLE ===
+
+/// Reusable XOR encryption interface / Interfaz reutilizable de cifrado XOR
+pub struct SimpleEncryption {
+    cipher: XorCipher, // Keeps the key in a `SecretKey` / Guarda la clave en un `SecretKey`
+}
+
+impl SimpleEncryption {
+    pub fn new(key: &str) -> Result<Self, CipherError> {
+        Ok(Self {
+            cipher: XorCipher::new(key.as_bytes())?,
+        })
+    }
+    
+    /// Use a stretched password instead of the raw passphrase bytes
+    /// Usar una contraseña estirada en lugar de los bytes crudos de la frase
+    pub fn from_password(password: &str, kdf: &KeyDerivation) -> Result<Self, CipherError> {
+        if password.is_empty() {
+            return Err(CipherError::EmptyKey);
+        }
+        let key = SecretKey::from(kdf.derive(password.as_bytes(), 32)?);
+        Ok(Self {
+            cipher: XorCipher::new(key.expose())?,
+        })
+    }
+    
+    pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        self.cipher.encrypt(data)
+    }
+    
+    pub fn decrypt(&self, encrypted_data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        self.cipher.decrypt(encrypted_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kdf::KdfParams;
+
+    #[test]
+    fn test_caesar_known_output() {
+        let cipher = CaesarCipher::new(3).unwrap();
+        assert_eq!(cipher.encrypt(b"Hello Rust World!").unwrap(), b"Khoor Uxvw Zruog!");
+    }
+
+    #[test]
+    fn test_caesar_rejects_out_of_range_shift() {
+        assert!(matches!(CaesarCipher::new(26), Err(CipherError::ShiftOutOfRange(26))));
+        assert!(matches!(CaesarCipher::new(200), Err(CipherError::ShiftOutOfRange(200))));
+    }
+
+    #[test]
+    fn test_caesar_zero_and_max_shift_round_trip() {
+        for shift in [0, 25] {
+            let cipher = CaesarCipher::new(shift).unwrap();
+            let encrypted = cipher.encrypt(b"Zebra zone").unwrap();
+            assert_eq!(cipher.decrypt(&encrypted).unwrap(), b"Zebra zone");
+        }
+    }
+
+    #[test]
+    fn test_rot13_is_self_inverse() {
+        let cipher = Rot13Cipher;
+        assert_eq!(cipher.encrypt(b"Hello").unwrap(), b"Uryyb");
+        assert_eq!(cipher.decrypt(b"Uryyb").unwrap(), b"Hello");
+    }
+
+    #[test]
+    fn test_empty_keys_are_rejected() {
+        assert!(matches!(XorCipher::new(b""), Err(CipherError::EmptyKey)));
+        assert!(matches!(VigenereCipher::new(b""), Err(CipherError::EmptyKey)));
+    }
+
+    #[test]
+    fn test_vigenere_advances_on_non_letters() {
+        let cipher = VigenereCipher::new(b"AB").unwrap();
+        // Space consumes the 'B' / El espacio consume la 'B'
+        assert_eq!(cipher.encrypt(b"a a").unwrap(), b"a a");
+    }
+
+    #[test]
+    fn test_caesar_cipher() {
+        let original = "Hello";
+        let encrypted = caesar_encrypt(original, 3).unwrap();
+        let decrypted = caesar_decrypt(&encrypted, 3).unwrap();
+        assert_eq!(original, decrypted);
+    }
+
+    #[test]
+    fn test_caesar_out_of_range_shift_is_error() {
+        assert!(matches!(caesar_decrypt("Hello", 27), Err(CipherError::ShiftOutOfRange(27))));
+    }
+
+    #[test]
+    fn test_xor_cipher() {
+        let original = b"Secret Message";
+        let key = b"Key";
+        let encrypted = xor_encrypt(original, key).unwrap();
+        let decrypted = xor_decrypt(&encrypted, key).unwrap();
+        assert_eq!(original, &decrypted[..]);
+    }
+
+    #[test]
+    fn test_xor_empty_key_is_error() {
+        assert!(matches!(xor_encrypt(b"data", b""), Err(CipherError::EmptyKey)));
+    }
+
+    #[test]
+    fn test_rot13() {
+        let original = "Hello World";
+        let encoded = rot13(original).unwrap();
+        let decoded = rot13(&encoded).unwrap();
+        assert_eq!(original, decoded);
+    }
+
+    #[test]
+    fn test_simple_encryption_struct() {
+        let cipher = SimpleEncryption::new("MySecretKey").unwrap();
+        let original = b"This is a test message";
+        let encrypted = cipher.encrypt(original).unwrap();
+        let decrypted = cipher.decrypt(&encrypted).unwrap();
+        assert_eq!(original, &decrypted[..]);
+    }
+
+    #[test]
+    fn test_positional_keystreams_match_one_shot() {
+        let data = b"Chunk boundaries must not matter, 123!";
+        let xor = XorCipher::new(b"KEY12").unwrap();
+        let vigenere = VigenereCipher::new(b"LEMON").unwrap();
+        let (head, tail) = data.split_at(13);
+        
+        let xor_parts = [xor.encrypt(head).unwrap(), xor.clone().starting_at(13).encrypt(tail).unwrap()].concat();
+        assert_eq!(xor_parts, xor.encrypt(data).unwrap());
+        
+        let vigenere_parts =
+            [vigenere.encrypt(head).unwrap(), vigenere.clone().starting_at(13).encrypt(tail).unwrap()].concat();
+        assert_eq!(vigenere_parts, vigenere.encrypt(data).unwrap());
+        assert_eq!(vigenere.starting_at(13).decrypt(&vigenere_parts[13..]).unwrap(), tail);
+    }
+
+    #[test]
+    fn test_spanish_and_greek_caesar() {
+        let spanish = Alphabet::spanish();
+        assert_eq!(caesar_encrypt_with("Año: mañana", 1, &spanish).unwrap(), "Bop: nbobñb");
+        assert_eq!(caesar_decrypt_with("Bop: nbobñb", 1, &spanish).unwrap(), "Año: mañana");
+        // Accented vowels are not letters of the alphabet / Las vocales acentuadas no son letras del alfabeto
+        assert_eq!(caesar_encrypt_with("canción", 2, &spanish).unwrap(), "ecoekóo");
+
+        let greek = Alphabet::greek();
+        assert_eq!(caesar_encrypt_with("Λόγος ω", 3, &greek).unwrap(), "Ξόζσς γ");
+        assert!(matches!(CaesarCipher::with_alphabet(24, greek), Err(CipherError::ShiftOutOfRange(24))));
+    }
+
+    #[test]
+    fn test_rot_round_trips_odd_alphabets() {
+        let text = "El niño comió piña; λ = 1";
+        for alphabet in [Alphabet::ascii(), Alphabet::spanish(), Alphabet::greek()] {
+            let encrypted = rot_encrypt(text, &alphabet).unwrap();
+            assert_eq!(rot_decrypt(&encrypted, &alphabet).unwrap(), text);
+        }
+        assert_eq!(rot_encrypt("Hello", &Alphabet::ascii()).unwrap(), rot13("Hello").unwrap());
+    }
+
+    #[test]
+    fn test_vigenere_with_alphabets() {
+        let spanish = Alphabet::spanish();
+        // Ñ in the key shifts by 14 / La Ñ en la clave desplaza 14
+        assert_eq!(vigenere_encrypt_with("aaa", "bñ", &spanish).unwrap(), "bñb");
+        let text = "Señor Muñoz, ¿dónde está el año 1999?";
+        let encrypted = vigenere_encrypt_with(text, "CLAVEÑ", &spanish).unwrap();
+        assert_ne!(encrypted, text);
+        assert_eq!(vigenere_decrypt_with(&encrypted, "CLAVEÑ", &spanish).unwrap(), text);
+
+        let digits = Alphabet::custom("0123456789").unwrap();
+        assert_eq!(vigenere_encrypt_with("2024-06", "19", &digits).unwrap(), "3933-97");
+
+        // ASCII keeps the byte-level behaviour / ASCII conserva el comportamiento por bytes
+        let ascii = VigenereCipher::with_alphabet("LEMON", Alphabet::ascii()).unwrap();
+        let legacy = VigenereCipher::new(b"LEMON").unwrap();
+        assert_eq!(ascii.encrypt(b"attack at dawn").unwrap(), legacy.encrypt(b"attack at dawn").unwrap());
+    }
+
+    #[test]
+    fn test_non_ascii_alphabets_reject_invalid_utf8() {
+        let cipher = CaesarCipher::with_alphabet(1, Alphabet::greek()).unwrap();
+        assert!(matches!(cipher.encrypt(b"ab\xffc"), Err(CipherError::MalformedInput { position: 2, .. })));
+    }
+
+    #[test]
+    fn test_registry_builds_with_alphabet() {
+        let registry = crate::cipher::CipherRegistry::with_defaults();
+        let mut params = CipherParams::new();
+        params.set("alphabet", "spanish");
+        params.set("shift", "26");
+        let cipher = registry.build("Caesar", &params).unwrap();
+        assert_eq!(cipher.encrypt("ñ".as_bytes()).unwrap(), "n".as_bytes());
+        params.set("alphabet", "greek");
+        assert!(matches!(registry.build("Caesar", &params), Err(CipherError::ShiftOutOfRange(26))));
+    }
+
+    #[test]
+    fn test_simple_encryption_from_password() {
+        let kdf = KeyDerivation::with_salt(KdfParams::Pbkdf2Sha256 { iterations: 100 }, b"saltsalt");
+        let cipher = SimpleEncryption::from_password("MySecretKey", &kdf).unwrap();
+        let raw = SimpleEncryption::new("MySecretKey").unwrap();
+        let original = b"This is a test message";
+        let encrypted = cipher.encrypt(original).unwrap();
+        assert_ne!(encrypted, raw.encrypt(original).unwrap());
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), original);
+        assert!(matches!(SimpleEncryption::from_password("", &kdf), Err(CipherError::EmptyKey)));
+    }
+}
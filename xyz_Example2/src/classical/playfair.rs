@@ -0,0 +1,198 @@
+// Playfair Cipher: Digraph Substitution on a Keyed 5x5 Square
+// Cifrado Playfair: Sustitución de Dígrafos en un Cuadrado 5x5 con Clave
+
+use std::fmt;
+
+use crate::cipher::{Cipher, CipherFactory, CipherParams, ParamSpec};
+use crate::error::CipherError;
+use crate::secret::Redacted;
+
+use super::KEY_PARAM;
+
+/// Textbook Playfair with I and J sharing a cell of the square
+/// Playfair de libro con I y J compartiendo una celda del cuadrado
+///
+/// Encryption keeps only the letters, in uppercase with J read as I, splits them into pairs with an X
+/// between doubled letters (Q after a doubled X) and an X after an odd last letter. Decryption returns
+/// that prepared text, fillers included, so it is not the original message byte for byte.
+/// El cifrado conserva solo las letras, en mayúsculas y con la J leída como I, las separa en pares con
+/// una X entre letras repetidas (Q tras una X repetida) y una X tras una última letra impar. El descifrado
+/// devuelve ese texto preparado, con los rellenos, así que no es el mensaje original byte a byte.
+#[derive(Clone)]
+pub struct PlayfairCipher {
+    square: [u8; 25],    // Uppercase letters row by row / Letras mayúsculas fila por fila
+    positions: [u8; 26], // Cell of each letter, J sharing I's / Celda de cada letra, J comparte la de I
+}
+
+impl fmt::Debug for PlayfairCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PlayfairCipher").field("square", &Redacted).finish_non_exhaustive()
+    }
+}
+
+impl PlayfairCipher {
+    /// The key's letters fill the square first, then the rest of the alphabet. Spaces, digits and
+    /// punctuation are skipped; other bytes count as `'A' + byte % 26`, so derived binary keys work too
+    /// Las letras de la clave llenan primero el cuadrado y luego el resto del alfabeto. Se saltan espacios,
+    /// dígitos y puntuación; los demás bytes cuentan como `'A' + byte % 26`, así que sirven claves binarias
+    pub fn new(key: &[u8]) -> Result<Self, CipherError> {
+        if key.is_empty() {
+            return Err(CipherError::EmptyKey);
+        }
+        let mut square = [0u8; 25];
+        let mut positions = [u8::MAX; 26];
+        let mut filled = 0;
+        let key_letters = key.iter().filter_map(|&k| match k {
+            _ if k.is_ascii_alphabetic() => Some(k.to_ascii_uppercase()),
+            _ if k == b' ' || k.is_ascii_graphic() => None,
+            _ => Some(b'A' + k % 26),
+        });
+        for letter in key_letters.chain(b'A'..=b'Z') {
+            let letter = if letter == b'J' { b'I' } else { letter };
+            let index = (letter - b'A') as usize;
+            if positions[index] == u8::MAX {
+                square[filled] = letter;
+                positions[index] = filled as u8;
+                filled += 1;
+            }
+        }
+        positions[(b'J' - b'A') as usize] = positions[(b'I' - b'A') as usize];
+        Ok(Self { square, positions })
+    }
+
+    /// The 5x5 square as text, one row per line / El cuadrado 5x5 como texto, una fila por línea
+    pub fn square(&self) -> String {
+        self.square
+            .chunks(5)
+            .map(|row| row.iter().map(|&b| b as char).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The pairs encryption works on: uppercase letters, J as I, fillers between doubles and after an
+    /// odd tail; this is also what decryption gives back
+    /// Los pares sobre los que trabaja el cifrado: letras mayúsculas, J como I, rellenos entre repetidas y
+    /// tras un final impar; es también lo que devuelve el descifrado
+    pub fn prepare(data: &[u8]) -> Vec<u8> {
+        let letters = Self::letters(data);
+        let mut out = Vec::with_capacity(letters.len() + letters.len() / 2 + 1);
+        let mut i = 0;
+        while i < letters.len() {
+            let first = letters[i];
+            match letters.get(i + 1) {
+                Some(&second) if second != first => {
+                    out.extend_from_slice(&[first, second]);
+                    i += 2;
+                }
+                _ => {
+                    out.extend_from_slice(&[first, if first == b'X' { b'Q' } else { b'X' }]);
+                    i += 1;
+                }
+            }
+        }
+        out
+    }
+
+    fn letters(data: &[u8]) -> Vec<u8> {
+        data.iter()
+            .filter(|b| b.is_ascii_alphabetic())
+            .map(|b| match b.to_ascii_uppercase() {
+                b'J' => b'I',
+                letter => letter,
+            })
+            .collect()
+    }
+
+    /// Apply the Playfair rules to one pair; `step` is 1 to encrypt and 4 to decrypt
+    /// Aplicar las reglas de Playfair a un par; `step` es 1 para cifrar y 4 para descifrar
+    fn transform_pair(&self, pair: &[u8], step: usize) -> [u8; 2] {
+        let cell = |b: u8| self.positions[(b - b'A') as usize] as usize;
+        let (p, q) = (cell(pair[0]), cell(pair[1]));
+        let (row_p, col_p, row_q, col_q) = (p / 5, p % 5, q / 5, q % 5);
+        let (new_p, new_q) = if row_p == row_q {
+            (row_p * 5 + (col_p + step) % 5, row_q * 5 + (col_q + step) % 5)
+        } else if col_p == col_q {
+            ((row_p + step) % 5 * 5 + col_p, (row_q + step) % 5 * 5 + col_q)
+        } else {
+            // Opposite corners of the rectangle / Esquinas opuestas del rectángulo
+            (row_p * 5 + col_q, row_q * 5 + col_p)
+        };
+        [self.square[new_p], self.square[new_q]]
+    }
+}
+
+impl Cipher for PlayfairCipher {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        Ok(Self::prepare(data).chunks_exact(2).flat_map(|pair| self.transform_pair(pair, 1)).collect())
+    }
+
+    /// Letters are read in pairs, skipping other bytes, so grouped ciphertext like `BMODZ BXDNA` works
+    /// Las letras se leen en pares, saltando los demás bytes, así que sirve texto cifrado en grupos como `BMODZ BXDNA`
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let letters = Self::letters(data);
+        if letters.len() % 2 == 1 {
+            return Err(CipherError::MalformedInput {
+                position: data.len(),
+                reason: "Playfair ciphertext needs an even number of letters".to_string(),
+            });
+        }
+        Ok(letters.chunks_exact(2).flat_map(|pair| self.transform_pair(pair, 4)).collect())
+    }
+}
+
+impl CipherFactory for PlayfairCipher {
+    const NAME: &'static str = "Playfair";
+    const DESCRIPTION: &'static str = "Playfair Cipher / Cifrado Playfair";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[KEY_PARAM]
+    }
+
+    fn from_params(params: &CipherParams) -> Result<Self, CipherError> {
+        Self::new(&params.bytes(KEY_PARAM.name)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyed_square() {
+        let cipher = PlayfairCipher::new(b"playfair example").unwrap();
+        assert_eq!(cipher.square(), "PLAYF\nIREXM\nBCDGH\nKNOQS\nTUVWZ");
+    }
+
+    #[test]
+    fn test_textbook_vector() {
+        let cipher = PlayfairCipher::new(b"PLAYFAIR EXAMPLE").unwrap();
+        let encrypted = cipher.encrypt(b"Hide the gold in the tree stump").unwrap();
+        assert_eq!(encrypted, b"BMODZBXDNABEKUDMUIXMMOUVIF");
+        assert_eq!(cipher.decrypt(b"BMODZ BXDNA BEKUD MUIXM MOUVI F").unwrap(), b"HIDETHEGOLDINTHETREXESTUMP");
+    }
+
+    #[test]
+    fn test_prepare_merges_j_and_inserts_fillers() {
+        assert_eq!(PlayfairCipher::prepare(b"balloon"), b"BALXLOON");
+        assert_eq!(PlayfairCipher::prepare(b"Jump, Jill!"), b"IUMPIXILLX");
+        assert_eq!(PlayfairCipher::prepare(b"taxx"), b"TAXQXQ");
+        assert_eq!(PlayfairCipher::prepare(b"x"), b"XQ");
+        assert_eq!(PlayfairCipher::prepare(b"\x00 42 \xff"), b"");
+    }
+
+    #[test]
+    fn test_round_trip_returns_prepared_text() {
+        let cipher = PlayfairCipher::new(b"MONARCHY").unwrap();
+        for text in [&b"balloon"[..], b"Jump, Jill!", b"x", b"", b"ee", b"\x00\xffbinary\x10"] {
+            let encrypted = cipher.encrypt(text).unwrap();
+            assert_eq!(encrypted.len(), PlayfairCipher::prepare(text).len());
+            assert_eq!(cipher.decrypt(&encrypted).unwrap(), PlayfairCipher::prepare(text));
+        }
+        assert!(matches!(cipher.decrypt(b"ABC"), Err(CipherError::MalformedInput { position: 3, .. })));
+        assert!(matches!(PlayfairCipher::new(b""), Err(CipherError::EmptyKey)));
+    }
+}
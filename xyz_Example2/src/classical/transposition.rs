@@ -0,0 +1,208 @@
+// Transposition Ciphers: Rail Fence and Keyed Columnar
+// Cifrados de Transposición: Rail Fence y Columnar con Clave
+
+use std::fmt;
+
+use crate::cipher::{Cipher, CipherFactory, CipherParams, ParamKind, ParamSpec};
+use crate::error::CipherError;
+use crate::secret::Redacted;
+
+use super::KEY_PARAM;
+
+/// Most rails `RailFenceCipher::new` accepts / Máximo de rieles que acepta `RailFenceCipher::new`
+pub const MAX_RAILS: usize = 10;
+
+const RAILS_PARAM: ParamSpec = ParamSpec {
+    name: "rails",
+    prompt: "Enter number of rails (2-10) / Ingresa número de rieles (2-10)",
+    kind: ParamKind::Range(2, MAX_RAILS as u32),
+};
+
+/// Reorder `data` so output byte k is input byte `order[k]`, or undo that
+/// Reordenar `data` para que el byte k de salida sea el byte `order[k]` de entrada, o deshacerlo
+fn permute(data: &[u8], order: &[usize], forward: bool) -> Vec<u8> {
+    let mut out = vec![0u8; data.len()];
+    for (k, &i) in order.iter().enumerate() {
+        if forward {
+            out[k] = data[i];
+        } else {
+            out[i] = data[k];
+        }
+    }
+    out
+}
+
+// === RAIL FENCE CIPHER / CIFRADO RAIL FENCE ===
+
+/// Rail Fence: bytes are written in a zigzag over the rails and read rail by rail
+/// Rail Fence: los bytes se escriben en zigzag sobre los rieles y se leen riel por riel
+#[derive(Clone)]
+pub struct RailFenceCipher {
+    rails: usize,
+}
+
+impl fmt::Debug for RailFenceCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RailFenceCipher").field("rails", &Redacted).finish()
+    }
+}
+
+impl RailFenceCipher {
+    /// Two to `MAX_RAILS` rails; one would leave the data unchanged / De dos a `MAX_RAILS` rieles; uno dejaría los datos igual
+    pub fn new(rails: usize) -> Result<Self, CipherError> {
+        if !(2..=MAX_RAILS).contains(&rails) {
+            return Err(CipherError::InvalidParam { name: "rails".to_string(), value: rails.to_string() });
+        }
+        Ok(Self { rails })
+    }
+
+    /// Input positions in reading order / Posiciones de entrada en orden de lectura
+    fn order(&self, len: usize) -> Vec<usize> {
+        let cycle = 2 * (self.rails - 1);
+        let rail = |i: usize| {
+            let phase = i % cycle;
+            phase.min(cycle - phase)
+        };
+        let mut order: Vec<usize> = (0..len).collect();
+        order.sort_by_key(|&i| rail(i)); // Stable, so each rail keeps its order / Estable, cada riel conserva su orden
+        order
+    }
+}
+
+impl Cipher for RailFenceCipher {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        Ok(permute(data, &self.order(data.len()), true))
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        Ok(permute(data, &self.order(data.len()), false))
+    }
+}
+
+impl CipherFactory for RailFenceCipher {
+    const NAME: &'static str = "RailFence";
+    const DESCRIPTION: &'static str = "Rail Fence Cipher / Cifrado Rail Fence (de la valla)";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[RAILS_PARAM]
+    }
+
+    fn from_params(params: &CipherParams) -> Result<Self, CipherError> {
+        let rails = params.number(RAILS_PARAM.name)?;
+        let invalid = || CipherError::InvalidParam { name: RAILS_PARAM.name.to_string(), value: rails.to_string() };
+        Self::new(usize::try_from(rails).map_err(|_| invalid())?)
+    }
+}
+
+// === COLUMNAR TRANSPOSITION / TRANSPOSICIÓN COLUMNAR ===
+
+/// Keyed columnar transposition: rows as wide as the key, columns read in the key's alphabetical order
+/// Transposición columnar con clave: filas tan anchas como la clave, columnas leídas en el orden
+/// alfabético de la clave
+///
+/// The last row may be short and is not padded; equal key letters keep their left-to-right order.
+/// La última fila puede ser corta y no se rellena; las letras iguales de la clave conservan su orden.
+#[derive(Clone)]
+pub struct ColumnarCipher {
+    columns: Vec<usize>, // Column indices in reading order / Índices de columna en orden de lectura
+}
+
+impl fmt::Debug for ColumnarCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ColumnarCipher").field("columns", &Redacted).finish()
+    }
+}
+
+impl ColumnarCipher {
+    /// Key must not be empty; letters compare case-insensitively, other bytes by value
+    /// La clave no debe estar vacía; las letras se comparan sin distinguir mayúsculas, los demás bytes por valor
+    pub fn new(key: &[u8]) -> Result<Self, CipherError> {
+        if key.is_empty() {
+            return Err(CipherError::EmptyKey);
+        }
+        let mut columns: Vec<usize> = (0..key.len()).collect();
+        columns.sort_by_key(|&c| key[c].to_ascii_uppercase());
+        Ok(Self { columns })
+    }
+
+    fn order(&self, len: usize) -> Vec<usize> {
+        let width = self.columns.len();
+        self.columns
+            .iter()
+            .flat_map(|&column| (column..len).step_by(width))
+            .collect()
+    }
+}
+
+impl Cipher for ColumnarCipher {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        Ok(permute(data, &self.order(data.len()), true))
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        Ok(permute(data, &self.order(data.len()), false))
+    }
+}
+
+impl CipherFactory for ColumnarCipher {
+    const NAME: &'static str = "Columnar";
+    const DESCRIPTION: &'static str = "Keyed Columnar Transposition / Transposición Columnar con Clave";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[KEY_PARAM]
+    }
+
+    fn from_params(params: &CipherParams) -> Result<Self, CipherError> {
+        Self::new(&params.bytes(KEY_PARAM.name)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rail_fence_known_output() {
+        let cipher = RailFenceCipher::new(3).unwrap();
+        let encrypted = cipher.encrypt(b"WEAREDISCOVEREDFLEEATONCE").unwrap();
+        assert_eq!(encrypted, b"WECRLTEERDSOEEFEAOCAIVDEN");
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), b"WEAREDISCOVEREDFLEEATONCE");
+        assert!(matches!(RailFenceCipher::new(1), Err(CipherError::InvalidParam { .. })));
+    }
+
+    #[test]
+    fn test_columnar_known_output() {
+        // ZEBRAS reads columns 4, 2, 1, 3, 5, 0 / ZEBRAS lee las columnas 4, 2, 1, 3, 5, 0
+        let cipher = ColumnarCipher::new(b"zebras").unwrap();
+        let encrypted = cipher.encrypt(b"WEAREDISCOVEREDFLEEATONCE").unwrap();
+        assert_eq!(encrypted, b"EVLNACDTESEAROFODEECWIREE");
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), b"WEAREDISCOVEREDFLEEATONCE");
+    }
+
+    #[test]
+    fn test_transpositions_round_trip_any_length() {
+        let data: Vec<u8> = (0..=255).collect();
+        for len in [0, 1, 2, 5, 17, 256] {
+            for rails in [2, 3, 7, MAX_RAILS] {
+                let cipher = RailFenceCipher::new(rails).unwrap();
+                assert_eq!(cipher.decrypt(&cipher.encrypt(&data[..len]).unwrap()).unwrap(), &data[..len]);
+            }
+            for key in [&b"K"[..], b"LEMON", b"\x00\xff\x10 repeated letters"] {
+                let cipher = ColumnarCipher::new(key).unwrap();
+                assert_eq!(cipher.decrypt(&cipher.encrypt(&data[..len]).unwrap()).unwrap(), &data[..len]);
+            }
+        }
+        assert!(matches!(ColumnarCipher::new(b""), Err(CipherError::EmptyKey)));
+        for rails in [0, 1, MAX_RAILS + 1, 300] {
+            assert!(matches!(RailFenceCipher::new(rails), Err(CipherError::InvalidParam { .. })));
+        }
+    }
+}
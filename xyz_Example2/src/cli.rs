@@ -0,0 +1,541 @@
+// Command-Line Interface: Scriptable Subcommands for Pipes and CI Jobs
+// Interfaz de Línea de Comandos: Subcomandos Automatizables para Tuberías y Trabajos de CI
+
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::time::Duration;
+
+use xyz_example2::bench::{self, BenchConfig, Measurement, CSV_HEADER};
+use xyz_example2::blake2b::Blake2b;
+use xyz_example2::cipher::{CipherParams, CipherRegistry, ParamKind};
+use xyz_example2::cryptanalysis::vigenere::MAX_KEY_LEN;
+use xyz_example2::cryptanalysis::xor::MAX_KEY_SIZE;
+use xyz_example2::cryptanalysis::{crack_caesar, crack_repeating_xor, crack_vigenere, parse_ciphertext, Language};
+use xyz_example2::encoding::{bytes_to_hex, encoding_by_name, encodings, Base64, Encoding, Hex};
+use xyz_example2::hash::{hash_reader, Digest, Sha224, Sha256, Sha384, Sha512};
+use xyz_example2::CipherError;
+
+use crate::messages::Messages;
+
+// Exit codes / Códigos de salida
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_IO: i32 = 1;            // File or pipe failure / Fallo de archivo o tubería
+pub const EXIT_USAGE: i32 = 2;         // Bad arguments, key or parameter / Argumentos, clave o parámetro incorrectos
+pub const EXIT_INVALID_INPUT: i32 = 3; // Malformed ciphertext or text encoding / Texto cifrado o codificación mal formados
+pub const EXIT_AUTH_FAILED: i32 = 4;   // Wrong key or tampered data / Clave incorrecta o datos alterados
+
+/// Path meaning standard input or output / Ruta que significa entrada o salida estándar
+const STDIO: &str = "-";
+/// `--encoding` value that writes bytes unchanged / Valor de `--encoding` que escribe los bytes sin cambios
+const RAW: &str = "raw";
+
+/// Exit code for an error / Código de salida para un error
+pub fn exit_code(error: &CipherError) -> i32 {
+    match error {
+        CipherError::Io(_) => EXIT_IO,
+        CipherError::MalformedInput { .. } => EXIT_INVALID_INPUT,
+        CipherError::AuthenticationFailed => EXIT_AUTH_FAILED,
+        CipherError::EmptyKey
+        | CipherError::InvalidKey(_)
+        | CipherError::ShiftOutOfRange(_)
+        | CipherError::MissingParam(_)
+        | CipherError::InvalidParam { .. }
+        | CipherError::UnknownAlgorithm(_) => EXIT_USAGE,
+    }
+}
+
+fn usage(messages: &Messages) -> String {
+    let registry = CipherRegistry::with_defaults();
+    let algorithms: Vec<&str> = registry.entries().iter().map(|e| e.name).collect();
+    let encodings: Vec<&str> = encodings().iter().map(|e| e.name()).collect();
+    messages.format(
+        "command.usage",
+        &[("algorithms", &algorithms.join(", ")), ("hashes", &HASHES.join(", ")), ("encodings", &encodings.join(", "))],
+    )
+}
+
+/// Name of a reference language in the catalog / Nombre de un idioma de referencia en el catálogo
+fn language_name(messages: &Messages, language: Language) -> &str {
+    messages.lookup(&format!("language.{:?}", language)).unwrap_or(language.name())
+}
+
+/// Flags shared by every subcommand / Opciones comunes a todos los subcomandos
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    algorithm: Option<String>,
+    key: Option<String>,
+    key_file: Option<String>,
+    params: Vec<(String, String)>,
+    input: Option<String>,
+    output: Option<String>,
+    encoding: Option<String>,
+}
+
+impl Options {
+    /// Accepts `--flag value`, `--flag=value` and one positional input path
+    /// Acepta `--flag valor`, `--flag=valor` y una ruta de entrada posicional
+    fn parse(args: &[String]) -> Result<Self, CipherError> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with('-') || arg == STDIO {
+                if options.input.replace(arg.clone()).is_some() {
+                    return Err(CipherError::InvalidParam { name: "input".to_string(), value: arg.clone() });
+                }
+                continue;
+            }
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let name = match flag {
+                "-a" | "--algorithm" => "algorithm",
+                "-k" | "--key" => "key",
+                "--key-file" => "key-file",
+                "-p" | "--param" => "param",
+                "-i" | "--in" => "in",
+                "-o" | "--out" => "out",
+                "-e" | "--encoding" => "encoding",
+                _ => return Err(CipherError::InvalidParam { name: "option".to_string(), value: arg.clone() }),
+            };
+            let value = inline.or_else(|| args.next().cloned()).ok_or(CipherError::MissingParam(name))?;
+            let slot = match name {
+                "algorithm" => &mut options.algorithm,
+                "key" => &mut options.key,
+                "key-file" => &mut options.key_file,
+                "in" => &mut options.input,
+                "out" => &mut options.output,
+                "encoding" => &mut options.encoding,
+                _ => {
+                    let (param, param_value) = value
+                        .split_once('=')
+                        .ok_or_else(|| CipherError::InvalidParam { name: "param".to_string(), value: value.clone() })?;
+                    options.params.push((param.to_string(), param_value.to_string()));
+                    continue;
+                }
+            };
+            *slot = Some(value);
+        }
+        Ok(options)
+    }
+
+    fn require_algorithm(&self) -> Result<&str, CipherError> {
+        self.algorithm.as_deref().ok_or(CipherError::MissingParam("algorithm"))
+    }
+
+    /// `--key`, or the contents of `--key-file` without the trailing newline
+    /// `--key`, o el contenido de `--key-file` sin el salto de línea final
+    fn key(&self) -> Result<Option<String>, CipherError> {
+        match (&self.key, &self.key_file) {
+            (Some(_), Some(path)) => Err(CipherError::InvalidParam { name: "key-file".to_string(), value: path.clone() }),
+            (Some(key), None) => Ok(Some(key.clone())),
+            (None, Some(path)) => Ok(Some(fs::read_to_string(path)?.trim_end_matches(['\r', '\n']).to_string())),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// The chosen encoding, `None` for raw bytes / La codificación elegida, `None` para bytes sin cambios
+    fn encoding(&self, default: &'static dyn Encoding) -> Result<Option<&'static dyn Encoding>, CipherError> {
+        match self.encoding.as_deref() {
+            None => Ok(Some(default)),
+            Some(name) if name.eq_ignore_ascii_case(RAW) => Ok(None),
+            Some(name) => encoding_by_name(name)
+                .map(Some)
+                .ok_or_else(|| CipherError::InvalidParam { name: "encoding".to_string(), value: name.to_string() }),
+        }
+    }
+
+    fn read_input(&self, stdin: &mut dyn Read) -> Result<Vec<u8>, CipherError> {
+        match self.input.as_deref() {
+            None | Some(STDIO) => {
+                let mut data = Vec::new();
+                stdin.read_to_end(&mut data)?;
+                Ok(data)
+            }
+            Some(path) => Ok(fs::read(path)?),
+        }
+    }
+
+    fn write_output(&self, stdout: &mut dyn Write, data: &[u8]) -> Result<(), CipherError> {
+        match self.output.as_deref() {
+            None | Some(STDIO) => stdout.write_all(data)?,
+            Some(path) => fs::write(path, data)?,
+        }
+        Ok(())
+    }
+}
+
+/// Input bytes as text for encodings and attacks / Bytes de entrada como texto para codificaciones y ataques
+fn input_text(data: &[u8]) -> Result<&str, CipherError> {
+    std::str::from_utf8(data).map_err(|e| CipherError::MalformedInput {
+        position: e.valid_up_to(),
+        reason: "input is not valid UTF-8 text".to_string(),
+    })
+}
+
+/// Run one subcommand and return its exit code; errors go to `stderr`
+/// Ejecutar un subcomando y devolver su código de salida; los errores van a `stderr`
+pub fn run(args: &[String], messages: &Messages, stdin: &mut dyn Read, stdout: &mut dyn Write, stderr: &mut dyn Write) -> i32 {
+    let (command, rest) = match args.split_first() {
+        Some((command, rest)) => (command.as_str(), rest),
+        None => ("help", args),
+    };
+    let result = match command {
+        "help" | "-h" | "--help" => writeln!(stdout, "{}", usage(messages)).map_err(CipherError::from),
+        "encrypt" | "decrypt" => Options::parse(rest).and_then(|o| crypt(&o, command == "encrypt", stdin, stdout)),
+        "encode" | "decode" => Options::parse(rest).and_then(|o| transcode(&o, command == "encode", stdin, stdout)),
+        "hash" => Options::parse(rest).and_then(|o| hash(&o, stdin, stdout)),
+        "crack" => Options::parse(rest).and_then(|o| crack(&o, messages, stdin, stdout, stderr)),
+        "bench" => Options::parse(rest).and_then(|o| bench(&o, messages, stdout)),
+        other => Err(CipherError::InvalidParam { name: "command".to_string(), value: other.to_string() }),
+    };
+    match result.and_then(|_| Ok(stdout.flush()?)) {
+        Ok(()) => EXIT_OK,
+        Err(e) => {
+            let code = exit_code(&e);
+            let _ = writeln!(stderr, "{}", messages.format("command.error", &[("error", &e)]));
+            if code == EXIT_USAGE {
+                let _ = writeln!(stderr, "{}", messages.get("command.hint"));
+            }
+            code
+        }
+    }
+}
+
+// === SUBCOMMANDS / SUBCOMANDOS ===
+
+/// Build a registered cipher and apply it; ciphertext is written and read through `--encoding`
+/// Construir un cifrado registrado y aplicarlo; el texto cifrado se escribe y lee con `--encoding`
+fn crypt(options: &Options, encrypt: bool, stdin: &mut dyn Read, stdout: &mut dyn Write) -> Result<(), CipherError> {
+    let registry = CipherRegistry::with_defaults();
+    let name = options.require_algorithm()?;
+    let entry = registry.get(name).ok_or_else(|| CipherError::UnknownAlgorithm(name.to_string()))?;
+
+    let mut params = CipherParams::new();
+    if let Some(key) = options.key()? {
+        // The "key" parameter, or the first secret one (Caesar's shift, the Enigma settings, ...)
+        // El parámetro "key", o el primero secreto (el desplazamiento César, la configuración Enigma, ...)
+        let spec = entry
+            .schema
+            .iter()
+            .find(|spec| spec.name == "key")
+            .or_else(|| entry.schema.iter().find(|spec| spec.kind != ParamKind::Data))
+            .ok_or_else(|| CipherError::InvalidKey(format!("{} takes no key", entry.name)))?;
+        params.set(spec.name, &key);
+    }
+    for (param, value) in &options.params {
+        params.set(param, value);
+    }
+    let cipher = entry.build(&params)?;
+    let encoding = options.encoding(&Hex)?;
+    let input = options.read_input(stdin)?;
+
+    let output = if encrypt {
+        let ciphertext = cipher.encrypt(&input)?;
+        match encoding {
+            Some(encoding) => format!("{}\n", encoding.encode(&ciphertext)?).into_bytes(),
+            None => ciphertext,
+        }
+    } else {
+        let ciphertext = match encoding {
+            Some(encoding) => encoding.decode(input_text(&input)?.trim())?,
+            None => input,
+        };
+        cipher.decrypt(&ciphertext)?
+    };
+    options.write_output(stdout, &output)
+}
+
+/// Bytes to text and back; Base64 unless `--encoding` says otherwise
+/// De bytes a texto y de vuelta; Base64 salvo que `--encoding` diga otra cosa
+fn transcode(options: &Options, encode: bool, stdin: &mut dyn Read, stdout: &mut dyn Write) -> Result<(), CipherError> {
+    let encoding = options
+        .encoding(&Base64::STANDARD)?
+        .ok_or_else(|| CipherError::InvalidParam { name: "encoding".to_string(), value: RAW.to_string() })?;
+    let input = options.read_input(stdin)?;
+    let output = if encode {
+        format!("{}\n", encoding.encode(&input)?).into_bytes()
+    } else {
+        encoding.decode(input_text(&input)?.trim())?
+    };
+    options.write_output(stdout, &output)
+}
+
+/// Hash names accepted by `hash` / Nombres de hash aceptados por `hash`
+const HASHES: [&str; 5] = [Sha224::NAME, Sha256::NAME, Sha384::NAME, Sha512::NAME, Blake2b::NAME];
+
+/// Stream the input through a digest and print `hex  path`, like `sha256sum`
+/// Pasar la entrada por un resumen e imprimir `hex  ruta`, como `sha256sum`
+fn hash(options: &Options, stdin: &mut dyn Read, stdout: &mut dyn Write) -> Result<(), CipherError> {
+    let name = options.algorithm.as_deref().unwrap_or(Sha256::NAME);
+    // "sha256" and "SHA-256" both work / "sha256" y "SHA-256" funcionan igual
+    let normalize = |s: &str| s.replace('-', "").to_ascii_lowercase();
+    let path = options.input.as_deref().unwrap_or(STDIO);
+    let input: Box<dyn Read + '_> = match path {
+        STDIO => Box::new(stdin),
+        path => Box::new(BufReader::new(File::open(path)?)),
+    };
+    let digest = match HASHES.iter().position(|h| normalize(h) == normalize(name)) {
+        Some(0) => hash_reader::<Sha224, _>(input)?,
+        Some(1) => hash_reader::<Sha256, _>(input)?,
+        Some(2) => hash_reader::<Sha384, _>(input)?,
+        Some(3) => hash_reader::<Sha512, _>(input)?,
+        Some(_) => hash_reader::<Blake2b, _>(input)?,
+        None => return Err(CipherError::UnknownAlgorithm(name.to_string())),
+    };
+    options.write_output(stdout, format!("{}  {}\n", bytes_to_hex(&digest), path).as_bytes())
+}
+
+/// Recover a classical key; the plaintext goes to the output and the key to `stderr`
+/// Recuperar una clave clásica; el texto plano va a la salida y la clave a `stderr`
+fn crack(
+    options: &Options,
+    messages: &Messages,
+    stdin: &mut dyn Read,
+    stdout: &mut dyn Write,
+    stderr: &mut dyn Write,
+) -> Result<(), CipherError> {
+    let attack = options.require_algorithm()?.to_ascii_lowercase();
+    let input = options.read_input(stdin)?;
+    let plaintext = match attack.as_str() {
+        "caesar" | "rot" => {
+            let best = crack_caesar(input_text(&input)?, 1)?.remove(0);
+            let confidence = format!("{:.1}", best.confidence * 100.0);
+            let language = language_name(messages, best.language);
+            let line = messages.format("command.shift", &[("shift", &best.shift), ("language", &language), ("confidence", &confidence)]);
+            writeln!(stderr, "{}", line)?;
+            best.plaintext.into_bytes()
+        }
+        "vigenere" => {
+            let solution = crack_vigenere(input_text(&input)?, MAX_KEY_LEN)?;
+            let language = language_name(messages, solution.language);
+            writeln!(stderr, "{}", messages.format("command.key", &[("key", &solution.key), ("language", &language)]))?;
+            solution.plaintext.into_bytes()
+        }
+        "xor" => {
+            // Hex or Base64 unless --encoding names the form / Hex o Base64 salvo que --encoding indique la forma
+            let ciphertext = match options.encoding {
+                Some(_) => match options.encoding(&Hex)? {
+                    Some(encoding) => encoding.decode(input_text(&input)?.trim())?,
+                    None => input,
+                },
+                None => parse_ciphertext(input_text(&input)?.trim())?,
+            };
+            let solution = crack_repeating_xor(&ciphertext, MAX_KEY_SIZE)?;
+            let (key, language) = (bytes_to_hex(&solution.key), language_name(messages, solution.language));
+            writeln!(stderr, "{}", messages.format("command.key_hex", &[("key", &key), ("language", &language)]))?;
+            solution.plaintext
+        }
+        _ => return Err(CipherError::UnknownAlgorithm(attack)),
+    };
+    options.write_output(stdout, &plaintext)
+}
+
+/// Output formats of `bench` / Formatos de salida de `bench`
+const BENCH_FORMATS: [&str; 3] = ["table", "csv", "json"];
+
+/// Time ciphers and encodings; `-p` sets sizes, repeats, warmup, limit (seconds, 0 for none) and format
+/// Medir cifrados y codificaciones; `-p` fija sizes, repeats, warmup, limit (segundos, 0 sin límite) y format
+fn bench(options: &Options, messages: &Messages, stdout: &mut dyn Write) -> Result<(), CipherError> {
+    let mut config = BenchConfig::default();
+    let mut format = BENCH_FORMATS[0];
+    for (name, value) in &options.params {
+        let invalid = || CipherError::InvalidParam { name: name.clone(), value: value.clone() };
+        let count = || value.parse::<usize>().map_err(|_| invalid());
+        match name.as_str() {
+            "sizes" => config.sizes = bench::parse_sizes(value)?,
+            "repeats" => config.repeats = count()?,
+            "warmup" => config.warmup = count()?,
+            "limit" => {
+                let seconds = value.parse::<f64>().ok().filter(|s| s.is_finite() && *s >= 0.0).ok_or_else(invalid)?;
+                config.limit = (seconds > 0.0).then(|| Duration::from_secs_f64(seconds));
+            }
+            "format" => format = BENCH_FORMATS.iter().find(|f| f.eq_ignore_ascii_case(value)).ok_or_else(invalid)?,
+            _ => return Err(invalid()),
+        }
+    }
+    let targets = bench::select(bench::targets()?, options.algorithm.as_deref().unwrap_or(""))?;
+
+    // Rows are written as they are measured, so long runs show progress
+    // Las filas se escriben al medirse, así las ejecuciones largas muestran su avance
+    let mut output: Box<dyn Write + '_> = match options.output.as_deref() {
+        None | Some(STDIO) => Box::new(stdout),
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+    };
+    match format {
+        "csv" => writeln!(output, "{}", CSV_HEADER)?,
+        "json" => write!(output, "[")?,
+        _ => writeln!(output, "{}", bench_header(messages))?,
+    }
+    let mut separator = "";
+    bench::run(&targets, &config, |measurement| {
+        match format {
+            "csv" => writeln!(output, "{}", measurement.to_csv())?,
+            "json" => write!(output, "{}\n  {}", std::mem::replace(&mut separator, ","), measurement.to_json())?,
+            _ => writeln!(output, "{}", bench_row(messages, measurement))?,
+        }
+        Ok(output.flush()?)
+    })?;
+    if format == "json" {
+        writeln!(output, "\n]")?;
+    }
+    Ok(output.flush()?)
+}
+
+/// Table header shared with the menu / Cabecera de la tabla compartida con el menú
+pub fn bench_header(messages: &Messages) -> String {
+    let [target, operation, size, median, best] =
+        ["bench.target", "bench.operation", "bench.size", "bench.median", "bench.best"].map(|key| messages.get(key));
+    format!("{:<20} {:<10} {:>9} {:>14} {:>14}", target, operation, size, median, best)
+}
+
+/// One table row: MB/s of the median and of the best run / Una fila de la tabla: MB/s de la mediana y de la mejor ejecución
+pub fn bench_row(messages: &Messages, measurement: &Measurement) -> String {
+    format!(
+        "{:<20} {:<10} {:>9} {:>14.2} {:>14.2}",
+        measurement.target,
+        messages.get(&format!("bench.{}", measurement.operation)),
+        bench::format_size(measurement.bytes),
+        measurement.mb_per_s(),
+        measurement.best_mb_per_s()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run with in-memory streams / Ejecutar con flujos en memoria
+    fn run_with(args: &[&str], stdin: &[u8]) -> (i32, Vec<u8>, String) {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+        let code = run(&args, &Messages::english(), &mut &stdin[..], &mut stdout, &mut stderr);
+        (code, stdout, String::from_utf8(stderr).unwrap())
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let args: Vec<String> = ["-a", "XOR", "--key=secret", "-p", "alphabet=greek", "-o", "-", "in.txt"]
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        let options = Options::parse(&args).unwrap();
+        assert_eq!(options.algorithm.as_deref(), Some("XOR"));
+        assert_eq!(options.key.as_deref(), Some("secret"));
+        assert_eq!(options.params, [("alphabet".to_string(), "greek".to_string())]);
+        assert_eq!(options.output.as_deref(), Some("-"));
+        assert_eq!(options.input.as_deref(), Some("in.txt"));
+
+        assert!(matches!(Options::parse(&["--key".to_string()]), Err(CipherError::MissingParam("key"))));
+        assert!(matches!(Options::parse(&["--bogus".to_string()]), Err(CipherError::InvalidParam { .. })));
+        assert!(matches!(Options::parse(&["-p".to_string(), "novalue".to_string()]), Err(CipherError::InvalidParam { .. })));
+    }
+
+    #[test]
+    fn test_encrypt_and_decrypt_through_pipes() {
+        let (code, stdout, _) = run_with(&["encrypt", "-a", "caesar", "-k", "3", "-e", "raw"], b"Hello");
+        assert_eq!((code, stdout), (EXIT_OK, b"Khoor".to_vec()));
+
+        let (code, encrypted, _) = run_with(&["encrypt", "-a", "vigenere", "-k", "LEMON"], b"attack at dawn");
+        assert_eq!(code, EXIT_OK);
+        assert_eq!(encrypted, format!("{}\n", bytes_to_hex(b"lxfopv mh oeib")).into_bytes());
+        let (code, decrypted, _) = run_with(&["decrypt", "-a", "Vigenere", "-k", "LEMON", "-"], &encrypted);
+        assert_eq!((code, decrypted), (EXIT_OK, b"attack at dawn".to_vec()));
+
+        let settings = "II IV V B BUL BLA AV BS CG DL FU HZ IN KM OW RX";
+        let (code, plaintext, _) = run_with(&["decrypt", "-a", "enigma", "-k", settings, "-e", "raw"], b"EDPUD NRGYS");
+        assert_eq!((code, plaintext), (EXIT_OK, b"AUFKL XABTE".to_vec()));
+    }
+
+    #[test]
+    fn test_files_key_file_and_parameters() {
+        let dir = std::env::temp_dir();
+        let key_file = dir.join("xyz_example2_cli_key.txt");
+        let input = dir.join("xyz_example2_cli_input.txt");
+        let output = dir.join("xyz_example2_cli_output.b64");
+        fs::write(&key_file, "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f\n").unwrap();
+        fs::write(&input, "Binary-safe file contents\n").unwrap();
+        let (key_file, input, output) = (key_file.to_str().unwrap(), input.to_str().unwrap(), output.to_str().unwrap());
+
+        let nonce = "nonce=000000000000000000000000";
+        let args = ["encrypt", "-a", "AES-GCM", "--key-file", key_file, "-p", nonce, "-i", input, "-o", output, "-e", "Base64"];
+        assert_eq!(run_with(&args, b"").0, EXIT_OK);
+        let (code, decrypted, _) = run_with(&["decrypt", "-a", "aes-gcm", "--key-file", key_file, "-p", nonce, "-e", "base64", output], b"");
+        assert_eq!((code, decrypted), (EXIT_OK, b"Binary-safe file contents\n".to_vec()));
+
+        // Flipping one ciphertext bit fails the tag / Cambiar un bit del texto cifrado hace fallar la etiqueta
+        let mut sealed = Base64::STANDARD.decode(fs::read_to_string(output).unwrap().trim()).unwrap();
+        sealed[0] ^= 1;
+        let args = ["decrypt", "-a", "AES-GCM", "--key-file", key_file, "-p", nonce, "-e", "raw"];
+        let (code, stdout, stderr) = run_with(&args, &sealed);
+        assert_eq!(code, EXIT_AUTH_FAILED);
+        assert!(stdout.is_empty());
+        assert!(stderr.starts_with("error: authentication failed"));
+
+        for path in [key_file, input, output] {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    #[test]
+    fn test_encode_hash_and_crack() {
+        assert_eq!(run_with(&["encode"], b"hello").1, b"aGVsbG8=\n");
+        assert_eq!(run_with(&["decode", "-e", "hex"], b"68656c6c6f\n").1, b"hello");
+        assert_eq!(
+            run_with(&["hash"], b"abc").1,
+            b"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  -\n"
+        );
+        assert!(run_with(&["hash", "-a", "blake2b-512"], b"abc").1.starts_with(b"ba80a53f981c4d0d"));
+
+        let (code, plaintext, stderr) = run_with(
+            &["crack", "-a", "caesar"],
+            b"Wkh txlfn eurzq ira mxpsv ryhu wkh odcb grj dqg uxqv dzdb lqwr wkh irumhvw",
+        );
+        assert_eq!(code, EXIT_OK);
+        assert!(plaintext.starts_with(b"The quick brown fox"));
+        assert!(stderr.contains("Shift: 3"));
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!(run_with(&[], b"").0, EXIT_OK);
+        assert_eq!(run_with(&["frobnicate"], b"").0, EXIT_USAGE);
+        assert_eq!(run_with(&["encrypt", "-a", "Nope", "-k", "x"], b"").0, EXIT_USAGE);
+        assert_eq!(run_with(&["encrypt", "-a", "XOR"], b"data").0, EXIT_USAGE);
+        assert_eq!(run_with(&["encrypt", "-a", "Caesar", "-k", "3", "-i", "/nonexistent/input"], b"").0, EXIT_IO);
+        assert_eq!(run_with(&["decode", "-e", "hex"], b"zz").0, EXIT_INVALID_INPUT);
+        assert_eq!(run_with(&["decrypt", "-a", "XOR", "-k", "k"], b"not hex").0, EXIT_INVALID_INPUT);
+    }
+
+    #[test]
+    fn test_bench_formats() {
+        let quick = ["-p", "sizes=1K,4K", "-p", "repeats=2", "-p", "warmup=0"];
+        let bench_with = |extra: &[&str]| {
+            let args: Vec<&str> = ["bench", "-a", "AES-CTR-128,simple_base64"].iter().chain(&quick).chain(extra).copied().collect();
+            let (code, stdout, stderr) = run_with(&args, b"");
+            assert_eq!(code, EXIT_OK, "{}", stderr);
+            String::from_utf8(stdout).unwrap()
+        };
+
+        let csv = bench_with(&["-p", "format=csv"]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 1 + 2 * 2 * 2);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].starts_with("AES-CTR-128,cipher,encrypt,1024,2,"), "{}", lines[1]);
+        assert!(lines[8].starts_with("simple_base64,encoding,decode,4096,2,"), "{}", lines[8]);
+
+        let json = bench_with(&["-p", "format=JSON"]);
+        assert!(json.starts_with("[\n  {\"target\": \"AES-CTR-128\", \"kind\": \"cipher\"") && json.ends_with("}\n]\n"), "{}", json);
+        assert_eq!(json.matches("\"mb_per_s\"").count(), 8);
+        assert_eq!(json.matches("},\n  {").count(), 7);
+
+        let table = bench_with(&[]);
+        assert!(table.starts_with("Algorithm") && table.contains("MB/s median"));
+        assert!(table.lines().any(|line| line.starts_with("simple_base64") && line.contains("decode") && line.contains("4 KiB")));
+
+        for bad in [["-p", "sizes=2X"], ["-p", "repeats=0"], ["-p", "limit=-1"], ["-p", "format=xml"], ["-a", "Rot47"]] {
+            let args: Vec<&str> = ["bench"].iter().chain(&quick).chain(&bad).copied().collect();
+            assert_eq!(run_with(&args, b"").0, EXIT_USAGE, "{:?}", bad);
+        }
+    }
+}
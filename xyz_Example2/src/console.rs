@@ -0,0 +1,172 @@
+// Console: the Input and Output Every Interactive Menu Goes Through
+// Consola: la Entrada y Salida por la que Pasa Cada Menú Interactivo
+
+use std::io::{self, BufRead, Write};
+
+use std::fmt::Display;
+
+use xyz_example2::CipherError;
+
+use crate::messages::Messages;
+
+/// Line-based console over any reader and writer, so menus run the same on a terminal and in tests
+/// Consola por líneas sobre cualquier lector y escritor, así los menús funcionan igual en una terminal y en pruebas
+pub struct Console<R, W> {
+    input: R,
+    output: W,
+    messages: Messages,
+}
+
+impl<R, W> Console<R, W> {
+    pub fn with_messages(input: R, output: W, messages: Messages) -> Self {
+        Self { input, output, messages }
+    }
+
+    pub fn messages(&self) -> &Messages {
+        &self.messages
+    }
+
+    /// Catalog text for `key` with its placeholders filled / Texto del catálogo para `key` con sus marcadores rellenos
+    pub fn text(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        self.messages.format(key, args)
+    }
+}
+
+impl<R, W: Write> Console<R, W> {
+    /// Print the catalog text for `key` as one line / Imprimir el texto del catálogo para `key` como una línea
+    pub fn say(&mut self, key: &str, args: &[(&str, &dyn Display)]) -> Result<(), CipherError> {
+        let line = self.text(key, args);
+        writeln!(self.output, "{}", line)?;
+        Ok(())
+    }
+}
+
+impl<R: BufRead, W: Write> Console<R, W> {
+    /// Prompt with the catalog text for `key` / Preguntar con el texto del catálogo para `key`
+    pub fn ask(&mut self, key: &str, args: &[(&str, &dyn Display)]) -> Result<String, CipherError> {
+        let message = format!("{} ", self.text(key, args));
+        self.prompt(&message)
+    }
+
+    /// Yes/no question with the catalog text for `key` / Pregunta de sí o no con el texto del catálogo para `key`
+    pub fn ask_yes_no(&mut self, key: &str) -> Result<bool, CipherError> {
+        let message = format!("{} ", self.text(key, &[]));
+        self.confirm(&message)
+    }
+
+    /// Print a prompt and read one trimmed line; end of input is an error
+    /// Mostrar un mensaje y leer una línea recortada; el fin de la entrada es un error
+    pub fn prompt(&mut self, message: &str) -> Result<String, CipherError> {
+        write!(self.output, "{}", message)?;
+        self.output.flush()?;
+
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "end of input").into());
+        }
+        Ok(line.trim().to_string())
+    }
+
+    /// Ask a yes/no question; "y", "yes", "s", "si" and "sí" mean yes
+    /// Hacer una pregunta de sí o no; "y", "yes", "s", "si" y "sí" significan sí
+    pub fn confirm(&mut self, message: &str) -> Result<bool, CipherError> {
+        let answer = self.prompt(message)?.to_lowercase();
+        Ok(matches!(answer.as_str(), "y" | "yes" | "s" | "si" | "sí"))
+    }
+}
+
+impl<R, W: Write> Write for Console<R, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
+
+/// Scripted sessions for tests / Sesiones guionizadas para pruebas
+#[cfg(test)]
+pub mod script {
+    use super::Console;
+    use crate::messages::Messages;
+
+    pub type ScriptConsole<'a> = Console<&'a [u8], Vec<u8>>;
+
+    /// Feed `input` to `session` and return everything it printed
+    /// Pasar `input` a `session` y devolver todo lo que imprimió
+    pub fn replay(input: &str, session: impl FnOnce(&mut ScriptConsole<'_>)) -> String {
+        replay_in(Messages::english(), input, session)
+    }
+
+    /// `replay` in another language / `replay` en otro idioma
+    pub fn replay_in(messages: Messages, input: &str, session: impl FnOnce(&mut ScriptConsole<'_>)) -> String {
+        let mut console = Console::with_messages(input.as_bytes(), Vec::new(), messages);
+        session(&mut console);
+        String::from_utf8(console.output).expect("menus print UTF-8")
+    }
+
+    /// Replay a transcript: lines starting with `>` are typed in (a lone `>` is an empty line), every
+    /// other non-blank line must appear in the output, in order. Returns the output for further checks.
+    /// Reproducir una transcripción: las líneas que empiezan por `>` se teclean (un `>` solo es una línea
+    /// vacía), y cada otra línea no vacía debe aparecer en la salida, en orden. Devuelve la salida.
+    pub fn assert_transcript(transcript: &str, session: impl FnOnce(&mut ScriptConsole<'_>)) -> String {
+        assert_transcript_in(Messages::english(), transcript, session)
+    }
+
+    /// `assert_transcript` in another language / `assert_transcript` en otro idioma
+    pub fn assert_transcript_in(
+        messages: Messages,
+        transcript: &str,
+        session: impl FnOnce(&mut ScriptConsole<'_>),
+    ) -> String {
+        let lines: Vec<&str> = transcript.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+        let input: String = lines
+            .iter()
+            .filter_map(|line| line.strip_prefix('>'))
+            .map(|line| format!("{}\n", line.trim_start()))
+            .collect();
+        let output = replay_in(messages, &input, session);
+
+        let mut rest = output.as_str();
+        for expected in lines.iter().filter(|line| !line.starts_with('>')) {
+            match rest.find(expected) {
+                Some(at) => rest = &rest[at + expected.len()..],
+                None => panic!("expected {:?} in the rest of the session:\n{}\n--- full output ---\n{}", expected, rest, output),
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::script::{assert_transcript, replay};
+    use super::*;
+
+    #[test]
+    fn test_prompt_reads_trimmed_lines_until_end_of_input() {
+        let output = replay("  first  \n", |console| {
+            assert_eq!(console.prompt("Name: ").unwrap(), "first");
+            assert!(matches!(console.prompt("Again: "), Err(CipherError::Io(_))));
+        });
+        assert_eq!(output, "Name: Again: ");
+    }
+
+    #[test]
+    fn test_confirm_accepts_english_and_spanish() {
+        replay("s\nYes\nno\nsí\n", |console| {
+            let answers: Vec<bool> = (0..4).map(|_| console.confirm("? ").unwrap()).collect();
+            assert_eq!(answers, [true, true, false, true]);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "expected \"hello\"")]
+    fn test_transcript_checks_output_order() {
+        assert_transcript("goodbye\n> x\nhello", |console| {
+            let name = console.prompt("hello ").unwrap();
+            writeln!(console, "goodbye {}", name).unwrap();
+        });
+    }
+}
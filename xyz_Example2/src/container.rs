@@ -0,0 +1,502 @@
+// Encrypted File Container: Self-Describing Header, Ciphertext and Integrity Tag
+// Contenedor de Archivos Cifrados: Cabecera Autodescriptiva, Texto Cifrado y Etiqueta de Integridad
+
+use std::io::{self, Read};
+
+use crate::classical::{hill, EnigmaSettings};
+use crate::error::CipherError;
+use crate::file::CipherType;
+use crate::hash::{Digest, Sha256};
+use crate::hmac::Hmac;
+use crate::kdf::{Argon2Params, KdfParams};
+use crate::random::SecureRandom;
+use crate::secret::{ct_eq, SecretKey};
+
+pub const MAGIC: &[u8; 4] = b"XYZC";
+/// One ciphertext and one tag for the whole file / Un texto cifrado y una etiqueta para todo el archivo
+pub const VERSION_WHOLE: u8 = 1;
+/// Chunked stream with a tag per chunk (see `stream`) / Flujo por fragmentos con una etiqueta por fragmento
+pub const VERSION_CHUNKED: u8 = 2;
+/// HMAC-SHA256 tag or SHA-256 checksum / Etiqueta HMAC-SHA256 o suma SHA-256
+pub const TAG_LEN: usize = 32;
+
+// Layout / Formato:
+//   magic(4) | version(1) | algorithm id(1) | Hill size or AES key size(1) | nonce len(1) | nonce
+//   | kdf id(1) | kdf params (u32 BE each) | salt len(1) | salt | integrity(1)
+// Version 1 / Versión 1:  | ciphertext | tag(32), the tag covers everything before it
+// Version 2 / Versión 2:  | chunk frames, see `stream` / fragmentos, ver `stream`
+
+/// How the trailing tag is computed / Cómo se calcula la etiqueta final
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Integrity {
+    Checksum = 0, // SHA-256, only for keyless classical ciphers / SHA-256, solo para cifrados clásicos sin clave
+    Hmac = 1,     // HMAC-SHA256 with the MAC subkey / HMAC-SHA256 con la subclave MAC
+}
+
+/// Everything needed to decrypt except the key itself
+/// Todo lo necesario para descifrar salvo la propia clave
+#[derive(Clone, Debug)]
+pub struct ContainerHeader {
+    pub version: u8,
+    pub algorithm: CipherType, // Keys and key-like parameters zeroed; sizes and nonce are real / Claves a cero
+    pub kdf: Option<KdfParams>,
+    pub salt: Vec<u8>,
+    pub integrity: Integrity,
+}
+
+impl ContainerHeader {
+    /// Fails when the nonce or salt does not fit its one-byte length
+    /// Falla si el nonce o la sal no caben en su longitud de un byte
+    pub fn to_bytes(&self) -> Result<Vec<u8>, CipherError> {
+        let length_byte = |name: &str, len: usize| {
+            u8::try_from(len).map_err(|_| CipherError::InvalidParam { name: name.to_string(), value: format!("{} bytes", len) })
+        };
+        let (id, param, nonce) = describe(&self.algorithm);
+        let mut out = MAGIC.to_vec();
+        out.extend_from_slice(&[self.version, id, param, length_byte("nonce", nonce.len())?]);
+        out.extend_from_slice(&nonce);
+        match self.kdf {
+            None => out.push(0),
+            Some(KdfParams::Pbkdf2Sha256 { iterations }) => {
+                out.push(1);
+                out.extend_from_slice(&iterations.to_be_bytes());
+            }
+            Some(KdfParams::Argon2id(params)) => {
+                out.push(2);
+                for value in [params.memory_kib, params.iterations, params.parallelism] {
+                    out.extend_from_slice(&value.to_be_bytes());
+                }
+            }
+        }
+        out.push(length_byte("salt", self.salt.len())?);
+        out.extend_from_slice(&self.salt);
+        out.push(self.integrity as u8);
+        Ok(out)
+    }
+
+    /// Parse a header, returning it with its length in bytes
+    /// Interpretar una cabecera, devolviéndola con su longitud en bytes
+    pub fn parse(data: &[u8]) -> Result<(Self, usize), CipherError> {
+        if !is_container(data) {
+            return Err(CipherError::MalformedInput {
+                position: 0,
+                reason: "missing container header (headerless files need legacy mode)".to_string(),
+            });
+        }
+        let mut reader = Reader { data, position: MAGIC.len() };
+        let version = reader.byte()?;
+        if version != VERSION_WHOLE && version != VERSION_CHUNKED {
+            return Err(reader.error(1, format!("unsupported container version {}", version)));
+        }
+
+        let id = reader.byte()?;
+        let param = reader.byte()?;
+        let nonce_len = reader.byte()? as usize;
+        let nonce = reader.take(nonce_len)?.to_vec();
+        let algorithm = algorithm_from_parts(id, param, &nonce).map_err(|reason| reader.error(3 + nonce_len, reason))?;
+
+        let kdf = match reader.byte()? {
+            0 => None,
+            1 => Some(KdfParams::Pbkdf2Sha256 { iterations: reader.u32()? }),
+            2 => Some(KdfParams::Argon2id(Argon2Params {
+                memory_kib: reader.u32()?,
+                iterations: reader.u32()?,
+                parallelism: reader.u32()?,
+            })),
+            other => return Err(reader.error(1, format!("unknown KDF id {}", other))),
+        };
+        // Refuse hostile costs here, before anything is allocated / Rechazar costes hostiles aquí, antes de reservar nada
+        if let Some(params) = kdf {
+            let len = match params {
+                KdfParams::Pbkdf2Sha256 { .. } => 4,
+                KdfParams::Argon2id(_) => 12,
+            };
+            params.validate().map_err(|e| reader.error(len, format!("KDF parameters out of range: {}", e)))?;
+        }
+        let salt_len = reader.byte()? as usize;
+        let salt = reader.take(salt_len)?.to_vec();
+        let integrity = match reader.byte()? {
+            0 => Integrity::Checksum,
+            1 => Integrity::Hmac,
+            other => return Err(reader.error(1, format!("unknown integrity mode {}", other))),
+        };
+        Ok((Self { version, algorithm, kdf, salt, integrity }, reader.position))
+    }
+
+    /// Read exactly one header from a stream, leaving the reader at the payload
+    /// Leer exactamente una cabecera de un flujo, dejando el lector al inicio del contenido
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self, CipherError> {
+        let mut buf = Vec::new();
+        let mut read = |buf: &mut Vec<u8>, len: usize| -> Result<(), CipherError> {
+            let start = buf.len();
+            buf.resize(start + len, 0);
+            reader.read_exact(&mut buf[start..]).map_err(|e| match e.kind() {
+                io::ErrorKind::UnexpectedEof => CipherError::MalformedInput {
+                    position: start,
+                    reason: "container header is truncated".to_string(),
+                },
+                _ => e.into(),
+            })
+        };
+
+        read(&mut buf, MAGIC.len())?;
+        if !is_container(&buf) {
+            return Self::parse(&buf).map(|(header, _)| header);
+        }
+        read(&mut buf, 4)?;
+        let nonce_len = buf[7] as usize;
+        read(&mut buf, nonce_len + 1)?;
+        let kdf_len = match buf[buf.len() - 1] {
+            1 => 4,
+            2 => 12,
+            _ => 0,
+        };
+        read(&mut buf, kdf_len + 1)?;
+        let salt_len = buf[buf.len() - 1] as usize;
+        read(&mut buf, salt_len + 1)?;
+        Self::parse(&buf).map(|(header, _)| header)
+    }
+}
+
+/// Does the data start with the container magic? / ¿Empiezan los datos con la firma del contenedor?
+pub fn is_container(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+/// A parsed container whose tag has not been checked yet
+/// Un contenedor interpretado cuya etiqueta aún no se ha comprobado
+pub struct Container<'a> {
+    pub header: ContainerHeader,
+    pub ciphertext: &'a [u8],
+    authenticated: &'a [u8],
+    tag: &'a [u8],
+}
+
+impl<'a> Container<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, CipherError> {
+        let (header, header_len) = ContainerHeader::parse(data)?;
+        if data.len() < header_len + TAG_LEN {
+            return Err(CipherError::MalformedInput {
+                position: data.len(),
+                reason: "container is truncated".to_string(),
+            });
+        }
+        let (authenticated, tag) = data.split_at(data.len() - TAG_LEN);
+        Ok(Self { header, ciphertext: &authenticated[header_len..], authenticated, tag })
+    }
+
+    /// Check the tag; a keyed container always needs the HMAC
+    /// Comprobar la etiqueta; un contenedor con clave siempre necesita el HMAC
+    pub fn verify(&self, mac_key: Option<&[u8]>) -> Result<(), CipherError> {
+        match (self.header.integrity, mac_key) {
+            (Integrity::Hmac, Some(key)) => {
+                let mut mac = Hmac::<Sha256>::new(key);
+                mac.update(self.authenticated);
+                mac.verify(self.tag)
+            }
+            (Integrity::Checksum, None) => {
+                let expected = Sha256::digest(self.authenticated);
+                if ct_eq(&expected, self.tag) { Ok(()) } else { Err(CipherError::AuthenticationFailed) }
+            }
+            (Integrity::Hmac, None) => Err(CipherError::EmptyKey),
+            // Refuse a keyless checksum where a MAC was expected / Rechazar una suma sin clave donde se esperaba un MAC
+            (Integrity::Checksum, Some(_)) => Err(CipherError::AuthenticationFailed),
+        }
+    }
+}
+
+/// Serialize header, ciphertext and tag / Serializar cabecera, texto cifrado y etiqueta
+pub fn seal(header: &ContainerHeader, ciphertext: &[u8], mac_key: Option<&[u8]>) -> Result<Vec<u8>, CipherError> {
+    let mut out = header.to_bytes()?;
+    out.extend_from_slice(ciphertext);
+    let tag = match mac_key {
+        Some(key) => {
+            let mut mac = Hmac::<Sha256>::new(key);
+            mac.update(&out);
+            mac.finalize()
+        }
+        None => Sha256::digest(&out),
+    };
+    out.extend_from_slice(&tag);
+    Ok(out)
+}
+
+/// Same algorithm with keys zeroed and a new random nonce, IV or counter
+/// El mismo algoritmo con claves a cero y un nonce, IV o contador aleatorio nuevo
+pub fn with_fresh_nonce(algorithm: &CipherType, rng: &mut SecureRandom) -> CipherType {
+    let (id, param, nonce) = describe(algorithm);
+    algorithm_from_parts(id, param, &rng.bytes(nonce.len())).expect("describe() output is always valid")
+}
+
+/// Algorithm id, its one-byte parameter and its nonce / Id del algoritmo, su parámetro de un byte y su nonce
+fn describe(algorithm: &CipherType) -> (u8, u8, Vec<u8>) {
+    let aes_len = |key: &SecretKey| match key.len() {
+        16 | 24 => key.len() as u8,
+        _ => 32,
+    };
+    match algorithm {
+        CipherType::Caesar(_) => (1, 0, Vec::new()),
+        CipherType::XOR => (2, 0, Vec::new()),
+        CipherType::Vigenere => (3, 0, Vec::new()),
+        CipherType::Enigma(_) => (15, 0, Vec::new()),
+        CipherType::Affine { .. } => (9, 0, Vec::new()),
+        CipherType::Atbash => (10, 0, Vec::new()),
+        CipherType::Playfair => (11, 0, Vec::new()),
+        CipherType::Hill { matrix } => (12, matrix.len().isqrt() as u8, Vec::new()),
+        CipherType::RailFence(_) => (13, 0, Vec::new()),
+        CipherType::Columnar => (14, 0, Vec::new()),
+        CipherType::ChaCha20Poly1305 { nonce, .. } => (4, 0, nonce.to_vec()),
+        CipherType::AesEcb { key } => (5, aes_len(key), Vec::new()),
+        CipherType::AesCbc { key, iv } => (6, aes_len(key), iv.to_vec()),
+        CipherType::AesCtr { key, counter } => (7, aes_len(key), counter.to_vec()),
+        CipherType::AesGcm { key, nonce } => (8, aes_len(key), nonce.to_vec()),
+    }
+}
+
+fn algorithm_from_parts(id: u8, param: u8, nonce: &[u8]) -> Result<CipherType, String> {
+    let aes_key = || match param {
+        16 | 24 | 32 => Ok(SecretKey::from(vec![0u8; param as usize])),
+        _ => Err(format!("invalid AES key size {}", param)),
+    };
+    let fixed = |expected: usize| -> Result<Vec<u8>, String> {
+        if nonce.len() == expected {
+            Ok(nonce.to_vec())
+        } else {
+            Err(format!("nonce must be {} bytes, got {}", expected, nonce.len()))
+        }
+    };
+    Ok(match id {
+        1 if param == 0 => CipherType::Caesar(0),
+        1 => return Err("the Caesar shift is key material and cannot be stored in the header".to_string()),
+        2 => CipherType::XOR,
+        3 => CipherType::Vigenere,
+        4 => CipherType::ChaCha20Poly1305 { key: SecretKey::new(&[0; 32]), nonce: fixed(12)?.try_into().unwrap() },
+        5 => CipherType::AesEcb { key: aes_key()? },
+        6 => CipherType::AesCbc { key: aes_key()?, iv: fixed(16)?.try_into().unwrap() },
+        7 => CipherType::AesCtr { key: aes_key()?, counter: fixed(16)?.try_into().unwrap() },
+        8 => CipherType::AesGcm { key: aes_key()?, nonce: fixed(12)?.try_into().unwrap() },
+        9 if nonce.is_empty() => CipherType::Affine { a: 1, b: 0 },
+        9 => return Err("the Affine key is key material and cannot be stored in the header".to_string()),
+        10 => CipherType::Atbash,
+        11 => CipherType::Playfair,
+        // Only the size is stored; the matrix comes from the key / Solo se guarda el tamaño; la matriz sale de la clave
+        12 if (2..=hill::MAX_SIZE).contains(&(param as usize)) && nonce.is_empty() => {
+            CipherType::Hill { matrix: "A".repeat(param as usize * param as usize) }
+        }
+        12 => return Err(format!("invalid {}x{} Hill matrix", param, param)),
+        13 if param == 0 => CipherType::RailFence(2),
+        13 => return Err("the rail count is key material and cannot be stored in the header".to_string()),
+        14 => CipherType::Columnar,
+        15 if nonce.is_empty() => CipherType::Enigma(EnigmaSettings::default()),
+        15 => return Err("the Enigma settings are key material and cannot be stored in the header".to_string()),
+        other => return Err(format!("unknown algorithm id {}", other)),
+    })
+}
+
+/// Bounds-checked cursor over the header / Cursor con comprobación de límites sobre la cabecera
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], CipherError> {
+        let end = self.position + len;
+        if end > self.data.len() {
+            return Err(CipherError::MalformedInput {
+                position: self.data.len(),
+                reason: "container header is truncated".to_string(),
+            });
+        }
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, CipherError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, CipherError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    /// Error pointing `back` bytes before the cursor / Error que apunta `back` bytes antes del cursor
+    fn error(&self, back: usize, reason: String) -> CipherError {
+        CipherError::MalformedInput { position: self.position - back, reason }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classical::EnigmaSettings;
+
+    fn sample_header() -> ContainerHeader {
+        ContainerHeader {
+            version: VERSION_WHOLE,
+            algorithm: CipherType::AesGcm { key: vec![0; 24].into(), nonce: [5; 12] },
+            kdf: Some(KdfParams::Argon2id(Argon2Params { memory_kib: 64, iterations: 3, parallelism: 2 })),
+            salt: vec![9; 16],
+            integrity: Integrity::Hmac,
+        }
+    }
+
+    #[test]
+    fn test_header_round_trip() {
+        let bytes = sample_header().to_bytes().unwrap();
+        assert!(bytes.starts_with(b"XYZC\x01\x08\x18\x0c"));
+        let (parsed, len) = ContainerHeader::parse(&bytes).unwrap();
+        assert_eq!(len, bytes.len());
+        assert_eq!(parsed.to_bytes().unwrap(), bytes);
+        assert_eq!(parsed.kdf, sample_header().kdf);
+        assert!(matches!(parsed.algorithm, CipherType::AesGcm { ref key, nonce } if key.len() == 24 && nonce == [5; 12]));
+    }
+
+    #[test]
+    fn test_header_rejects_bad_input_with_positions() {
+        let bytes = sample_header().to_bytes().unwrap();
+        assert!(matches!(
+            ContainerHeader::parse(b"plain old ciphertext"),
+            Err(CipherError::MalformedInput { position: 0, .. })
+        ));
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[4] = 9;
+        assert!(matches!(ContainerHeader::parse(&wrong_version), Err(CipherError::MalformedInput { position: 4, .. })));
+
+        let mut wrong_algorithm = bytes.clone();
+        wrong_algorithm[5] = 42;
+        assert!(matches!(ContainerHeader::parse(&wrong_algorithm), Err(CipherError::MalformedInput { position: 5, .. })));
+
+        assert!(matches!(ContainerHeader::parse(&bytes[..10]), Err(CipherError::MalformedInput { position: 10, .. })));
+
+        // The shift is never written, so a header carrying one is refused / El desplazamiento nunca se escribe
+        let mut caesar = ContainerHeader { algorithm: CipherType::Caesar(3), ..sample_header() }.to_bytes().unwrap();
+        assert_eq!(caesar[6], 0);
+        caesar[6] = 3;
+        assert!(matches!(ContainerHeader::parse(&caesar), Err(CipherError::MalformedInput { position: 5, .. })));
+    }
+
+    #[test]
+    fn test_header_rejects_hostile_kdf_parameters() {
+        let kdf_at = sample_header().to_bytes().unwrap().len() - 1 - 1 - 16 - 12;
+        for (params, hostile) in [
+            (Argon2Params { memory_kib: u32::MAX, iterations: 1, parallelism: 1 }, "memory"),
+            (Argon2Params { memory_kib: 64, iterations: u32::MAX, parallelism: 1 }, "passes"),
+            (Argon2Params { memory_kib: u32::MAX, iterations: 1, parallelism: u32::MAX }, "lanes"),
+        ] {
+            let bytes = ContainerHeader { kdf: Some(KdfParams::Argon2id(params)), ..sample_header() }.to_bytes().unwrap();
+            let error = ContainerHeader::parse(&bytes).err();
+            assert!(matches!(error, Some(CipherError::MalformedInput { position, .. }) if position == kdf_at), "{}", hostile);
+            assert!(ContainerHeader::read_from(&mut &bytes[..]).is_err(), "{}", hostile);
+        }
+
+        let pbkdf2 = ContainerHeader { kdf: Some(KdfParams::Pbkdf2Sha256 { iterations: u32::MAX }), ..sample_header() };
+        assert!(matches!(ContainerHeader::parse(&pbkdf2.to_bytes().unwrap()), Err(CipherError::MalformedInput { .. })));
+    }
+
+    #[test]
+    fn test_to_bytes_rejects_salt_longer_than_its_length_byte() {
+        let longest = ContainerHeader { salt: vec![7; 255], ..sample_header() };
+        let (parsed, _) = ContainerHeader::parse(&longest.to_bytes().unwrap()).unwrap();
+        assert_eq!(parsed.salt, longest.salt);
+        let too_long = ContainerHeader { salt: vec![7; 256], ..sample_header() };
+        assert!(matches!(too_long.to_bytes(), Err(CipherError::InvalidParam { name, .. }) if name == "salt"));
+        assert!(seal(&too_long, b"ciphertext", None).is_err());
+    }
+
+    #[test]
+    fn test_read_from_stops_at_payload() {
+        let mut bytes = sample_header().to_bytes().unwrap();
+        let header_len = bytes.len();
+        bytes.extend_from_slice(b"payload");
+        let mut reader = &bytes[..];
+        let header = ContainerHeader::read_from(&mut reader).unwrap();
+        assert_eq!(header.to_bytes().unwrap().len(), header_len);
+        assert_eq!(reader, b"payload");
+
+        let mut truncated = &bytes[..header_len - 3];
+        assert!(matches!(ContainerHeader::read_from(&mut truncated), Err(CipherError::MalformedInput { .. })));
+        let mut plain = &b"no header here"[..];
+        assert!(matches!(ContainerHeader::read_from(&mut plain), Err(CipherError::MalformedInput { position: 0, .. })));
+    }
+
+    #[test]
+    fn test_seal_and_verify() {
+        let header = sample_header();
+        let sealed = seal(&header, b"ciphertext", Some(b"mac key")).unwrap();
+        let container = Container::parse(&sealed).unwrap();
+        assert_eq!(container.ciphertext, b"ciphertext");
+        assert!(container.verify(Some(b"mac key")).is_ok());
+        assert!(matches!(container.verify(Some(b"other key")), Err(CipherError::AuthenticationFailed)));
+        assert!(matches!(container.verify(None), Err(CipherError::EmptyKey)));
+
+        let mut corrupted = sealed.clone();
+        let last = corrupted.len() - TAG_LEN - 1;
+        corrupted[last] ^= 1;
+        assert!(matches!(Container::parse(&corrupted).unwrap().verify(Some(b"mac key")), Err(CipherError::AuthenticationFailed)));
+    }
+
+    #[test]
+    fn test_checksum_mode_and_downgrade() {
+        let header = ContainerHeader {
+            version: VERSION_WHOLE,
+            algorithm: CipherType::Caesar(3),
+            kdf: None,
+            salt: Vec::new(),
+            integrity: Integrity::Checksum,
+        };
+        let sealed = seal(&header, b"Khoor", None).unwrap();
+        let container = Container::parse(&sealed).unwrap();
+        assert!(container.verify(None).is_ok());
+        assert!(matches!(container.verify(Some(b"key")), Err(CipherError::AuthenticationFailed)));
+    }
+
+    #[test]
+    fn test_fresh_nonce_keeps_algorithm_shape() {
+        let mut rng = SecureRandom::from_seed([3; 32]);
+        let fresh = with_fresh_nonce(&CipherType::AesCbc { key: vec![1; 16].into(), iv: [0; 16] }, &mut rng);
+        assert!(matches!(fresh, CipherType::AesCbc { ref key, iv } if key.expose() == [0; 16] && iv != [0; 16]));
+        // Only the Hill size survives / Solo sobrevive el tamaño de Hill
+        let hill = with_fresh_nonce(&CipherType::Hill { matrix: "GYBNQKURP".to_string() }, &mut rng);
+        assert!(matches!(hill, CipherType::Hill { ref matrix } if matrix == "AAAAAAAAA"));
+        let enigma = with_fresh_nonce(&CipherType::Enigma("II IV V B BUL BLA AV BS CG DL FU HZ IN KM OW RX".parse().unwrap()), &mut rng);
+        assert!(matches!(enigma, CipherType::Enigma(ref settings) if *settings == EnigmaSettings::default()));
+    }
+
+    #[test]
+    fn test_classical_parameters_round_trip() {
+        for algorithm in [
+            CipherType::Affine { a: 5, b: 8 },
+            CipherType::Hill { matrix: "HILL".to_string() },
+            CipherType::RailFence(3),
+            CipherType::Playfair,
+            CipherType::Enigma("II IV V B BUL BLA AV BS CG DL FU HZ IN KM OW RX".parse().unwrap()),
+        ] {
+            let header = ContainerHeader { algorithm, ..sample_header() };
+            let bytes = header.to_bytes().unwrap();
+            assert_eq!(ContainerHeader::parse(&bytes).unwrap().0.to_bytes().unwrap(), bytes);
+        }
+        let hill = ContainerHeader { algorithm: CipherType::Hill { matrix: "HILL".to_string() }, ..sample_header() }.to_bytes().unwrap();
+        assert!(!hill.windows(4).any(|w| w == b"HILL"));
+        let mut bad_hill = hill.clone();
+        bad_hill[6] = 7;
+        assert!(matches!(ContainerHeader::parse(&bad_hill), Err(CipherError::MalformedInput { .. })));
+
+        // Affine a and b, the rail count and the Enigma settings are key material
+        // Afín a y b, el número de rieles y la configuración de Enigma son material de clave
+        let affine = ContainerHeader { algorithm: CipherType::Affine { a: 5, b: 8 }, ..sample_header() }.to_bytes().unwrap();
+        assert_eq!(&affine[5..8], &[9, 0, 0]);
+        let mut rails = ContainerHeader { algorithm: CipherType::RailFence(3), ..sample_header() }.to_bytes().unwrap();
+        assert_eq!(&rails[5..8], &[13, 0, 0]);
+        let enigma = ContainerHeader { algorithm: CipherType::Enigma(EnigmaSettings::default()), ..sample_header() };
+        let mut enigma = enigma.to_bytes().unwrap();
+        assert_eq!(&enigma[5..8], &[15, 0, 0]);
+        enigma[7] = 1;
+        enigma.insert(8, b'I');
+        assert!(matches!(ContainerHeader::parse(&enigma), Err(CipherError::MalformedInput { .. })));
+        rails[6] = 3;
+        assert!(matches!(ContainerHeader::parse(&rails), Err(CipherError::MalformedInput { .. })));
+    }
+}
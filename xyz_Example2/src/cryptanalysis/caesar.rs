@@ -0,0 +1,97 @@
+// Caesar and ROT-n Cracker Using Chi-Squared Scoring
+// Descifrador de César y ROT-n Usando Puntuación Chi-Cuadrado
+
+use crate::classical::caesar_decrypt;
+use crate::error::CipherError;
+
+use super::frequency::{chi_squared, letter_counts, unshift_counts, Language};
+
+/// One ranked guess at the shift / Una suposición clasificada del desplazamiento
+#[derive(Clone, Debug)]
+pub struct CaesarCandidate {
+    pub shift: u8,            // Shift (ROT-n is shift n) / Desplazamiento (ROT-n es desplazamiento n)
+    pub language: Language,   // Best-matching language / Idioma que mejor coincide
+    pub chi_squared: f64,     // Lower is better / Menor es mejor
+    pub confidence: f64,      // Share of likelihood in 0-1 / Proporción de verosimilitud en 0-1
+    pub plaintext: String,
+}
+
+/// Try all 26 shifts against every language and return the `top` best candidates
+/// Probar los 26 desplazamientos con cada idioma y devolver los `top` mejores candidatos
+pub fn crack_caesar(ciphertext: &str, top: usize) -> Result<Vec<CaesarCandidate>, CipherError> {
+    let counts = letter_counts(ciphertext.as_bytes());
+    if counts.iter().all(|&c| c == 0) {
+        return Err(CipherError::MalformedInput {
+            position: 0,
+            reason: "no letters to analyze / no hay letras para analizar".to_string(),
+        });
+    }
+
+    // Best language for each shift / Mejor idioma para cada desplazamiento
+    let mut scored: Vec<(u8, Language, f64)> = (0..26u8)
+        .map(|shift| {
+            let plain = unshift_counts(&counts, shift);
+            Language::ALL
+                .iter()
+                .map(|&language| (shift, language, chi_squared(&plain, language)))
+                .min_by(|a, b| a.2.total_cmp(&b.2))
+                .expect("at least one language")
+        })
+        .collect();
+    scored.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+    // Treat exp(-chi²/2) as a likelihood and normalize (log-sum-exp keeps it finite)
+    // Tratar exp(-chi²/2) como verosimilitud y normalizar (log-sum-exp lo mantiene finito)
+    let best = scored[0].2;
+    let weights: Vec<f64> = scored.iter().map(|s| (-(s.2 - best) / 2.0).exp()).collect();
+    let total: f64 = weights.iter().sum();
+
+    scored
+        .into_iter()
+        .zip(weights)
+        .take(top)
+        .map(|((shift, language, chi), weight)| {
+            Ok(CaesarCandidate {
+                shift,
+                language,
+                chi_squared: chi,
+                confidence: weight / total,
+                plaintext: caesar_decrypt(ciphertext, shift)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classical::caesar_encrypt;
+
+    #[test]
+    fn test_cracks_english_shift() {
+        let plain = "It was the best of times, it was the worst of times, it was the age of wisdom.";
+        let candidates = crack_caesar(&caesar_encrypt(plain, 7).unwrap(), 3).unwrap();
+        assert_eq!(candidates.len(), 3);
+        assert_eq!(candidates[0].shift, 7);
+        assert_eq!(candidates[0].language, Language::English);
+        assert_eq!(candidates[0].plaintext, plain);
+        assert!(candidates[0].confidence > 0.9);
+    }
+
+    #[test]
+    fn test_identifies_spanish() {
+        let plain = "En un lugar de la Mancha, de cuyo nombre no quiero acordarme, no ha mucho tiempo que vivia un hidalgo";
+        let candidates = crack_caesar(&caesar_encrypt(plain, 19).unwrap(), 26).unwrap();
+        assert_eq!(candidates.len(), 26);
+        assert_eq!(candidates[0].shift, 19);
+        assert_eq!(candidates[0].language, Language::Spanish);
+        assert!(candidates.windows(2).all(|w| w[0].confidence >= w[1].confidence));
+        let total: f64 = candidates.iter().map(|c| c.confidence).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rejects_text_without_letters() {
+        assert!(matches!(crack_caesar("1234 !?", 5), Err(CipherError::MalformedInput { .. })));
+    }
+}
@@ -0,0 +1,104 @@
+// Letter Frequency Statistics for English and Spanish
+// Estadísticas de Frecuencia de Letras para Inglés y Español
+
+/// Reference language for frequency scoring / Idioma de referencia para puntuar frecuencias
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    English,
+    Spanish,
+}
+
+// Percentages for A-Z. Accented Spanish letters and Ñ are not ASCII, so the ciphers leave them
+// alone and they are left out here; scoring renormalizes each table.
+// Porcentajes para A-Z. Las letras acentuadas y la Ñ no son ASCII, así que los cifrados no las
+// tocan y aquí se omiten; la puntuación renormaliza cada tabla.
+const ENGLISH: [f64; 26] = [
+    8.167, 1.492, 2.782, 4.253, 12.702, 2.228, 2.015, 6.094, 6.966, 0.153, 0.772, 4.025, 2.406,
+    6.749, 7.507, 1.929, 0.095, 5.987, 6.327, 9.056, 2.758, 0.978, 2.360, 0.150, 1.974, 0.074,
+];
+
+const SPANISH: [f64; 26] = [
+    11.525, 2.215, 4.019, 5.010, 12.181, 0.692, 1.768, 0.703, 6.247, 0.493, 0.011, 4.967, 3.157,
+    6.712, 8.683, 2.510, 0.877, 6.871, 7.977, 4.632, 2.927, 1.138, 0.017, 0.215, 1.008, 0.467,
+];
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English / Inglés",
+            Language::Spanish => "Spanish / Español",
+        }
+    }
+
+    /// Expected share of each letter A-Z, in percent / Proporción esperada de cada letra A-Z, en porcentaje
+    pub fn frequencies(self) -> &'static [f64; 26] {
+        match self {
+            Language::English => &ENGLISH,
+            Language::Spanish => &SPANISH,
+        }
+    }
+}
+
+/// Count ASCII letters case-insensitively / Contar letras ASCII sin distinguir mayúsculas
+pub fn letter_counts(text: &[u8]) -> [usize; 26] {
+    let mut counts = [0usize; 26];
+    for &b in text {
+        if b.is_ascii_alphabetic() {
+            counts[(b.to_ascii_uppercase() - b'A') as usize] += 1;
+        }
+    }
+    counts
+}
+
+/// Pearson's chi-squared statistic; lower means closer to the language
+/// Estadístico chi-cuadrado de Pearson; menor significa más cercano al idioma
+pub fn chi_squared(counts: &[usize; 26], language: Language) -> f64 {
+    let total: usize = counts.iter().sum();
+    if total == 0 {
+        return f64::INFINITY;
+    }
+    let table_total: f64 = language.frequencies().iter().sum();
+    counts
+        .iter()
+        .zip(language.frequencies())
+        .map(|(&observed, &percent)| {
+            let expected = total as f64 * percent / table_total;
+            let diff = observed as f64 - expected;
+            diff * diff / expected
+        })
+        .sum()
+}
+
+/// Counts after undoing a shift: letter `i` of the result was letter `i + shift` in the input
+/// Conteos tras deshacer un desplazamiento: la letra `i` del resultado era la `i + shift` de la entrada
+pub fn unshift_counts(counts: &[usize; 26], shift: u8) -> [usize; 26] {
+    std::array::from_fn(|i| counts[(i + shift as usize) % 26])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables_cover_most_letters() {
+        for language in Language::ALL {
+            let total: f64 = language.frequencies().iter().sum();
+            assert!((95.0..=100.5).contains(&total), "{:?}: {}", language, total);
+        }
+    }
+
+    #[test]
+    fn test_counts_and_chi_squared() {
+        let counts = letter_counts(b"Hello, World!");
+        assert_eq!(counts[(b'L' - b'A') as usize], 3);
+        assert_eq!(counts.iter().sum::<usize>(), 10);
+        assert_eq!(unshift_counts(&letter_counts(b"B"), 1), letter_counts(b"A"));
+
+        let english = letter_counts(b"the quick brown fox jumps over the lazy dog and then some more english text");
+        let gibberish = letter_counts(b"zzqx jjvk qqzx wxyz kkqq zzjj xxvv qqzz");
+        assert!(chi_squared(&english, Language::English) < chi_squared(&gibberish, Language::English));
+        assert!(chi_squared(&[0; 26], Language::Spanish).is_infinite());
+    }
+}
@@ -0,0 +1,12 @@
+// Cryptanalysis: Breaking the Classical Ciphers Without the Key
+// Criptoanálisis: Romper los Cifrados Clásicos sin la Clave
+
+pub mod caesar;
+pub mod frequency;
+pub mod vigenere;
+pub mod xor;
+
+pub use caesar::{crack_caesar, CaesarCandidate};
+pub use frequency::Language;
+pub use vigenere::{crack_vigenere, VigenereSolution};
+pub use xor::{crack_repeating_xor, parse_ciphertext, XorSolution};
@@ -0,0 +1,294 @@
+// Vigenère Breaker: Kasiski Examination and Index of Coincidence
+// Romper Vigenère: Examen de Kasiski e Índice de Coincidencia
+
+use std::collections::HashMap;
+
+use crate::cipher::Cipher;
+use crate::classical::VigenereCipher;
+use crate::error::CipherError;
+
+use super::frequency::{chi_squared, unshift_counts, Language};
+
+/// Longest key length tried by default / Longitud de clave máxima probada por defecto
+pub const MAX_KEY_LEN: usize = 20;
+
+
+/// Evidence for one key length / Evidencia para una longitud de clave
+#[derive(Clone, Debug)]
+pub struct KeyLengthCandidate {
+    pub length: usize,
+    pub index_of_coincidence: f64, // Average over the columns / Media de las columnas
+    pub kasiski_votes: usize,      // Repeat distances divisible by the length / Distancias divisibles por la longitud
+}
+
+/// Recovered key and plaintext / Clave y texto plano recuperados
+#[derive(Clone, Debug)]
+pub struct VigenereSolution {
+    pub key: String,
+    pub language: Language,
+    pub plaintext: String,
+    pub key_lengths: Vec<KeyLengthCandidate>, // Best first / El mejor primero
+}
+
+/// Index of coincidence of a letter histogram / Índice de coincidencia de un histograma
+pub fn index_of_coincidence(counts: &[usize; 26]) -> f64 {
+    let total: usize = counts.iter().sum();
+    if total < 2 {
+        return 0.0;
+    }
+    let pairs: usize = counts.iter().map(|&c| c * c.saturating_sub(1)).sum();
+    pairs as f64 / (total * (total - 1)) as f64
+}
+
+// IC of uniformly random letters / IC de letras uniformemente aleatorias
+const RANDOM_IC: f64 = 1.0 / 26.0;
+
+// Lengths within this share of the best IC count as language-like
+// Las longitudes dentro de esta fracción del mejor IC se consideran de idioma
+const IC_TOLERANCE: f64 = 0.9;
+
+// Letters per column needed for a trustworthy IC / Letras por columna para un IC fiable
+const MIN_COLUMN_LETTERS: usize = 20;
+
+/// IC expected from plaintext in `language` / IC esperado de texto plano en `language`
+pub fn language_ic(language: Language) -> f64 {
+    let table = language.frequencies();
+    let total: f64 = table.iter().sum();
+    table.iter().map(|p| (p / total) * (p / total)).sum()
+}
+
+/// Friedman's key-length estimate from the whole-text IC
+/// Estimación de Friedman de la longitud de clave a partir del IC del texto completo
+pub fn friedman_estimate(ic: f64, language: Language) -> f64 {
+    (language_ic(language) - RANDOM_IC) / (ic - RANDOM_IC).max(f64::EPSILON)
+}
+
+/// Byte distances between repeated letter trigrams
+/// Distancias en bytes entre trigramas de letras repetidos
+///
+/// Distances are measured in bytes because the key advances on every byte.
+/// Las distancias se miden en bytes porque la clave avanza en cada byte.
+pub fn kasiski_distances(ciphertext: &[u8]) -> Vec<usize> {
+    let mut last_seen: HashMap<[u8; 3], usize> = HashMap::new();
+    let mut distances = Vec::new();
+    for (i, window) in ciphertext.windows(3).enumerate() {
+        if !window.iter().all(u8::is_ascii_alphabetic) {
+            continue;
+        }
+        let trigram = [
+            window[0].to_ascii_uppercase(),
+            window[1].to_ascii_uppercase(),
+            window[2].to_ascii_uppercase(),
+        ];
+        if let Some(previous) = last_seen.insert(trigram, i) {
+            distances.push(i - previous);
+        }
+    }
+    distances
+}
+
+/// Letter histograms of each key column; every byte advances the column
+/// Histogramas de letras de cada columna de la clave; cada byte avanza la columna
+fn column_counts(ciphertext: &[u8], length: usize) -> Vec<[usize; 26]> {
+    let mut columns = vec![[0usize; 26]; length];
+    for (i, &b) in ciphertext.iter().enumerate() {
+        if b.is_ascii_alphabetic() {
+            columns[i % length][(b.to_ascii_uppercase() - b'A') as usize] += 1;
+        }
+    }
+    columns
+}
+
+/// Rank key lengths 1..=`max_len`: the language-like IC ones first, then by Kasiski votes
+/// Ordenar longitudes 1..=`max_len`: primero las de IC de idioma, luego por votos de Kasiski
+pub fn estimate_key_lengths(ciphertext: &[u8], max_len: usize) -> Vec<KeyLengthCandidate> {
+    let distances = kasiski_distances(ciphertext);
+    let mut candidates: Vec<KeyLengthCandidate> = (1..=max_len.max(1))
+        .map(|length| {
+            let columns = column_counts(ciphertext, length);
+            let ic = columns.iter().map(index_of_coincidence).sum::<f64>() / length as f64;
+            KeyLengthCandidate {
+                length,
+                index_of_coincidence: ic,
+                kasiski_votes: distances.iter().filter(|&&d| d % length == 0).count(),
+            }
+        })
+        .collect();
+
+    // Multiples of the true length score as well as it on IC, and its divisors collect more
+    // Kasiski votes, so filter on IC first and then prefer the most votes and the shortest key.
+    // Sparse columns give noisy ICs, so the reference best only counts well-filled lengths.
+    // Los múltiplos de la longitud real puntúan igual en IC y sus divisores acumulan más votos
+    // de Kasiski, así que se filtra por IC y luego se prefieren más votos y la clave más corta.
+    // Las columnas escasas dan IC ruidosos, así que el mejor de referencia solo cuenta
+    // longitudes con columnas bien llenas.
+    let letters = ciphertext.iter().filter(|b| b.is_ascii_alphabetic()).count();
+    let plaintext_ic = Language::ALL.iter().map(|&l| language_ic(l)).fold(f64::INFINITY, f64::min);
+    let midpoint = (RANDOM_IC + plaintext_ic) / 2.0;
+    let best_ic = |filled: bool| {
+        candidates
+            .iter()
+            .filter(|c| !filled || c.length == 1 || letters / c.length >= MIN_COLUMN_LETTERS)
+            .map(|c| c.index_of_coincidence)
+            .fold(0.0, f64::max)
+    };
+    // Too short for filled columns to look like language: fall back to the midpoint
+    // Demasiado corto para que las columnas llenas parezcan idioma: volver al punto medio
+    let threshold = match best_ic(true) {
+        best if best >= midpoint => (best * IC_TOLERANCE).max(midpoint),
+        _ => midpoint.min(best_ic(false)),
+    };
+    candidates.sort_by(|a, b| {
+        let a_ok = a.index_of_coincidence >= threshold;
+        let b_ok = b.index_of_coincidence >= threshold;
+        b_ok.cmp(&a_ok)
+            .then(b.kasiski_votes.cmp(&a.kasiski_votes))
+            .then(a.length.cmp(&b.length))
+    });
+    candidates
+}
+
+/// Recover the key and plaintext, trying key lengths up to `max_len`
+/// Recuperar la clave y el texto plano, probando longitudes hasta `max_len`
+pub fn crack_vigenere(ciphertext: &str, max_len: usize) -> Result<VigenereSolution, CipherError> {
+    let bytes = ciphertext.as_bytes();
+    let letters = bytes.iter().filter(|b| b.is_ascii_alphabetic()).count();
+    if letters == 0 {
+        return Err(CipherError::MalformedInput {
+            position: 0,
+            reason: "no letters to analyze / no hay letras para analizar".to_string(),
+        });
+    }
+
+    // Each column needs a couple of letters to say anything / Cada columna necesita algunas letras
+    let key_lengths = estimate_key_lengths(bytes, max_len.min(letters / 2));
+    let columns = column_counts(bytes, key_lengths[0].length);
+
+    // Solve every column as a Caesar shift, per language / Resolver cada columna como César, por idioma
+    let (language, shifts) = Language::ALL
+        .iter()
+        .map(|&language| {
+            let mut total = 0.0;
+            let shifts: Vec<u8> = columns
+                .iter()
+                .map(|counts| {
+                    let (shift, chi) = (0..26u8)
+                        .map(|shift| (shift, chi_squared(&unshift_counts(counts, shift), language)))
+                        .min_by(|a, b| a.1.total_cmp(&b.1))
+                        .expect("26 shifts");
+                    // Empty columns score infinity; leave them at shift 0 / Las columnas vacías quedan en 0
+                    if chi.is_finite() {
+                        total += chi;
+                        shift
+                    } else {
+                        0
+                    }
+                })
+                .collect();
+            (language, shifts, total)
+        })
+        .min_by(|a, b| a.2.total_cmp(&b.2))
+        .map(|(language, shifts, _)| (language, shifts))
+        .expect("at least one language");
+
+    let key: String = shifts.iter().map(|&s| (b'A' + s) as char).collect();
+    let plaintext = VigenereCipher::new(key.as_bytes())?.decrypt(bytes)?;
+    Ok(VigenereSolution {
+        key,
+        language,
+        plaintext: String::from_utf8_lossy(&plaintext).into_owned(),
+        key_lengths,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptanalysis::frequency::letter_counts;
+
+    fn encrypt(text: &str, key: &str) -> String {
+        let ciphertext = VigenereCipher::new(key.as_bytes()).unwrap().encrypt(text.as_bytes()).unwrap();
+        String::from_utf8(ciphertext).unwrap()
+    }
+
+    const ENGLISH_TEXT: &str = "It is a truth universally acknowledged, that a single man in possession \
+        of a good fortune, must be in want of a wife. However little known the feelings or views of \
+        such a man may be on his first entering a neighbourhood, this truth is so well fixed in the \
+        minds of the surrounding families, that he is considered the rightful property of some one \
+        or other of their daughters. My dear Mr. Bennet, said his lady to him one day, have you heard \
+        that Netherfield Park is let at last? Mr. Bennet replied that he had not.";
+
+    const SPANISH_TEXT: &str = "En un lugar de la Mancha, de cuyo nombre no quiero acordarme, no ha \
+        mucho tiempo que vivia un hidalgo de los de lanza en astillero, adarga antigua, rocin flaco \
+        y galgo corredor. Una olla de algo mas vaca que carnero, salpicon las mas noches, duelos y \
+        quebrantos los sabados, lentejas los viernes, algun palomino de anadidura los domingos, \
+        consumian las tres partes de su hacienda. El resto della concluian sayo de velarte, calzas \
+        de velludo para las fiestas con sus pantuflos de lo mismo.";
+
+    #[test]
+    fn test_index_of_coincidence() {
+        let mut counts = [0; 26];
+        assert_eq!(index_of_coincidence(&counts), 0.0);
+        counts[0] = 10;
+        assert_eq!(index_of_coincidence(&counts), 1.0);
+        let flat = [4; 26];
+        assert!((index_of_coincidence(&flat) - 3.0 / 103.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_kasiski_counts_bytes_not_letters() {
+        // "THE" repeats 9 bytes later, spaces included / "THE" se repite 9 bytes después, con espacios
+        assert_eq!(kasiski_distances(b"THE CAT, THE"), vec![9]);
+        assert!(kasiski_distances(b"ab ab ab").is_empty());
+    }
+
+    #[test]
+    fn test_recovers_english_key() {
+        let ciphertext = encrypt(ENGLISH_TEXT, "LEMON");
+        let solution = crack_vigenere(&ciphertext, MAX_KEY_LEN).unwrap();
+        assert_eq!(solution.key_lengths[0].length, 5);
+        assert_eq!(solution.key, "LEMON");
+        assert_eq!(solution.language, Language::English);
+        assert_eq!(solution.plaintext, ENGLISH_TEXT);
+    }
+
+    #[test]
+    fn test_divisor_of_key_length_is_not_chosen() {
+        // Lengths 2 and 3 also collect the Kasiski votes of 4 and 6
+        // Las longitudes 2 y 3 también reciben los votos de Kasiski de 4 y 6
+        for key in ["RUST", "SECRET"] {
+            let solution = crack_vigenere(&encrypt(ENGLISH_TEXT, key), MAX_KEY_LEN).unwrap();
+            assert_eq!(solution.key, key);
+        }
+    }
+
+    #[test]
+    fn test_recovers_spanish_key() {
+        let ciphertext = encrypt(SPANISH_TEXT, "CLAVE");
+        let solution = crack_vigenere(&ciphertext, MAX_KEY_LEN).unwrap();
+        assert_eq!(solution.key, "CLAVE");
+        assert_eq!(solution.language, Language::Spanish);
+        assert_eq!(solution.plaintext, SPANISH_TEXT);
+    }
+
+    #[test]
+    fn test_short_text_prefers_short_key() {
+        let plain = "This is a secret message that should be broken in the process of testing the long text analysis";
+        let solution = crack_vigenere(&encrypt(plain, "E"), MAX_KEY_LEN).unwrap();
+        assert_eq!(solution.key, "E");
+        assert_eq!(solution.plaintext, plain);
+    }
+
+    #[test]
+    fn test_friedman_estimate_is_close() {
+        let ciphertext = encrypt(ENGLISH_TEXT, "KEY");
+        let counts = letter_counts(ciphertext.as_bytes());
+        let estimate = friedman_estimate(index_of_coincidence(&counts), Language::English);
+        assert!((1.5..6.0).contains(&estimate), "{}", estimate);
+    }
+
+    #[test]
+    fn test_rejects_text_without_letters() {
+        assert!(matches!(crack_vigenere("12 34", MAX_KEY_LEN), Err(CipherError::MalformedInput { .. })));
+    }
+}
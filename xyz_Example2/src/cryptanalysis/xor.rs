@@ -0,0 +1,232 @@
+// Repeating-Key XOR Breaker Using Normalized Hamming Distance
+// Romper XOR de Clave Repetida Usando Distancia de Hamming Normalizada
+
+use crate::encoding::{hex_to_bytes, Base64};
+use crate::error::CipherError;
+
+use super::frequency::Language;
+
+/// Longest key size tried by default / Tamaño de clave máximo probado por defecto
+pub const MAX_KEY_SIZE: usize = 40;
+
+// Key sizes with the lowest distance that get fully solved, with their divisors
+// Tamaños con menor distancia que se resuelven, junto con sus divisores
+const SIZES_TO_SOLVE: usize = 5;
+
+// Score each key byte costs, so a multiple of the key cannot win by overfitting the columns
+// Puntuación que cuesta cada byte de clave, para que un múltiplo no gane sobreajustando columnas
+const KEY_BYTE_PENALTY: f64 = 5.0;
+
+/// Evidence for one key size / Evidencia para un tamaño de clave
+#[derive(Clone, Debug)]
+pub struct KeySizeCandidate {
+    pub size: usize,
+    pub distance: f64, // Differing bits per byte between blocks / Bits distintos por byte entre bloques
+}
+
+/// Recovered key and plaintext / Clave y texto plano recuperados
+#[derive(Clone, Debug)]
+pub struct XorSolution {
+    pub key: Vec<u8>,
+    pub language: Language,
+    pub plaintext: Vec<u8>,
+    pub key_sizes: Vec<KeySizeCandidate>, // Lowest distance first / Menor distancia primero
+}
+
+/// Read ciphertext printed as hex (as the demos do) or as Base64
+/// Leer texto cifrado impreso en hex (como hacen las demos) o en Base64
+///
+/// Whitespace is ignored and Base64 may be URL-safe or unpadded. Text that is valid hex is read as
+/// hex even if it is also valid Base64.
+/// Se ignoran los espacios y el Base64 puede ser seguro para URL o sin relleno. El texto hex válido
+/// se lee como hex aunque también sea Base64 válido.
+pub fn parse_ciphertext(text: &str) -> Result<Vec<u8>, CipherError> {
+    let compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.is_empty() {
+        return Err(CipherError::MalformedInput {
+            position: 0,
+            reason: "empty ciphertext / texto cifrado vacío".to_string(),
+        });
+    }
+    hex_to_bytes(&compact).or_else(|_| Base64::STANDARD.decode_lenient(&compact))
+}
+
+/// Number of differing bits / Número de bits distintos
+pub fn hamming_distance(a: &[u8], b: &[u8]) -> u32 {
+    a.iter().zip(b).map(|(x, y)| (x ^ y).count_ones()).sum()
+}
+
+/// Rank key sizes 1..=`max_size` by the normalized Hamming distance between consecutive blocks
+/// Ordenar tamaños 1..=`max_size` por la distancia de Hamming normalizada entre bloques consecutivos
+///
+/// With the right size both blocks are XORed with the same key, so their distance is that of the
+/// plaintexts, which is small for text.
+/// Con el tamaño correcto ambos bloques comparten clave, así que su distancia es la de los textos
+/// planos, que es pequeña para texto.
+pub fn estimate_key_sizes(ciphertext: &[u8], max_size: usize) -> Vec<KeySizeCandidate> {
+    let mut candidates: Vec<KeySizeCandidate> = (1..=max_size.min(ciphertext.len() / 2).max(1))
+        .map(|size| {
+            let blocks: Vec<&[u8]> = ciphertext.chunks_exact(size).collect();
+            let pairs = blocks.len().saturating_sub(1).max(1);
+            let bits: u32 = blocks.windows(2).map(|w| hamming_distance(w[0], w[1])).sum();
+            KeySizeCandidate { size, distance: bits as f64 / (pairs * size) as f64 }
+        })
+        .collect();
+    candidates.sort_by(|a, b| a.distance.total_cmp(&b.distance).then(a.size.cmp(&b.size)));
+    candidates
+}
+
+// How much a byte looks like plaintext in `language`; higher is better
+// Cuánto parece un byte texto plano en `language`; mayor es mejor
+fn byte_score(b: u8, language: Language) -> f64 {
+    match b {
+        b' ' => 15.0,
+        b'a'..=b'z' => language.frequencies()[(b - b'a') as usize],
+        b'A'..=b'Z' => language.frequencies()[(b - b'A') as usize] / 2.0,
+        b'0'..=b'9' | b'\n' | b'.' | b',' | b'\'' | b'"' | b'!' | b'?' | b';' | b':' | b'-' => 1.0,
+        b'\t' | b'\r' | 0x21..=0x7e => 0.0,
+        // UTF-8 accents in Spanish text / Acentos UTF-8 en texto español
+        0x80..=0xff => -2.0,
+        _ => -20.0,
+    }
+}
+
+/// Plaintext score summed over the bytes / Puntuación de texto plano sumada sobre los bytes
+pub fn plaintext_score(text: &[u8], language: Language) -> f64 {
+    text.iter().map(|&b| byte_score(b, language)).sum()
+}
+
+// Best single-byte key for bytes that share a key byte / Mejor clave de un byte para una columna
+fn solve_column<'a>(column: impl Iterator<Item = &'a u8> + Clone, language: Language) -> u8 {
+    (0..=255u8)
+        .max_by(|&a, &b| {
+            let score = |k: u8| column.clone().map(|&c| byte_score(c ^ k, language)).sum::<f64>();
+            score(a).total_cmp(&score(b))
+        })
+        .expect("256 keys")
+}
+
+// Shortest period of the key, so a solve at twice the size gives the real key
+// Periodo más corto de la clave, para que resolver al doble del tamaño dé la clave real
+fn shortest_period(key: &[u8]) -> &[u8] {
+    let period = (1..key.len())
+        .find(|&p| key.len().is_multiple_of(p) && key.iter().enumerate().all(|(i, &k)| k == key[i % p]))
+        .unwrap_or(key.len());
+    &key[..period]
+}
+
+fn xor_with_key(data: &[u8], key: &[u8]) -> Vec<u8> {
+    data.iter().zip(key.iter().cycle()).map(|(d, k)| d ^ k).collect()
+}
+
+/// Recover a repeating XOR key of up to `max_size` bytes and the plaintext
+/// Recuperar una clave XOR repetida de hasta `max_size` bytes y el texto plano
+pub fn crack_repeating_xor(ciphertext: &[u8], max_size: usize) -> Result<XorSolution, CipherError> {
+    if ciphertext.len() < 2 {
+        return Err(CipherError::MalformedInput {
+            position: ciphertext.len(),
+            reason: "need at least two bytes / se necesitan al menos dos bytes".to_string(),
+        });
+    }
+    let key_sizes = estimate_key_sizes(ciphertext, max_size);
+
+    // The true size often hides behind its multiples on short texts, so solve their divisors too
+    // En textos cortos el tamaño real suele esconderse tras sus múltiplos, así que se prueban sus divisores
+    let mut sizes: Vec<usize> = key_sizes
+        .iter()
+        .take(SIZES_TO_SOLVE)
+        .flat_map(|c| (1..=c.size).filter(move |d| c.size.is_multiple_of(*d)))
+        .collect();
+    sizes.sort_unstable();
+    sizes.dedup();
+
+    // Transpose into columns, solve each as single-byte XOR, then keep the best-scoring plaintext
+    // Transponer en columnas, resolver cada una como XOR de un byte y quedarse con la mejor
+    let mut best: Option<(f64, Vec<u8>, Language)> = None;
+    for size in sizes {
+        for language in Language::ALL {
+            let key: Vec<u8> = (0..size)
+                .map(|offset| solve_column(ciphertext.iter().skip(offset).step_by(size), language))
+                .collect();
+            let key = shortest_period(&key).to_vec();
+            let score = plaintext_score(&xor_with_key(ciphertext, &key), language)
+                - KEY_BYTE_PENALTY * key.len() as f64;
+            if best.as_ref().is_none_or(|(best_score, _, _)| score > *best_score) {
+                best = Some((score, key, language));
+            }
+        }
+    }
+
+    let (_, key, language) = best.expect("at least one key size");
+    Ok(XorSolution {
+        plaintext: xor_with_key(ciphertext, &key),
+        key,
+        language,
+        key_sizes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classical::xor_encrypt;
+    use crate::encoding::{bytes_to_hex, simple_base64_encode};
+
+    const ENGLISH_TEXT: &str = "It is a truth universally acknowledged, that a single man in possession \
+        of a good fortune, must be in want of a wife. However little known the feelings or views of \
+        such a man may be on his first entering a neighbourhood, this truth is so well fixed in the \
+        minds of the surrounding families, that he is considered the rightful property of some one \
+        or other of their daughters.";
+
+    #[test]
+    fn test_hamming_distance() {
+        assert_eq!(hamming_distance(b"this is a test", b"wokka wokka!!!"), 37);
+    }
+
+    #[test]
+    fn test_parse_hex_and_base64() {
+        assert_eq!(parse_ciphertext("48 65\n6c").unwrap(), b"Hel");
+        assert_eq!(parse_ciphertext("SGVsbG8=").unwrap(), b"Hello");
+        assert_eq!(parse_ciphertext("SGVs\nbG8").unwrap(), b"Hello");
+        assert!(matches!(parse_ciphertext("  "), Err(CipherError::MalformedInput { .. })));
+        assert!(matches!(parse_ciphertext("not*valid"), Err(CipherError::MalformedInput { .. })));
+    }
+
+    #[test]
+    fn test_recovers_key_from_hex() {
+        let ciphertext = bytes_to_hex(&xor_encrypt(ENGLISH_TEXT.as_bytes(), b"MyKey").unwrap());
+        let solution = crack_repeating_xor(&parse_ciphertext(&ciphertext).unwrap(), MAX_KEY_SIZE).unwrap();
+        assert_eq!(solution.key, b"MyKey");
+        assert_eq!(solution.plaintext, ENGLISH_TEXT.as_bytes());
+    }
+
+    #[test]
+    fn test_recovers_binary_key_from_base64() {
+        let key = [0x00, 0x9f, 0x13, 0xfe, 0x42, 0x7a, 0xc1];
+        let ciphertext = simple_base64_encode(&xor_encrypt(ENGLISH_TEXT.as_bytes(), &key).unwrap()).unwrap();
+        let solution = crack_repeating_xor(&parse_ciphertext(&ciphertext).unwrap(), MAX_KEY_SIZE).unwrap();
+        assert_eq!(solution.key, key);
+        assert_eq!(solution.language, Language::English);
+    }
+
+    #[test]
+    fn test_recovers_spanish_text() {
+        let plain = "En un lugar de la Mancha, de cuyo nombre no quiero acordarme, no ha mucho tiempo \
+            que vivía un hidalgo de los de lanza en astillero, adarga antigua, rocín flaco y galgo corredor.";
+        let solution = crack_repeating_xor(&xor_encrypt(plain.as_bytes(), b"clave").unwrap(), MAX_KEY_SIZE).unwrap();
+        assert_eq!(solution.key, b"clave");
+        assert_eq!(solution.plaintext, plain.as_bytes());
+    }
+
+    #[test]
+    fn test_shortest_period() {
+        assert_eq!(shortest_period(b"keykeykey"), b"key");
+        assert_eq!(shortest_period(b"keyke"), b"keyke");
+        assert_eq!(shortest_period(b"a"), b"a");
+    }
+
+    #[test]
+    fn test_rejects_tiny_input() {
+        assert!(matches!(crack_repeating_xor(b"x", MAX_KEY_SIZE), Err(CipherError::MalformedInput { .. })));
+    }
+}
@@ -0,0 +1,197 @@
+// Ascii85 (btoa/Adobe) and Z85 (ZeroMQ RFC 32)
+// Ascii85 (btoa/Adobe) y Z85 (RFC 32 de ZeroMQ)
+
+use crate::error::CipherError;
+
+use super::Encoding;
+
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// Ascii85 with `!`..`u` digits and `z` for four zero bytes; decoding also accepts `<~ ~>` and whitespace
+/// Ascii85 con dígitos `!`..`u` y `z` para cuatro bytes cero; al decodificar acepta `<~ ~>` y espacios
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Ascii85;
+
+/// Z85: a string-safe alphabet; input must be whole 4-byte groups
+/// Z85: un alfabeto seguro para cadenas; la entrada debe ser de grupos completos de 4 bytes
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Z85;
+
+// Four bytes as five base-85 digits, most significant first / Cuatro bytes como cinco dígitos base 85
+fn to_digits(group: &[u8]) -> [u8; 5] {
+    let mut padded = [0u8; 4];
+    padded[..group.len()].copy_from_slice(group);
+    let mut value = u32::from_be_bytes(padded);
+    let mut digits = [0u8; 5];
+    for digit in digits.iter_mut().rev() {
+        *digit = (value % 85) as u8;
+        value /= 85;
+    }
+    digits
+}
+
+// Five base-85 digits back to four bytes; `None` if they overflow 32 bits
+// Cinco dígitos base 85 de vuelta a cuatro bytes; `None` si exceden 32 bits
+fn from_digits(digits: &[u8; 5]) -> Option<[u8; 4]> {
+    digits
+        .iter()
+        .try_fold(0u32, |acc, &d| acc.checked_mul(85)?.checked_add(d as u32))
+        .map(u32::to_be_bytes)
+}
+
+fn malformed(position: usize, reason: &str) -> CipherError {
+    CipherError::MalformedInput { position, reason: reason.to_string() }
+}
+
+impl Encoding for Ascii85 {
+    fn name(&self) -> &'static str {
+        "Ascii85"
+    }
+
+    fn encode(&self, data: &[u8]) -> Result<String, CipherError> {
+        let mut out = String::with_capacity(data.len().div_ceil(4) * 5);
+        for group in data.chunks(4) {
+            if group == [0, 0, 0, 0] {
+                out.push('z');
+                continue;
+            }
+            // A partial group of n bytes keeps n + 1 digits / Un grupo parcial de n bytes conserva n + 1 dígitos
+            let digits = to_digits(group);
+            out.extend(digits[..group.len() + 1].iter().map(|&d| (d + b'!') as char));
+        }
+        Ok(out)
+    }
+
+    fn decode(&self, text: &str) -> Result<Vec<u8>, CipherError> {
+        let mut input = text.as_bytes();
+        let mut offset = 0;
+        if let Some(rest) = input.strip_prefix(b"<~") {
+            input = rest;
+            offset = 2;
+        }
+        let trimmed = input.trim_ascii_end();
+        if let Some(rest) = trimmed.strip_suffix(b"~>") {
+            input = rest;
+        }
+
+        let mut out = Vec::with_capacity(input.len() / 5 * 4);
+        let mut digits = [0u8; 5];
+        let mut count = 0;
+        let mut last = 0;
+        for (i, &b) in input.iter().enumerate() {
+            let position = offset + i;
+            match b {
+                _ if b.is_ascii_whitespace() => continue,
+                b'z' if count == 0 => out.extend_from_slice(&[0; 4]),
+                b'z' => return Err(malformed(position, "'z' inside a group")),
+                b'!'..=b'u' => {
+                    digits[count] = b - b'!';
+                    count += 1;
+                    last = position;
+                    if count == 5 {
+                        let bytes = from_digits(&digits).ok_or_else(|| malformed(position, "group overflows 32 bits"))?;
+                        out.extend_from_slice(&bytes);
+                        count = 0;
+                    }
+                }
+                _ => return Err(malformed(position, &format!("invalid Ascii85 character '{}'", b as char))),
+            }
+        }
+
+        // Pad a short final group with the highest digit / Rellenar un grupo final corto con el dígito mayor
+        match count {
+            0 => {}
+            1 => return Err(malformed(last, "final group has a single digit")),
+            _ => {
+                digits[count..].fill(84);
+                let bytes = from_digits(&digits).ok_or_else(|| malformed(last, "group overflows 32 bits"))?;
+                out.extend_from_slice(&bytes[..count - 1]);
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl Encoding for Z85 {
+    fn name(&self) -> &'static str {
+        "Z85"
+    }
+
+    fn encode(&self, data: &[u8]) -> Result<String, CipherError> {
+        if !data.len().is_multiple_of(4) {
+            return Err(malformed(data.len(), "Z85 input length must be a multiple of 4"));
+        }
+        Ok(data
+            .chunks(4)
+            .flat_map(to_digits)
+            .map(|d| Z85_ALPHABET[d as usize] as char)
+            .collect())
+    }
+
+    fn decode(&self, text: &str) -> Result<Vec<u8>, CipherError> {
+        let input = text.as_bytes();
+        if !input.len().is_multiple_of(5) {
+            return Err(malformed(input.len(), "Z85 text length must be a multiple of 5"));
+        }
+        let mut out = Vec::with_capacity(input.len() / 5 * 4);
+        for (start, group) in (0..input.len()).step_by(5).zip(input.chunks(5)) {
+            let mut digits = [0u8; 5];
+            for (i, &b) in group.iter().enumerate() {
+                digits[i] = Z85_ALPHABET
+                    .iter()
+                    .position(|&a| a == b)
+                    .ok_or_else(|| malformed(start + i, &format!("invalid Z85 character '{}'", b as char)))?
+                    as u8;
+            }
+            let bytes = from_digits(&digits).ok_or_else(|| malformed(start + 4, "group overflows 32 bits"))?;
+            out.extend_from_slice(&bytes);
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii85_vectors() {
+        // Checked against Python's base64.a85encode / Verificado con base64.a85encode de Python
+        let cases: [(&[u8], &str); 5] = [
+            (b"Man ", "9jqo^"),
+            (b"sure.", "F*2M7/c"),
+            (&[0, 0, 0, 0, 1], "z!<"),
+            (b"Hello, World!", "87cURD_*#4DfTZ)+T"),
+            (b"", ""),
+        ];
+        for (data, encoded) in cases {
+            assert_eq!(Ascii85.encode(data).unwrap(), encoded);
+            assert_eq!(Ascii85.decode(encoded).unwrap(), data);
+        }
+        assert_eq!(Ascii85.decode("<~87cURD_*#4\nDfTZ)+T~>").unwrap(), b"Hello, World!");
+    }
+
+    #[test]
+    fn test_ascii85_rejects_malformed_input() {
+        let position = |text| match Ascii85.decode(text) {
+            Err(CipherError::MalformedInput { position, .. }) => position,
+            other => panic!("expected MalformedInput, got {:?}", other),
+        };
+        assert_eq!(position("9jqo^v"), 5);
+        assert_eq!(position("9jzo^"), 2);
+        assert_eq!(position("9jqo^F"), 5);
+        assert_eq!(position("s8W-\""), 4); // Above 2^32 - 1 / Mayor que 2^32 - 1
+    }
+
+    #[test]
+    fn test_z85_spec_vector() {
+        let data = [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+        assert_eq!(Z85.encode(&data).unwrap(), "HelloWorld");
+        assert_eq!(Z85.decode("HelloWorld").unwrap(), data);
+        assert!(Z85.encode(b"abc").is_err());
+        assert!(Z85.decode("Hello").is_ok());
+        assert!(matches!(Z85.decode("Hell~"), Err(CipherError::MalformedInput { position: 4, .. })));
+        assert!(Z85.decode("Hell").is_err());
+    }
+}
@@ -0,0 +1,156 @@
+// RFC 4648 Base32 and Base32hex
+// Base32 y Base32hex según RFC 4648
+
+use crate::error::CipherError;
+
+use super::Encoding;
+
+const STANDARD_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const HEX_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+
+// Symbols used by a final group of 1-5 bytes; padding fills the rest of the 8
+// Símbolos que usa un grupo final de 1-5 bytes; el relleno completa hasta 8
+const SYMBOLS_FOR_BYTES: [usize; 6] = [0, 2, 4, 5, 7, 8];
+
+/// Base32 with the RFC 4648 §6 or the sortable §7 "extended hex" alphabet
+/// Base32 con el alfabeto de RFC 4648 §6 o el "hex extendido" ordenable de §7
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Base32 {
+    alphabet: &'static [u8; 32],
+    padding: bool,
+}
+
+impl Base32 {
+    pub const STANDARD: Base32 = Base32 { alphabet: STANDARD_ALPHABET, padding: true };
+    /// Preserves the sort order of the data / Conserva el orden de los datos
+    pub const HEX: Base32 = Base32 { alphabet: HEX_ALPHABET, padding: true };
+
+    pub const fn with_padding(self, padding: bool) -> Self {
+        Self { padding, ..self }
+    }
+}
+
+impl Encoding for Base32 {
+    fn name(&self) -> &'static str {
+        match (self.alphabet == HEX_ALPHABET, self.padding) {
+            (false, true) => "Base32",
+            (false, false) => "Base32 (unpadded)",
+            (true, true) => "Base32hex",
+            (true, false) => "Base32hex (unpadded)",
+        }
+    }
+
+    fn encode(&self, data: &[u8]) -> Result<String, CipherError> {
+        let mut out = String::with_capacity(data.len().div_ceil(5) * 8);
+        for chunk in data.chunks(5) {
+            let mut group = [0u8; 8];
+            group[..chunk.len()].copy_from_slice(chunk);
+            let bits = u64::from_be_bytes(group) >> 24;
+            let symbols = SYMBOLS_FOR_BYTES[chunk.len()];
+            for i in 0..symbols {
+                out.push(self.alphabet[(bits >> (35 - 5 * i) & 31) as usize] as char);
+            }
+            if self.padding {
+                out.extend(std::iter::repeat_n('=', 8 - symbols));
+            }
+        }
+        Ok(out)
+    }
+
+    /// Letters may be either case; padding must match this configuration and leftover bits be zero
+    /// Las letras pueden ir en cualquier caso; el relleno debe coincidir con la configuración y los
+    /// bits sobrantes ser cero
+    fn decode(&self, text: &str) -> Result<Vec<u8>, CipherError> {
+        let malformed = |position: usize, reason: &str| CipherError::MalformedInput {
+            position,
+            reason: reason.to_string(),
+        };
+        let input = text.as_bytes();
+        let data_len = input.iter().position(|&b| b == b'=').unwrap_or(input.len());
+        let padding = input.len() - data_len;
+        if let Some(offset) = input[data_len..].iter().position(|&b| b != b'=') {
+            return Err(malformed(data_len + offset, "data after padding"));
+        }
+        if !self.padding && padding > 0 {
+            return Err(malformed(data_len, "padding is not allowed"));
+        }
+        if self.padding && !input.len().is_multiple_of(8) {
+            return Err(malformed(input.len(), "length is not a multiple of 8"));
+        }
+
+        // Size of the final group decides how many bytes it carries / El tamaño del grupo final decide sus bytes
+        let tail = data_len % 8;
+        let tail_bytes = match SYMBOLS_FOR_BYTES.iter().position(|&s| s == tail) {
+            Some(bytes) => bytes,
+            None => return Err(malformed(data_len, "impossible final group length")),
+        };
+        if self.padding && tail > 0 && padding != 8 - tail {
+            return Err(malformed(data_len, "wrong amount of padding"));
+        }
+
+        let mut out = Vec::with_capacity(data_len * 5 / 8);
+        for (start, group) in (0..data_len).step_by(8).zip(input[..data_len].chunks(8)) {
+            let mut bits = 0u64;
+            for (i, &b) in group.iter().enumerate() {
+                let value = self.alphabet
+                    .iter()
+                    .position(|&a| a == b.to_ascii_uppercase())
+                    .ok_or_else(|| malformed(start + i, &format!("invalid character '{}'", b as char)))?;
+                bits |= (value as u64) << (35 - 5 * i);
+            }
+            let bytes = if group.len() == 8 { 5 } else { tail_bytes };
+            // RFC 4648 §3.5: leftover bits must be zero / los bits sobrantes deben ser cero
+            if bits & (0xff_ffff_ffff >> (8 * bytes)) != 0 {
+                return Err(malformed(start + group.len() - 1, "non-zero trailing bits"));
+            }
+            out.extend_from_slice(&bits.to_be_bytes()[3..3 + bytes]);
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 4648 §10 / RFC 4648 §10
+    const VECTORS: [(&str, &str, &str); 7] = [
+        ("", "", ""),
+        ("f", "MY======", "CO======"),
+        ("fo", "MZXQ====", "CPNG===="),
+        ("foo", "MZXW6===", "CPNMU==="),
+        ("foob", "MZXW6YQ=", "CPNMUOG="),
+        ("fooba", "MZXW6YTB", "CPNMUOJ1"),
+        ("foobar", "MZXW6YTBOI======", "CPNMUOJ1E8======"),
+    ];
+
+    #[test]
+    fn test_rfc4648_vectors() {
+        for (plain, standard, hex) in VECTORS {
+            assert_eq!(Base32::STANDARD.encode(plain.as_bytes()).unwrap(), standard);
+            assert_eq!(Base32::STANDARD.decode(standard).unwrap(), plain.as_bytes());
+            assert_eq!(Base32::HEX.encode(plain.as_bytes()).unwrap(), hex);
+            assert_eq!(Base32::HEX.decode(hex).unwrap(), plain.as_bytes());
+
+            let unpadded = Base32::STANDARD.with_padding(false);
+            assert_eq!(unpadded.encode(plain.as_bytes()).unwrap(), standard.trim_end_matches('='));
+            assert_eq!(unpadded.decode(standard.trim_end_matches('=')).unwrap(), plain.as_bytes());
+        }
+        assert_eq!(Base32::STANDARD.decode("mzxw6ytb").unwrap(), b"fooba");
+    }
+
+    #[test]
+    fn test_rejects_malformed_input() {
+        let position = |text: &str| match Base32::STANDARD.decode(text) {
+            Err(CipherError::MalformedInput { position, .. }) => position,
+            other => panic!("expected MalformedInput, got {:?}", other),
+        };
+        assert_eq!(position("MZXW6YT1"), 7); // '1' is not in the alphabet / '1' no está en el alfabeto
+        assert_eq!(position("MZXW6Y=="), 6); // No group has 6 symbols / Ningún grupo tiene 6 símbolos
+        assert_eq!(position("MZ=W6==="), 3);
+        assert_eq!(position("MZXW6"), 5);
+        assert_eq!(position("MZ======"), 1); // 'Z' leaves bits set / 'Z' deja bits activos
+        assert_eq!(position("MZX====="), 3);
+        assert!(Base32::STANDARD.with_padding(false).decode("MY======").is_err());
+    }
+}
@@ -0,0 +1,153 @@
+// Base58 (Bitcoin Alphabet) and Base58Check
+// Base58 (Alfabeto de Bitcoin) y Base58Check
+
+use crate::error::CipherError;
+use crate::hash::{Digest, Sha256};
+
+use super::Encoding;
+
+// No 0, O, I or l, which are easy to confuse / Sin 0, O, I ni l, que se confunden fácilmente
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Bytes of checksum appended by Base58Check / Bytes de suma de verificación que añade Base58Check
+pub const CHECKSUM_LEN: usize = 4;
+
+/// Plain Base58; each leading zero byte becomes a leading `1`
+/// Base58 simple; cada byte cero inicial se convierte en un `1` inicial
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Base58;
+
+/// Base58 with a 4-byte double SHA-256 checksum, as in Bitcoin addresses
+/// Base58 con una suma de verificación de 4 bytes de doble SHA-256, como en direcciones Bitcoin
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Base58Check;
+
+fn checksum(data: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let hash = Sha256::digest(&Sha256::digest(data));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+impl Encoding for Base58 {
+    fn name(&self) -> &'static str {
+        "Base58"
+    }
+
+    fn encode(&self, data: &[u8]) -> Result<String, CipherError> {
+        let zeros = data.iter().take_while(|&&b| b == 0).count();
+
+        // Repeated division of a big-endian number, kept as little-endian base-58 digits
+        // División repetida de un número big-endian, guardado como dígitos base 58 little-endian
+        let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+        for &byte in &data[zeros..] {
+            let mut carry = byte as u32;
+            for digit in digits.iter_mut() {
+                carry += (*digit as u32) << 8;
+                *digit = (carry % 58) as u8;
+                carry /= 58;
+            }
+            while carry > 0 {
+                digits.push((carry % 58) as u8);
+                carry /= 58;
+            }
+        }
+
+        Ok(std::iter::repeat_n('1', zeros)
+            .chain(digits.iter().rev().map(|&d| ALPHABET[d as usize] as char))
+            .collect())
+    }
+
+    fn decode(&self, text: &str) -> Result<Vec<u8>, CipherError> {
+        let input = text.as_bytes();
+        let zeros = input.iter().take_while(|&&b| b == b'1').count();
+
+        let mut bytes: Vec<u8> = Vec::with_capacity(input.len() * 733 / 1000 + 1);
+        for (position, &symbol) in input.iter().enumerate().skip(zeros) {
+            let value = ALPHABET.iter().position(|&a| a == symbol).ok_or_else(|| {
+                CipherError::MalformedInput {
+                    position,
+                    reason: format!("invalid Base58 character '{}'", symbol as char),
+                }
+            })?;
+            let mut carry = value as u32;
+            for byte in bytes.iter_mut() {
+                carry += (*byte as u32) * 58;
+                *byte = carry as u8;
+                carry >>= 8;
+            }
+            while carry > 0 {
+                bytes.push(carry as u8);
+                carry >>= 8;
+            }
+        }
+
+        let mut out = vec![0u8; zeros];
+        out.extend(bytes.iter().rev());
+        Ok(out)
+    }
+}
+
+impl Encoding for Base58Check {
+    fn name(&self) -> &'static str {
+        "Base58Check"
+    }
+
+    fn encode(&self, data: &[u8]) -> Result<String, CipherError> {
+        Base58.encode(&[data, &checksum(data)].concat())
+    }
+
+    fn decode(&self, text: &str) -> Result<Vec<u8>, CipherError> {
+        let mut data = Base58.decode(text)?;
+        if data.len() < CHECKSUM_LEN {
+            return Err(CipherError::MalformedInput {
+                position: text.len(),
+                reason: "too short for a checksum".to_string(),
+            });
+        }
+        let expected = data.split_off(data.len() - CHECKSUM_LEN);
+        if checksum(&data) != expected[..] {
+            return Err(CipherError::MalformedInput {
+                position: text.len(),
+                reason: "checksum mismatch".to_string(),
+            });
+        }
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex_to_bytes;
+
+    #[test]
+    fn test_base58_vectors() {
+        // From the draft-msporny-base58 specification / De la especificación draft-msporny-base58
+        let cases: [(&[u8], &str); 4] = [
+            (b"Hello World!", "2NEpo7TZRRrLZSi2U"),
+            (b"The quick brown fox jumps over the lazy dog.", "USm3fpXnKG5EUBx2ndxBDMPVciP5hGey2Jh4NDv6gmeo1LkMeiKrLJUUBk6Z"),
+            (&[0x00, 0x00, 0x28, 0x7f, 0xb4, 0xcd], "11233QC4"),
+            (b"", ""),
+        ];
+        for (data, encoded) in cases {
+            assert_eq!(Base58.encode(data).unwrap(), encoded);
+            assert_eq!(Base58.decode(encoded).unwrap(), data);
+        }
+        assert!(matches!(Base58.decode("2NEpO7"), Err(CipherError::MalformedInput { position: 4, .. })));
+    }
+
+    #[test]
+    fn test_base58check_bitcoin_address() {
+        // Version byte 0 followed by a HASH160 / Byte de versión 0 seguido de un HASH160
+        let payload = hex_to_bytes("00f54a5851e9372b87810a8e60cdd2e7cfd80b6e31").unwrap();
+        let address = "1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAs";
+        assert_eq!(Base58Check.encode(&payload).unwrap(), address);
+        assert_eq!(Base58Check.decode(address).unwrap(), payload);
+
+        // One changed character breaks the checksum / Un carácter cambiado rompe la suma
+        assert!(matches!(
+            Base58Check.decode("1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAt"),
+            Err(CipherError::MalformedInput { .. })
+        ));
+        assert!(Base58Check.decode("1").is_err());
+    }
+}
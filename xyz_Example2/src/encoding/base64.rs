@@ -0,0 +1,541 @@
+// RFC 4648 Base64: Standard and URL-Safe Alphabets, Strict and Lenient Decoding
+// Base64 RFC 4648: Alfabetos Estándar y Seguro para URL, Decodificación Estricta y Flexible
+
+use std::io::{self, Read, Write};
+
+use crate::error::CipherError;
+
+use super::Encoding;
+
+const STANDARD_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// Marks bytes outside the alphabet in decode tables / Marca bytes fuera del alfabeto en las tablas
+const INVALID: u8 = 0xff;
+
+// Built once at compile time / Construidas una vez en tiempo de compilación
+const STANDARD_TABLE: [u8; 256] = decode_table(&[STANDARD_ALPHABET]);
+const URL_SAFE_TABLE: [u8; 256] = decode_table(&[URL_SAFE_ALPHABET]);
+const EITHER_TABLE: [u8; 256] = decode_table(&[STANDARD_ALPHABET, URL_SAFE_ALPHABET]);
+
+const fn decode_table(alphabets: &[&[u8; 64]]) -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    let mut a = 0;
+    while a < alphabets.len() {
+        let mut i = 0;
+        while i < 64 {
+            table[alphabets[a][i] as usize] = i as u8;
+            i += 1;
+        }
+        a += 1;
+    }
+    table
+}
+
+/// Which 64 symbols are used / Qué 64 símbolos se usan
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alphabet {
+    Standard, // `+` and `/` (RFC 4648 §4) / `+` y `/` (RFC 4648 §4)
+    UrlSafe,  // `-` and `_` (RFC 4648 §5) / `-` y `_` (RFC 4648 §5)
+}
+
+impl Alphabet {
+    fn symbols(self) -> &'static [u8; 64] {
+        match self {
+            Alphabet::Standard => STANDARD_ALPHABET,
+            Alphabet::UrlSafe => URL_SAFE_ALPHABET,
+        }
+    }
+
+    fn table(self) -> &'static [u8; 256] {
+        match self {
+            Alphabet::Standard => &STANDARD_TABLE,
+            Alphabet::UrlSafe => &URL_SAFE_TABLE,
+        }
+    }
+}
+
+/// How forgiving decoding is / Qué tan permisiva es la decodificación
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// Only the canonical encoding this configuration would produce
+    /// Solo la codificación canónica que produciría esta configuración
+    Strict,
+    /// Skips whitespace, accepts either alphabet, optional padding and stray trailing bits
+    /// Omite espacios, acepta cualquier alfabeto, relleno opcional y bits finales sobrantes
+    Lenient,
+}
+
+/// A Base64 configuration: alphabet, padding and optional line wrapping
+/// Una configuración Base64: alfabeto, relleno y salto de línea opcional
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Base64 {
+    alphabet: Alphabet,
+    padding: bool,
+    line_len: Option<usize>, // Wrap with CRLF after this many symbols / Saltar con CRLF tras estos símbolos
+}
+
+impl Base64 {
+    /// RFC 4648 §4 with `=` padding / RFC 4648 §4 con relleno `=`
+    pub const STANDARD: Base64 = Base64 { alphabet: Alphabet::Standard, padding: true, line_len: None };
+    pub const STANDARD_NO_PAD: Base64 = Base64 { padding: false, ..Self::STANDARD };
+    /// RFC 4648 §5, safe in URLs and file names / RFC 4648 §5, seguro en URLs y nombres de archivo
+    pub const URL_SAFE: Base64 = Base64 { alphabet: Alphabet::UrlSafe, padding: true, line_len: None };
+    pub const URL_SAFE_NO_PAD: Base64 = Base64 { padding: false, ..Self::URL_SAFE };
+    /// RFC 2045 MIME: 76-column lines ending in CRLF / MIME RFC 2045: líneas de 76 columnas con CRLF
+    pub const MIME: Base64 = Base64 { line_len: Some(76), ..Self::STANDARD };
+
+    pub const fn with_padding(self, padding: bool) -> Self {
+        Self { padding, ..self }
+    }
+
+    /// Wrap output every `line_len` symbols (`None` for one line); zero is treated as `None`
+    /// Saltar la salida cada `line_len` símbolos (`None` para una línea); cero equivale a `None`
+    pub const fn with_line_len(self, line_len: Option<usize>) -> Self {
+        let line_len = match line_len {
+            Some(0) => None,
+            other => other,
+        };
+        Self { line_len, ..self }
+    }
+
+    pub fn alphabet(&self) -> Alphabet {
+        self.alphabet
+    }
+
+    /// Encode into a string / Codificar en una cadena
+    pub fn encode(&self, data: &[u8]) -> String {
+        let mut out = Vec::with_capacity(data.len().div_ceil(3) * 4);
+        let mut column = 0;
+        for chunk in data.chunks(3) {
+            self.encode_group(chunk, &mut column, &mut out);
+        }
+        String::from_utf8(out).expect("Base64 output is ASCII")
+    }
+
+    /// Strict decode; see `DecodeMode::Strict` / Decodificación estricta; ver `DecodeMode::Strict`
+    pub fn decode(&self, text: &str) -> Result<Vec<u8>, CipherError> {
+        self.decode_with(text, DecodeMode::Strict)
+    }
+
+    /// Lenient decode; see `DecodeMode::Lenient` / Decodificación flexible; ver `DecodeMode::Lenient`
+    pub fn decode_lenient(&self, text: &str) -> Result<Vec<u8>, CipherError> {
+        self.decode_with(text, DecodeMode::Lenient)
+    }
+
+    pub fn decode_with(&self, text: &str, mode: DecodeMode) -> Result<Vec<u8>, CipherError> {
+        let mut state = DecodeState::new(*self, mode);
+        let mut out = Vec::with_capacity(text.len() / 4 * 3);
+        state.feed(text.as_bytes(), &mut out)?;
+        state.finish(&mut out)?;
+        Ok(out)
+    }
+
+    /// Streaming encoder over a writer / Codificador en flujo sobre un escritor
+    pub fn encoder<W: Write>(&self, writer: W) -> Base64Encoder<W> {
+        Base64Encoder { writer, config: *self, pending: Vec::with_capacity(3), column: 0 }
+    }
+
+    /// Streaming decoder over a reader / Decodificador en flujo sobre un lector
+    pub fn decoder<R: Read>(&self, reader: R, mode: DecodeMode) -> Base64Decoder<R> {
+        Base64Decoder {
+            reader,
+            state: DecodeState::new(*self, mode),
+            decoded: Vec::new(),
+            offset: 0,
+            done: false,
+        }
+    }
+
+    // Encode up to three bytes, wrapping lines as needed / Codificar hasta tres bytes, saltando líneas
+    fn encode_group(&self, chunk: &[u8], column: &mut usize, out: &mut Vec<u8>) {
+        let symbols = self.alphabet.symbols();
+        let b = (chunk[0] as u32) << 16
+            | (chunk.get(1).copied().unwrap_or(0) as u32) << 8
+            | chunk.get(2).copied().unwrap_or(0) as u32;
+        let count = chunk.len() + 1;
+        let padding = if self.padding { 4 - count } else { 0 };
+        let group = (0..count)
+            .map(|i| symbols[(b >> (18 - 6 * i) & 63) as usize])
+            .chain(std::iter::repeat_n(b'=', padding));
+        for symbol in group {
+            if self.line_len == Some(*column) {
+                out.extend_from_slice(b"\r\n");
+                *column = 0;
+            }
+            out.push(symbol);
+            *column += 1;
+        }
+    }
+}
+
+impl Encoding for Base64 {
+    fn name(&self) -> &'static str {
+        match (self.alphabet, self.padding, self.line_len.is_some()) {
+            (Alphabet::Standard, true, false) => "Base64",
+            (Alphabet::Standard, false, false) => "Base64 (unpadded)",
+            (Alphabet::Standard, _, true) => "Base64 (MIME)",
+            (Alphabet::UrlSafe, false, false) => "Base64url",
+            (Alphabet::UrlSafe, true, false) => "Base64url (padded)",
+            (Alphabet::UrlSafe, _, true) => "Base64url (wrapped)",
+        }
+    }
+
+    fn encode(&self, data: &[u8]) -> Result<String, CipherError> {
+        Ok(Base64::encode(self, data))
+    }
+
+    fn decode(&self, text: &str) -> Result<Vec<u8>, CipherError> {
+        Base64::decode(self, text)
+    }
+}
+
+// === INCREMENTAL DECODER / DECODIFICADOR INCREMENTAL ===
+
+// Shared by one-shot and streaming decoding, so errors carry absolute positions
+// Compartido por la decodificación única y en flujo, para que los errores lleven posiciones absolutas
+struct DecodeState {
+    config: Base64,
+    mode: DecodeMode,
+    group: [u8; 4],
+    symbols: usize,  // Symbols in the current group / Símbolos en el grupo actual
+    padding: usize,  // `=` seen in the current group / `=` vistos en el grupo actual
+    closed: bool,    // A padded group ended the data / Un grupo con relleno terminó los datos
+    position: usize, // Bytes consumed so far / Bytes consumidos hasta ahora
+    column: usize,   // Symbols on the current line / Símbolos en la línea actual
+    short_line: bool, // A line ended before `line_len` / Una línea terminó antes de `line_len`
+    last_symbol: usize,
+}
+
+impl DecodeState {
+    fn new(config: Base64, mode: DecodeMode) -> Self {
+        Self {
+            config,
+            mode,
+            group: [0; 4],
+            symbols: 0,
+            padding: 0,
+            closed: false,
+            position: 0,
+            column: 0,
+            short_line: false,
+            last_symbol: 0,
+        }
+    }
+
+    fn error(&self, position: usize, reason: String) -> CipherError {
+        CipherError::MalformedInput { position, reason }
+    }
+
+    fn feed(&mut self, input: &[u8], out: &mut Vec<u8>) -> Result<(), CipherError> {
+        for &byte in input {
+            let position = self.position;
+            self.position += 1;
+            match (byte, self.mode) {
+                (b' ' | b'\t' | b'\r' | b'\n', DecodeMode::Lenient) => {}
+                // Strict input may only break lines where this configuration would
+                // La entrada estricta solo puede saltar líneas donde lo haría esta configuración
+                (b'\r', DecodeMode::Strict) if self.config.line_len.is_some() => {}
+                (b'\n', DecodeMode::Strict) if self.config.line_len.is_some() => {
+                    if self.column != 0 && Some(self.column) != self.config.line_len {
+                        self.short_line = true;
+                    }
+                    self.column = 0;
+                }
+                (b'=', _) => self.push_padding(position, out)?,
+                _ => self.push_symbol(byte, position, out)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn check_line(&mut self, position: usize) -> Result<(), CipherError> {
+        if self.mode == DecodeMode::Strict {
+            if let Some(line_len) = self.config.line_len {
+                if self.short_line || self.column == line_len {
+                    return Err(self.error(position, format!("line is not {} characters long", line_len)));
+                }
+            }
+        }
+        self.column += 1;
+        Ok(())
+    }
+
+    fn push_symbol(&mut self, byte: u8, position: usize, out: &mut Vec<u8>) -> Result<(), CipherError> {
+        let table = match self.mode {
+            DecodeMode::Strict => self.config.alphabet.table(),
+            DecodeMode::Lenient => &EITHER_TABLE,
+        };
+        let value = table[byte as usize];
+        if value == INVALID {
+            let shown = if byte.is_ascii_graphic() {
+                format!("'{}'", byte as char)
+            } else {
+                format!("byte 0x{:02x}", byte)
+            };
+            return Err(self.error(position, format!("invalid character {}", shown)));
+        }
+        if self.closed || self.padding > 0 {
+            return Err(self.error(position, "data after padding".to_string()));
+        }
+        self.check_line(position)?;
+        self.group[self.symbols] = value;
+        self.symbols += 1;
+        self.last_symbol = position;
+        if self.symbols == 4 {
+            self.emit(out)?;
+        }
+        Ok(())
+    }
+
+    fn push_padding(&mut self, position: usize, out: &mut Vec<u8>) -> Result<(), CipherError> {
+        if self.mode == DecodeMode::Strict && !self.config.padding {
+            return Err(self.error(position, "padding is not allowed".to_string()));
+        }
+        if self.closed || self.symbols < 2 {
+            return Err(self.error(position, "misplaced padding".to_string()));
+        }
+        self.check_line(position)?;
+        self.padding += 1;
+        if self.symbols + self.padding == 4 {
+            self.emit(out)?;
+            self.closed = true;
+        }
+        Ok(())
+    }
+
+    // Output the bytes of a complete or final group / Emitir los bytes de un grupo completo o final
+    fn emit(&mut self, out: &mut Vec<u8>) -> Result<(), CipherError> {
+        let g = &self.group;
+        let bits = (g[0] as u32) << 18 | (g[1] as u32) << 12 | (g[2] as u32) << 6 | g[3] as u32;
+        let bytes = self.symbols - 1;
+        // RFC 4648 §3.5: leftover bits must be zero in canonical input
+        // RFC 4648 §3.5: los bits sobrantes deben ser cero en la entrada canónica
+        if self.mode == DecodeMode::Strict && bits & (0xff_ffff >> (8 * bytes)) != 0 {
+            return Err(self.error(self.last_symbol, "non-zero trailing bits".to_string()));
+        }
+        out.extend_from_slice(&bits.to_be_bytes()[1..1 + bytes]);
+        self.group = [0; 4];
+        self.symbols = 0;
+        self.padding = 0;
+        Ok(())
+    }
+
+    fn finish(&mut self, out: &mut Vec<u8>) -> Result<(), CipherError> {
+        if self.padding > 0 {
+            return Err(self.error(self.position, "incomplete padding".to_string()));
+        }
+        match self.symbols {
+            0 => Ok(()),
+            1 => Err(self.error(self.position, "truncated final group".to_string())),
+            _ if self.mode == DecodeMode::Strict && self.config.padding => {
+                Err(self.error(self.position, "missing padding".to_string()))
+            }
+            _ => self.emit(out),
+        }
+    }
+}
+
+// === STREAMING ENCODER / CODIFICADOR EN FLUJO ===
+
+/// Encodes everything written to it; call `finish` to write the final group
+/// Codifica todo lo que se le escribe; llamar a `finish` para escribir el grupo final
+pub struct Base64Encoder<W: Write> {
+    writer: W,
+    config: Base64,
+    pending: Vec<u8>, // Fewer than three bytes waiting / Menos de tres bytes en espera
+    column: usize,
+}
+
+impl<W: Write> Base64Encoder<W> {
+    /// Write the final group and return the writer / Escribir el grupo final y devolver el escritor
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut out = Vec::with_capacity(6);
+        if !self.pending.is_empty() {
+            self.config.encode_group(&self.pending, &mut self.column, &mut out);
+        }
+        self.writer.write_all(&out)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> Write for Base64Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut input = buf;
+        let mut out = Vec::with_capacity(buf.len() / 3 * 4 + 8);
+        if !self.pending.is_empty() {
+            let take = (3 - self.pending.len()).min(input.len());
+            self.pending.extend_from_slice(&input[..take]);
+            input = &input[take..];
+            if self.pending.len() < 3 {
+                return Ok(buf.len());
+            }
+            self.config.encode_group(&self.pending, &mut self.column, &mut out);
+            self.pending.clear();
+        }
+        let whole = input.len() / 3 * 3;
+        for chunk in input[..whole].chunks(3) {
+            self.config.encode_group(chunk, &mut self.column, &mut out);
+        }
+        self.pending.extend_from_slice(&input[whole..]);
+        self.writer.write_all(&out)?;
+        Ok(buf.len())
+    }
+
+    /// Flushes the inner writer; up to two bytes wait for `finish`
+    /// Vacía el escritor interno; hasta dos bytes esperan a `finish`
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+// === STREAMING DECODER / DECODIFICADOR EN FLUJO ===
+
+/// Decodes Base64 text read from the inner reader / Decodifica el texto Base64 leído del lector interno
+pub struct Base64Decoder<R: Read> {
+    reader: R,
+    state: DecodeState,
+    decoded: Vec<u8>,
+    offset: usize,
+    done: bool,
+}
+
+impl<R: Read> Read for Base64Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut text = [0u8; 4096];
+        while self.offset == self.decoded.len() && !self.done {
+            self.decoded.clear();
+            self.offset = 0;
+            let n = self.reader.read(&mut text)?;
+            if n == 0 {
+                self.state.finish(&mut self.decoded)?;
+                self.done = true;
+            } else {
+                self.state.feed(&text[..n], &mut self.decoded)?;
+            }
+        }
+        let n = (self.decoded.len() - self.offset).min(buf.len());
+        buf[..n].copy_from_slice(&self.decoded[self.offset..self.offset + n]);
+        self.offset += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 4648 §10 / RFC 4648 §10
+    const VECTORS: [(&str, &str); 7] = [
+        ("", ""),
+        ("f", "Zg=="),
+        ("fo", "Zm8="),
+        ("foo", "Zm9v"),
+        ("foob", "Zm9vYg=="),
+        ("fooba", "Zm9vYmE="),
+        ("foobar", "Zm9vYmFy"),
+    ];
+
+    fn position(result: Result<Vec<u8>, CipherError>) -> usize {
+        match result {
+            Err(CipherError::MalformedInput { position, .. }) => position,
+            other => panic!("expected MalformedInput, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rfc4648_vectors() {
+        for (plain, encoded) in VECTORS {
+            assert_eq!(Base64::STANDARD.encode(plain.as_bytes()), encoded);
+            assert_eq!(Base64::STANDARD.decode(encoded).unwrap(), plain.as_bytes());
+
+            let unpadded = encoded.trim_end_matches('=');
+            assert_eq!(Base64::STANDARD_NO_PAD.encode(plain.as_bytes()), unpadded);
+            assert_eq!(Base64::STANDARD_NO_PAD.decode(unpadded).unwrap(), plain.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_url_safe_alphabet() {
+        let data = [0xfb, 0xff, 0xbf];
+        assert_eq!(Base64::STANDARD.encode(&data), "+/+/");
+        assert_eq!(Base64::URL_SAFE.encode(&data), "-_-_");
+        assert_eq!(Base64::URL_SAFE_NO_PAD.encode(&[0xfb]), "-w");
+        assert_eq!(position(Base64::URL_SAFE.decode("+/+/")), 0);
+        assert_eq!(Base64::URL_SAFE.decode_lenient("+/-_").unwrap(), [0xfb, 0xff, 0xbf]);
+    }
+
+    #[test]
+    fn test_strict_errors_have_positions() {
+        assert_eq!(position(Base64::STANDARD.decode("Zm9v*mFy")), 4);
+        assert_eq!(position(Base64::STANDARD.decode("Zm9vYg")), 6); // Missing padding / Falta relleno
+        assert_eq!(position(Base64::STANDARD.decode("Zm=vYg==")), 3); // Data after padding / Datos tras relleno
+        assert_eq!(position(Base64::STANDARD.decode("Z===")), 1); // Misplaced padding / Relleno mal puesto
+        assert_eq!(position(Base64::STANDARD.decode("Zg==Zg==")), 4);
+        assert_eq!(position(Base64::STANDARD.decode("Zh==")), 1); // Non-zero trailing bits / Bits finales
+        assert_eq!(position(Base64::STANDARD.decode("Zm9 v")), 3);
+        assert_eq!(position(Base64::STANDARD.decode("Zg=")), 3);
+        assert_eq!(position(Base64::STANDARD.decode("Zm9vY")), 5);
+        assert_eq!(position(Base64::STANDARD_NO_PAD.decode("Zg==")), 2);
+        assert_eq!(position(Base64::STANDARD.decode("Zm\u{e9}v")), 2);
+    }
+
+    #[test]
+    fn test_lenient_decoding() {
+        let lenient = |text| Base64::STANDARD.decode_lenient(text).unwrap();
+        assert_eq!(lenient(" Zm9v\r\nYmFy \t"), b"foobar");
+        assert_eq!(lenient("Zm9vYg"), b"foob");
+        assert_eq!(lenient("Zh=="), b"f");
+        // Still rejects garbage rather than mapping it to zero / Sigue rechazando basura en vez de mapearla a cero
+        assert_eq!(position(Base64::STANDARD.decode_lenient("Zm9v!")), 4);
+    }
+
+    #[test]
+    fn test_mime_wrapping() {
+        let data: Vec<u8> = (0..=255).collect();
+        let encoded = Base64::MIME.encode(&data);
+        let lines: Vec<&str> = encoded.split("\r\n").collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[..4].iter().all(|line| line.len() == 76));
+        assert_eq!(lines.concat(), Base64::STANDARD.encode(&data));
+        assert_eq!(Base64::MIME.encode(&data[..57]).len(), 76); // Exactly one line, no break / Una línea
+        assert_eq!(Base64::MIME.decode(&encoded).unwrap(), data);
+        assert_eq!(Base64::MIME.decode(&encoded.replace("\r\n", "\n")).unwrap(), data);
+
+        // A short line in the middle is not canonical / Una línea corta en medio no es canónica
+        let broken = format!("{}\r\n{}", &encoded[..8], &encoded[8..]);
+        assert!(Base64::MIME.decode(&broken).is_err());
+        assert_eq!(Base64::MIME.decode_lenient(&broken).unwrap(), data);
+        assert!(Base64::STANDARD.decode(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i * 7 + i / 13) as u8).collect();
+        for config in [Base64::STANDARD, Base64::URL_SAFE_NO_PAD, Base64::MIME] {
+            let mut encoder = config.encoder(Vec::new());
+            for piece in data.chunks(1 + data.len() / 37).flat_map(|c| c.chunks(5)) {
+                encoder.write_all(piece).unwrap();
+            }
+            let encoded = encoder.finish().unwrap();
+            assert_eq!(encoded, config.encode(&data).as_bytes());
+
+            let mut decoded = Vec::new();
+            config.decoder(&encoded[..], DecodeMode::Strict).read_to_end(&mut decoded).unwrap();
+            assert_eq!(decoded, data);
+        }
+    }
+
+    #[test]
+    fn test_streaming_decoder_reports_errors() {
+        let mut text = Base64::STANDARD.encode(&[7u8; 6000]).into_bytes();
+        text[5000] = b'*';
+        let err = Base64::STANDARD
+            .decoder(&text[..], DecodeMode::Strict)
+            .read_to_end(&mut Vec::new())
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("5000"), "{}", err);
+    }
+}
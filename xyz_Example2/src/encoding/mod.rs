@@ -0,0 +1,255 @@
+// Binary-to-Text Encodings: Hex, Base32, Base58, Base64 and Ascii85
+// Codificaciones Binario-a-Texto: Hex, Base32, Base58, Base64 y Ascii85
+
+use std::collections::HashMap;
+
+use crate::error::CipherError;
+
+pub mod ascii85;
+pub mod base32;
+pub mod base58;
+pub mod base64;
+
+pub use ascii85::{Ascii85, Z85};
+pub use base32::Base32;
+pub use base58::{Base58, Base58Check};
+pub use base64::{Base64, DecodeMode};
+
+/// Common interface of every binary-to-text encoding
+/// Interfaz común de cada codificación binario-a-texto
+pub trait Encoding {
+    /// Display name, also used for lookup / Nombre mostrado, también usado para buscar
+    fn name(&self) -> &'static str;
+
+    /// Encode bytes; fails only when the input shape is not allowed (Z85 needs whole 4-byte groups)
+    /// Codificar bytes; solo falla si la forma de la entrada no se permite (Z85 necesita grupos de 4 bytes)
+    fn encode(&self, data: &[u8]) -> Result<String, CipherError>;
+
+    /// Decode text, reporting where it is malformed / Decodificar texto, indicando dónde está mal formado
+    fn decode(&self, text: &str) -> Result<Vec<u8>, CipherError>;
+}
+
+/// Every built-in encoding that takes any input, in menu order; `Z85` needs whole 4-byte groups, so it is only
+/// used directly
+/// Todas las codificaciones incluidas que aceptan cualquier entrada, en orden de menú; `Z85` necesita grupos de
+/// 4 bytes, así que solo se usa directamente
+pub fn encodings() -> [&'static dyn Encoding; 8] {
+    [
+        &Hex,
+        &Base32::STANDARD,
+        &Base32::HEX,
+        &Base58,
+        &Base58Check,
+        &Base64::STANDARD,
+        &Base64::URL_SAFE_NO_PAD,
+        &Ascii85,
+    ]
+}
+
+/// Look up an encoding by name, ignoring case / Buscar una codificación por nombre, sin distinguir mayúsculas
+pub fn encoding_by_name(name: &str) -> Option<&'static dyn Encoding> {
+    encodings().into_iter().find(|e| e.name().eq_ignore_ascii_case(name))
+}
+
+/// Lowercase hexadecimal, two digits per byte / Hexadecimal en minúsculas, dos dígitos por byte
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Hex;
+
+impl Encoding for Hex {
+    fn name(&self) -> &'static str {
+        "Hex"
+    }
+
+    fn encode(&self, data: &[u8]) -> Result<String, CipherError> {
+        Ok(bytes_to_hex(data))
+    }
+
+    fn decode(&self, text: &str) -> Result<Vec<u8>, CipherError> {
+        hex_to_bytes(text)
+    }
+}
+
+// === BASE64 ENCODING / CODIFICACIÓN BASE64 ===
+
+// Simple Base64 implementation (for educational purposes); `Base64` is the full RFC 4648 version
+// Implementación simple de Base64 (para propósitos educativos); `Base64` es la versión RFC 4648 completa
+pub fn simple_base64_encode(data: &[u8]) -> Result<String, CipherError> {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut result = String::new();
+    
+    for chunk in data.chunks(3) {
+        let mut buf = [0u8; 3];
+        for (i, &byte) in chunk.iter().enumerate() {
+            buf[i] = byte;
+        }
+        
+        let b = ((buf[0] as u32) << 16) | ((buf[1] as u32) << 8) | (buf[2] as u32);
+        
+        result.push(ALPHABET[((b >> 18) & 63) as usize] as char);
+        result.push(ALPHABET[((b >> 12) & 63) as usize] as char);
+        result.push(if chunk.len() > 1 { ALPHABET[((b >> 6) & 63) as usize] as char } else { '=' });
+        result.push(if chunk.len() > 2 { ALPHABET[(b & 63) as usize] as char } else { '=' });
+    }
+    
+    Ok(result)
+}
+
+pub fn simple_base64_decode(encoded: &str) -> Result<Vec<u8>, CipherError> {
+    let mut decode_table = HashMap::new();
+    let alphabet = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    
+    for (i, c) in alphabet.chars().enumerate() {
+        decode_table.insert(c, i as u8);
+    }
+    
+    let malformed = |position: usize, reason: &str| CipherError::MalformedInput {
+        position,
+        reason: reason.to_string(),
+    };
+    
+    // Padding is only allowed as the last one or two characters
+    // El relleno solo se permite como el último o los dos últimos caracteres
+    let chars: Vec<char> = encoded.chars().collect();
+    if !chars.len().is_multiple_of(4) {
+        return Err(malformed(chars.len(), "length is not a multiple of 4"));
+    }
+    let data_len = chars.iter().rposition(|&c| c != '=').map_or(0, |p| p + 1);
+    if chars.len() - data_len > 2 {
+        return Err(malformed(data_len, "too much padding"));
+    }
+    
+    let mut values = Vec::with_capacity(data_len);
+    for (position, c) in chars[..data_len].iter().enumerate() {
+        match decode_table.get(c) {
+            Some(&v) => values.push(v),
+            None => return Err(malformed(position, &format!("invalid character '{}'", c))),
+        }
+    }
+    
+    let mut result = Vec::new();
+    for chunk in values.chunks(4) {
+        if chunk.len() < 2 {
+            return Err(malformed(data_len - 1, "truncated final group"));
+        }
+        
+        let b1 = chunk[0];
+        let b2 = chunk[1];
+        let b3 = if chunk.len() > 2 { chunk[2] } else { 0 };
+        let b4 = if chunk.len() > 3 { chunk[3] } else { 0 };
+        
+        let combined = ((b1 as u32) << 18) | ((b2 as u32) << 12) | ((b3 as u32) << 6) | (b4 as u32);
+        
+        result.push((combined >> 16) as u8);
+        if chunk.len() > 2 {
+            result.push((combined >> 8) as u8);
+        }
+        if chunk.len() > 3 {
+            result.push(combined as u8);
+        }
+    }
+    
+    Ok(result)
+}
+
+// === UTILITY FUNCTIONS / FUNCIONES UTILITARIAS ===
+pub fn bytes_to_hex(bytes: &[u8]) -> String {
+    bytes.iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+/// Parse a hexadecimal string (either case) back into bytes
+/// Convertir una cadena hexadecimal (mayúsculas o minúsculas) de vuelta a bytes
+pub fn hex_to_bytes(hex: &str) -> Result<Vec<u8>, CipherError> {
+    let digits = hex.as_bytes();
+    if !digits.len().is_multiple_of(2) {
+        return Err(CipherError::MalformedInput {
+            position: digits.len(),
+            reason: "odd number of hex digits".to_string(),
+        });
+    }
+    
+    let nibble = |position: usize| -> Result<u8, CipherError> {
+        (digits[position] as char)
+            .to_digit(16)
+            .map(|d| d as u8)
+            .ok_or_else(|| CipherError::MalformedInput {
+                position,
+                reason: format!("invalid hex digit '{}'", digits[position] as char),
+            })
+    };
+    
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| Ok((nibble(i)? << 4) | nibble(i + 1)?))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_round_trip() {
+        for message in [&b""[..], b"f", b"fo", b"foo", b"foob", b"Hello, Base64 World!"] {
+            let encoded = simple_base64_encode(message).unwrap();
+            assert_eq!(simple_base64_decode(&encoded).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn test_base64_rejects_malformed_input() {
+        assert!(matches!(
+            simple_base64_decode("SGV*bG8="),
+            Err(CipherError::MalformedInput { position: 3, .. })
+        ));
+        assert!(simple_base64_decode("SGVsbG8").is_err());
+        assert!(simple_base64_decode("SG=sbG8=").is_err());
+        assert!(simple_base64_decode("S===").is_err());
+    }
+
+    #[test]
+    fn test_every_encoding_round_trips() {
+        let data: Vec<u8> = (0..64u8).map(|i| i.wrapping_mul(37) ^ 0xa5).chain([0, 0, 0, 0]).collect();
+        let mut names = Vec::new();
+        for encoding in encodings() {
+            let text = encoding.encode(&data).unwrap();
+            assert_eq!(encoding.decode(&text).unwrap(), data, "{}", encoding.name());
+            assert_eq!(encoding_by_name(&encoding.name().to_lowercase()).unwrap().name(), encoding.name());
+            names.push(encoding.name());
+        }
+        names.dedup();
+        assert_eq!(names.len(), encodings().len());
+        assert!(encoding_by_name("Base65").is_none());
+        // Z85 cannot take every length, so it is not offered / Z85 no acepta toda longitud, así que no se ofrece
+        assert!(encoding_by_name("Z85").is_none());
+    }
+
+    #[test]
+    fn test_property_every_encoding_round_trips() {
+        use crate::property::{check, ensure_eq, Bytes, Ints};
+        
+        // The index past the last encoding is the original `simple_base64` pair
+        // El índice tras la última codificación es el par `simple_base64` original
+        let strategy = (Ints { lo: 0, hi: encodings().len() as u64 }, Bytes { min: 0, max: 256 });
+        check("every encoding round-trips", 5000, &strategy, |(index, data)| {
+            let Some(encoding) = encodings().get(*index as usize).copied() else {
+                return ensure_eq(simple_base64_decode(&simple_base64_encode(data)?)?, data.clone());
+            };
+            ensure_eq(encoding.decode(&encoding.encode(data)?)?, data.clone())
+        });
+    }
+
+    #[test]
+    fn test_bytes_to_hex() {
+        assert_eq!(bytes_to_hex(&[0x00, 0xab, 0x10]), "00ab10");
+    }
+
+    #[test]
+    fn test_hex_to_bytes() {
+        assert_eq!(hex_to_bytes("00aB10").unwrap(), vec![0x00, 0xab, 0x10]);
+        assert!(matches!(hex_to_bytes("abc"), Err(CipherError::MalformedInput { position: 3, .. })));
+        assert!(matches!(hex_to_bytes("0g"), Err(CipherError::MalformedInput { position: 1, .. })));
+    }
+}
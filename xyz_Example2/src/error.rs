@@ -0,0 +1,104 @@
+// Error Type for Cipher Operations
+// Tipo de Error para Operaciones de Cifrado
+
+use std::fmt;
+use std::io;
+
+/// Everything that can go wrong while encrypting, decrypting, encoding or decoding
+/// Todo lo que puede fallar al cifrar, descifrar, codificar o decodificar
+#[derive(Debug)]
+pub enum CipherError {
+    EmptyKey,                                       // Key has no bytes / La clave está vacía
+    InvalidKey(String),                             // Key rejected by the cipher / Clave rechazada
+    ShiftOutOfRange(i64),                           // Shift outside 0-25 / Desplazamiento fuera de 0-25
+    MissingParam(&'static str),                     // Required parameter absent / Falta un parámetro
+    InvalidParam { name: String, value: String },   // Parameter could not be parsed / Parámetro no interpretable
+    UnknownAlgorithm(String),                       // Not in the registry / No está en el registro
+    MalformedInput { position: usize, reason: String }, // Bad ciphertext or encoding / Entrada mal formada
+    AuthenticationFailed,                           // Tag mismatch: wrong key or tampered data / Etiqueta no coincide
+    Io(io::Error),                                  // File or console failure / Fallo de archivo o consola
+}
+
+impl fmt::Display for CipherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CipherError::EmptyKey => write!(f, "key must not be empty"),
+            CipherError::InvalidKey(reason) => write!(f, "invalid key: {}", reason),
+            CipherError::ShiftOutOfRange(shift) => {
+                write!(f, "shift {} is out of range (expected 0-25)", shift)
+            }
+            CipherError::MissingParam(name) => write!(f, "missing parameter '{}'", name),
+            CipherError::InvalidParam { name, value } => {
+                write!(f, "invalid value '{}' for parameter '{}'", value, name)
+            }
+            CipherError::UnknownAlgorithm(name) => write!(f, "unknown algorithm '{}'", name),
+            CipherError::MalformedInput { position, reason } => {
+                write!(f, "malformed input at position {}: {}", position, reason)
+            }
+            CipherError::AuthenticationFailed => {
+                write!(f, "authentication failed: wrong key or corrupted data")
+            }
+            CipherError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CipherError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CipherError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CipherError {
+    /// Unwraps a `CipherError` that travelled through an `io::Read`/`io::Write` impl
+    /// Desenvuelve un `CipherError` que viajó a través de una implementación de `io::Read`/`io::Write`
+    fn from(e: io::Error) -> Self {
+        if e.get_ref().is_some_and(|inner| inner.is::<CipherError>()) {
+            // Both unwraps are guarded by the check above / Ambos unwrap están protegidos por la comprobación anterior
+            return *e.into_inner().unwrap().downcast::<CipherError>().unwrap();
+        }
+        CipherError::Io(e)
+    }
+}
+
+impl From<CipherError> for io::Error {
+    fn from(e: CipherError) -> Self {
+        match e {
+            CipherError::Io(inner) => inner,
+            other => io::Error::new(io::ErrorKind::InvalidData, other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_messages() {
+        assert_eq!(CipherError::EmptyKey.to_string(), "key must not be empty");
+        assert_eq!(
+            CipherError::ShiftOutOfRange(30).to_string(),
+            "shift 30 is out of range (expected 0-25)"
+        );
+        let malformed = CipherError::MalformedInput { position: 4, reason: "bad char".into() };
+        assert_eq!(malformed.to_string(), "malformed input at position 4: bad char");
+    }
+
+    #[test]
+    fn test_io_error_converts() {
+        let err: CipherError = io::Error::new(io::ErrorKind::NotFound, "gone").into();
+        assert!(matches!(err, CipherError::Io(_)));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn test_cipher_error_round_trips_through_io_error() {
+        let wrapped: io::Error = CipherError::AuthenticationFailed.into();
+        assert_eq!(wrapped.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(CipherError::from(wrapped), CipherError::AuthenticationFailed));
+    }
+}
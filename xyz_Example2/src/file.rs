@@ -0,0 +1,1074 @@
+// File Encryption: `EncryptionManager` with Multiple Algorithms
+// Cifrado de Archivos: `EncryptionManager` con Múltiples Algoritmos
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+
+use crate::aes::BLOCK_LEN;
+use crate::chacha20poly1305::{self, ChaCha20Poly1305};
+use crate::cipher::Cipher;
+use crate::classical::transposition::MAX_RAILS;
+use crate::classical::{
+    hill, AffineCipher, AtbashCipher, CaesarCipher, ColumnarCipher, EnigmaCipher, EnigmaSettings, HillCipher,
+    PlayfairCipher, RailFenceCipher, VigenereCipher, XorCipher,
+};
+use crate::container::{self, Container, ContainerHeader, Integrity};
+use crate::error::CipherError;
+use crate::hash::{Digest, Sha256};
+use crate::hmac::{Hkdf, Hmac};
+use crate::kdf::{KdfParams, KeyDerivation};
+use crate::modes::{AesCbc, AesCtr, AesEcb, AesGcm, GCM_NONCE_LEN};
+use crate::random::{SecureRandom, TEXT_KEY_LEN};
+use crate::secret::{zeroize, Redacted, SecretKey};
+use crate::stream::{StreamDecryptor, StreamEncryptor};
+
+/// Advanced encryption manager with multiple cipher options
+/// Gestor de cifrado avanzado con múltiples opciones de cifrado
+pub struct EncryptionManager {
+    cipher: Box<dyn Cipher>,
+    algorithm: Option<CipherType>, // None for custom ciphers / None para cifrados personalizados
+    key: SecretKey,  // Master key; empty when built from a bare cipher / Clave maestra
+    kdf: Option<KeyDerivation>, // Salt and cost when built from a password / Sal y coste si viene de una contraseña
+}
+
+// HKDF labels that keep the two subkeys independent
+// Etiquetas HKDF que mantienen independientes las dos subclaves
+const ENCRYPTION_INFO: &[u8] = b"xyz_example2 encryption key";
+const MAC_INFO: &[u8] = b"xyz_example2 mac key";
+pub const SUBKEY_LEN: usize = 32;
+
+/// Encryption and MAC keys derived from one master key with HKDF-SHA256
+/// Claves de cifrado y MAC derivadas de una clave maestra con HKDF-SHA256
+#[derive(Clone)]
+pub struct SubKeys {
+    pub encryption: [u8; SUBKEY_LEN],
+    pub mac: [u8; SUBKEY_LEN],
+}
+
+impl SubKeys {
+    pub fn derive(master_key: &[u8]) -> Result<Self, CipherError> {
+        if master_key.is_empty() {
+            return Err(CipherError::EmptyKey);
+        }
+        let hkdf = Hkdf::<Sha256>::extract(&[], master_key);
+        let mut keys = Self { encryption: [0; SUBKEY_LEN], mac: [0; SUBKEY_LEN] };
+        keys.encryption.copy_from_slice(&hkdf.expand(ENCRYPTION_INFO, SUBKEY_LEN)?);
+        keys.mac.copy_from_slice(&hkdf.expand(MAC_INFO, SUBKEY_LEN)?);
+        Ok(keys)
+    }
+}
+
+impl Drop for SubKeys {
+    fn drop(&mut self) {
+        zeroize(&mut self.encryption);
+        zeroize(&mut self.mac);
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+pub enum CipherType {
+    Caesar(u8),      // Caesar cipher with shift
+    XOR,             // XOR cipher
+    Vigenere,        // Vigenère cipher
+    Enigma(EnigmaSettings), // Enigma I / M3 rotor machine (ignores the text key)
+    Affine { a: u8, b: u8 }, // Affine cipher a·x + b mod 26
+    Atbash,          // Atbash (reversed alphabet)
+    Playfair,        // Playfair square keyed by the text key
+    Hill { matrix: String }, // Hill cipher; the letters fill the matrix row by row
+    RailFence(u8),   // Rail Fence with this many rails
+    Columnar,        // Columnar transposition keyed by the text key
+    ChaCha20Poly1305 {
+        key: SecretKey, // 32 bytes
+        nonce: [u8; chacha20poly1305::NONCE_LEN],
+    },               // RFC 8439 AEAD (ignores the text key)
+    AesEcb { key: SecretKey },                          // AES-ECB, teaching only
+    AesCbc { key: SecretKey, iv: [u8; BLOCK_LEN] },     // AES-CBC with PKCS#7
+    AesCtr { key: SecretKey, counter: [u8; BLOCK_LEN] }, // AES-CTR
+    AesGcm { key: SecretKey, nonce: [u8; GCM_NONCE_LEN] }, // AES-GCM AEAD
+}
+
+/// Keys are copied with `SecretKey::duplicate`, so every clone is wiped when dropped
+/// Las claves se copian con `SecretKey::duplicate`, así cada clon se borra al liberarse
+impl Clone for CipherType {
+    fn clone(&self) -> Self {
+        match self {
+            CipherType::Caesar(shift) => CipherType::Caesar(*shift),
+            CipherType::XOR => CipherType::XOR,
+            CipherType::Vigenere => CipherType::Vigenere,
+            CipherType::Enigma(settings) => CipherType::Enigma(settings.clone()),
+            CipherType::Affine { a, b } => CipherType::Affine { a: *a, b: *b },
+            CipherType::Atbash => CipherType::Atbash,
+            CipherType::Playfair => CipherType::Playfair,
+            CipherType::Hill { matrix } => CipherType::Hill { matrix: matrix.clone() },
+            CipherType::RailFence(rails) => CipherType::RailFence(*rails),
+            CipherType::Columnar => CipherType::Columnar,
+            CipherType::ChaCha20Poly1305 { key, nonce } => {
+                CipherType::ChaCha20Poly1305 { key: key.duplicate(), nonce: *nonce }
+            }
+            CipherType::AesEcb { key } => CipherType::AesEcb { key: key.duplicate() },
+            CipherType::AesCbc { key, iv } => CipherType::AesCbc { key: key.duplicate(), iv: *iv },
+            CipherType::AesCtr { key, counter } => CipherType::AesCtr { key: key.duplicate(), counter: *counter },
+            CipherType::AesGcm { key, nonce } => CipherType::AesGcm { key: key.duplicate(), nonce: *nonce },
+        }
+    }
+}
+
+/// Like a derived `Debug`, but AES and ChaCha20 keys show only their length
+/// Como un `Debug` derivado, pero las claves AES y ChaCha20 solo muestran su longitud
+impl fmt::Debug for CipherType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let redacted = |key: &SecretKey| format!("[REDACTED; {} bytes]", key.len());
+        match self {
+            CipherType::Caesar(_) => f.debug_tuple("Caesar").field(&Redacted).finish(),
+            CipherType::XOR => f.write_str("XOR"),
+            CipherType::Vigenere => f.write_str("Vigenere"),
+            CipherType::Enigma(_) => f.debug_tuple("Enigma").field(&Redacted).finish(),
+            CipherType::Affine { .. } => f.debug_struct("Affine").field("a", &Redacted).field("b", &Redacted).finish(),
+            CipherType::Atbash => f.write_str("Atbash"),
+            CipherType::Playfair => f.write_str("Playfair"),
+            CipherType::Hill { .. } => f.debug_struct("Hill").field("matrix", &Redacted).finish(),
+            CipherType::RailFence(_) => f.debug_tuple("RailFence").field(&Redacted).finish(),
+            CipherType::Columnar => f.write_str("Columnar"),
+            CipherType::ChaCha20Poly1305 { key, nonce } => f
+                .debug_struct("ChaCha20Poly1305")
+                .field("key", &format_args!("{}", redacted(key)))
+                .field("nonce", nonce)
+                .finish(),
+            CipherType::AesEcb { key } => {
+                f.debug_struct("AesEcb").field("key", &format_args!("{}", redacted(key))).finish()
+            }
+            CipherType::AesCbc { key, iv } => {
+                f.debug_struct("AesCbc").field("key", &format_args!("{}", redacted(key))).field("iv", iv).finish()
+            }
+            CipherType::AesCtr { key, counter } => f
+                .debug_struct("AesCtr")
+                .field("key", &format_args!("{}", redacted(key)))
+                .field("counter", counter)
+                .finish(),
+            CipherType::AesGcm { key, nonce } => {
+                f.debug_struct("AesGcm").field("key", &format_args!("{}", redacted(key))).field("nonce", nonce).finish()
+            }
+        }
+    }
+}
+
+impl CipherType {
+    /// Build the cipher implementation for this algorithm
+    /// Construir la implementación del cifrado para este algoritmo
+    pub fn into_cipher(self, key: &[u8]) -> Result<Box<dyn Cipher>, CipherError> {
+        Ok(match self {
+            CipherType::Caesar(shift) => Box::new(CaesarCipher::new(shift)?),
+            CipherType::XOR => Box::new(XorCipher::new(key)?),
+            CipherType::Vigenere => Box::new(VigenereCipher::new(key)?),
+            CipherType::Enigma(settings) => Box::new(EnigmaCipher::new(settings)?),
+            CipherType::Affine { a, b } => Box::new(AffineCipher::new(a, b)?),
+            CipherType::Atbash => Box::new(AtbashCipher),
+            CipherType::Playfair => Box::new(PlayfairCipher::new(key)?),
+            CipherType::Hill { matrix } => Box::new(HillCipher::new(matrix.as_bytes())?),
+            CipherType::RailFence(rails) => Box::new(RailFenceCipher::new(rails.into())?),
+            CipherType::Columnar => Box::new(ColumnarCipher::new(key)?),
+            CipherType::ChaCha20Poly1305 { key, nonce } => {
+                let key = key.expose().try_into().map_err(|_| {
+                    CipherError::InvalidKey(format!("ChaCha20 key must be {} bytes, got {}", chacha20poly1305::KEY_LEN, key.len()))
+                })?;
+                Box::new(ChaCha20Poly1305::new(key, nonce))
+            }
+            CipherType::AesEcb { key } => Box::new(AesEcb::new(key.expose())?),
+            CipherType::AesCbc { key, iv } => Box::new(AesCbc::new(key.expose(), iv)?),
+            CipherType::AesCtr { key, counter } => Box::new(AesCtr::new(key.expose(), counter)?),
+            CipherType::AesGcm { key, nonce } => Box::new(AesGcm::new(key.expose(), nonce)?),
+        })
+    }
+    
+    /// One of each variant with placeholder parameters, for `with_random_key`; AES keys get `aes_key_len` bytes
+    /// Una de cada variante con parámetros de relleno, para `with_random_key`; las claves AES reciben `aes_key_len` bytes
+    pub fn variants(aes_key_len: usize) -> [CipherType; 15] {
+        [
+            CipherType::Caesar(0),
+            CipherType::XOR,
+            CipherType::Vigenere,
+            CipherType::Enigma(EnigmaSettings::default()),
+            CipherType::Affine { a: 1, b: 0 },
+            CipherType::Atbash,
+            CipherType::Playfair,
+            CipherType::Hill { matrix: String::new() },
+            CipherType::RailFence(2),
+            CipherType::Columnar,
+            CipherType::ChaCha20Poly1305 { key: SecretKey::new(&[0; chacha20poly1305::KEY_LEN]), nonce: [0; 12] },
+            CipherType::AesEcb { key: vec![0; aes_key_len].into() },
+            CipherType::AesCbc { key: vec![0; aes_key_len].into(), iv: [0; BLOCK_LEN] },
+            CipherType::AesCtr { key: vec![0; aes_key_len].into(), counter: [0; BLOCK_LEN] },
+            CipherType::AesGcm { key: vec![0; aes_key_len].into(), nonce: [0; GCM_NONCE_LEN] },
+        ]
+    }
+    
+    /// Key size in bits of the AES variants / Tamaño de clave en bits de las variantes AES
+    pub fn aes_key_bits(&self) -> Option<usize> {
+        match self {
+            CipherType::AesEcb { key }
+            | CipherType::AesCbc { key, .. }
+            | CipherType::AesCtr { key, .. }
+            | CipherType::AesGcm { key, .. } => Some(key.len() * 8),
+            _ => None,
+        }
+    }
+    
+    /// Parameters that are the whole key (Caesar shift, Enigma settings, Affine a and b, Hill matrix, rail count); a
+    /// manager with no text key uses them as its master key
+    /// Parámetros que son toda la clave (desplazamiento César, configuración Enigma, a y b afines, matriz de Hill,
+    /// número de rieles); un gestor sin clave de texto los usa como clave maestra
+    pub fn parameter_key(&self) -> Option<SecretKey> {
+        match self {
+            CipherType::Caesar(shift) => Some(SecretKey::new(&[*shift])),
+            CipherType::Enigma(settings) => Some(SecretKey::from(settings.to_string().into_bytes())),
+            CipherType::Affine { a, b } => Some(SecretKey::new(&[*a, *b])),
+            CipherType::Hill { matrix } => Some(SecretKey::new(matrix.as_bytes())),
+            CipherType::RailFence(rails) => Some(SecretKey::new(&[*rails])),
+            _ => None,
+        }
+    }
+    
+    /// What decrypting the encryption of `data` gives back: `data` itself, except for Playfair's prepared pairs
+    /// and Hill's X padding; chunked containers apply it to each chunk
+    /// Lo que devuelve descifrar el cifrado de `data`: el propio `data`, salvo los pares preparados de Playfair
+    /// y el relleno X de Hill; los contenedores por fragmentos lo aplican a cada fragmento
+    pub fn round_trip(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            CipherType::Playfair => PlayfairCipher::prepare(data),
+            CipherType::Hill { matrix } => HillCipher::pad(data, matrix.len().isqrt()),
+            _ => data.to_vec(),
+        }
+    }
+    
+    /// Replace the key carried inside AES/ChaCha20 variants, keeping the AES key size; parameters that are the
+    /// key are drawn from it through `SecureRandom::from_key`
+    /// Reemplazar la clave que llevan las variantes AES/ChaCha20, conservando el tamaño de clave AES; los
+    /// parámetros que son la clave se obtienen de ella mediante `SecureRandom::from_key`
+    pub fn with_key(self, key: &[u8; SUBKEY_LEN]) -> Self {
+        let aes_key = |old: SecretKey| match old.len() {
+            16 | 24 => SecretKey::new(&key[..old.len()]),
+            _ => SecretKey::new(key),
+        };
+        match self {
+            CipherType::ChaCha20Poly1305 { nonce, .. } => CipherType::ChaCha20Poly1305 { key: SecretKey::new(key), nonce },
+            CipherType::AesEcb { key } => CipherType::AesEcb { key: aes_key(key) },
+            CipherType::AesCbc { key, iv } => CipherType::AesCbc { key: aes_key(key), iv },
+            CipherType::AesCtr { key, counter } => CipherType::AesCtr { key: aes_key(key), counter },
+            CipherType::AesGcm { key, nonce } => CipherType::AesGcm { key: aes_key(key), nonce },
+            keyed @ (CipherType::Caesar(_)
+            | CipherType::Enigma(_)
+            | CipherType::Affine { .. }
+            | CipherType::Hill { .. }
+            | CipherType::RailFence(_)) => {
+                keyed.with_random_key(&mut SecureRandom::from_key(key)).0
+            }
+            other => other,
+        }
+    }
+    
+    /// Fresh key material for this algorithm; XOR, Vigenère, Playfair and Columnar also get a text key for
+    /// `EncryptionManager::new`
+    /// Material de clave nuevo para este algoritmo; XOR, Vigenère, Playfair y Columnar también reciben una clave
+    /// de texto para `EncryptionManager::new`
+    pub fn with_random_key(self, rng: &mut SecureRandom) -> (Self, String) {
+        let aes_len = |old: &SecretKey| match old.len() {
+            16 | 24 => old.len(),
+            _ => 32,
+        };
+        match self {
+            CipherType::Caesar(_) => (CipherType::Caesar(rng.shift_below(26) as u8), String::new()),
+            CipherType::XOR => (CipherType::XOR, rng.text_key(TEXT_KEY_LEN)),
+            CipherType::Vigenere => (CipherType::Vigenere, rng.text_key(TEXT_KEY_LEN)),
+            CipherType::Enigma(_) => (CipherType::Enigma(EnigmaSettings::random(rng)), String::new()),
+            CipherType::Affine { .. } => {
+                let (a, b) = (rng.coprime_below(26) as u8, rng.below(26) as u8);
+                (CipherType::Affine { a, b }, String::new())
+            }
+            CipherType::Atbash => (CipherType::Atbash, String::new()),
+            CipherType::Playfair => (CipherType::Playfair, rng.text_key(TEXT_KEY_LEN)),
+            CipherType::Hill { matrix } => {
+                // Keep the matrix size, 3x3 if the old one was not valid / Conservar el tamaño, 3x3 si no era válido
+                let size = (2..=hill::MAX_SIZE).find(|n| n * n == matrix.len()).unwrap_or(3);
+                (CipherType::Hill { matrix: HillCipher::random_key(rng, size) }, String::new())
+            }
+            CipherType::RailFence(_) => {
+                (CipherType::RailFence(rng.below(MAX_RAILS as u32 - 1) as u8 + 2), String::new())
+            }
+            CipherType::Columnar => (CipherType::Columnar, rng.text_key(TEXT_KEY_LEN)),
+            CipherType::ChaCha20Poly1305 { .. } => {
+                let key = rng.bytes(chacha20poly1305::KEY_LEN).into();
+                (CipherType::ChaCha20Poly1305 { key, nonce: rng.array() }, String::new())
+            }
+            CipherType::AesEcb { key } => (CipherType::AesEcb { key: rng.bytes(aes_len(&key)).into() }, String::new()),
+            CipherType::AesCbc { key, .. } => {
+                (CipherType::AesCbc { key: rng.bytes(aes_len(&key)).into(), iv: rng.array() }, String::new())
+            }
+            CipherType::AesCtr { key, .. } => {
+                (CipherType::AesCtr { key: rng.bytes(aes_len(&key)).into(), counter: rng.array() }, String::new())
+            }
+            CipherType::AesGcm { key, .. } => {
+                (CipherType::AesGcm { key: rng.bytes(aes_len(&key)).into(), nonce: rng.array() }, String::new())
+            }
+        }
+    }
+}
+
+impl EncryptionManager {
+    /// Create a new encryption manager, validating the key and parameters
+    /// Crear un nuevo gestor de cifrado, validando la clave y los parámetros
+    pub fn new(algorithm: CipherType, key: &str) -> Result<Self, CipherError> {
+        let cipher = algorithm.clone().into_cipher(key.as_bytes())?;
+        Ok(Self { cipher, algorithm: Some(algorithm), key: SecretKey::new(key.as_bytes()), kdf: None })
+    }
+    
+    /// Like `new`, but the cipher gets the HKDF encryption subkey instead of the raw key
+    /// Como `new`, pero el cifrado recibe la subclave de cifrado HKDF en lugar de la clave cruda
+    pub fn with_derived_keys(algorithm: CipherType, key: &[u8]) -> Result<Self, CipherError> {
+        let subkeys = SubKeys::derive(key)?;
+        let cipher = algorithm.clone().with_key(&subkeys.encryption).into_cipher(&subkeys.encryption)?;
+        Ok(Self { cipher, algorithm: Some(algorithm), key: SecretKey::new(key), kdf: None })
+    }
+    
+    /// Stretch a password into the master key; keep `key_derivation()` to decrypt later
+    /// Estirar una contraseña hasta la clave maestra; guarda `key_derivation()` para descifrar después
+    pub fn from_password(algorithm: CipherType, password: &str, kdf: KeyDerivation) -> Result<Self, CipherError> {
+        if password.is_empty() {
+            return Err(CipherError::EmptyKey);
+        }
+        let master_key = SecretKey::from(kdf.derive(password.as_bytes(), SUBKEY_LEN)?);
+        let mut manager = Self::with_derived_keys(algorithm, master_key.expose())?;
+        manager.kdf = Some(kdf);
+        Ok(manager)
+    }
+    
+    /// Create a manager around any `Cipher` implementation
+    /// Crear un gestor con cualquier implementación de `Cipher`
+    pub fn with_cipher(cipher: Box<dyn Cipher>) -> Self {
+        Self { cipher, algorithm: None, key: SecretKey::new(&[]), kdf: None }
+    }
+    
+    /// Salt and parameters used by `from_password` / Sal y parámetros usados por `from_password`
+    pub fn key_derivation(&self) -> Option<&KeyDerivation> {
+        self.kdf.as_ref()
+    }
+    
+    /// Subkeys derived from the master key / Subclaves derivadas de la clave maestra
+    pub fn subkeys(&self) -> Result<SubKeys, CipherError> {
+        SubKeys::derive(self.key.expose())
+    }
+    
+    /// Name of the active algorithm / Nombre del algoritmo activo
+    pub fn algorithm_name(&self) -> &'static str {
+        self.cipher.name()
+    }
+    
+    /// Encrypt data using the selected algorithm
+    /// Cifrar datos usando el algoritmo seleccionado
+    pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        self.cipher.encrypt(data)
+    }
+    
+    /// Decrypt data using the selected algorithm
+    /// Descifrar datos usando el algoritmo seleccionado
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        self.cipher.decrypt(data)
+    }
+    
+    /// Encrypt, then append an HMAC-SHA256 tag computed with the MAC subkey
+    /// Cifrar y luego añadir una etiqueta HMAC-SHA256 calculada con la subclave MAC
+    pub fn seal_with_mac(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let mac_key = self.subkeys()?.mac;
+        let mut sealed = self.encrypt(data)?;
+        let mut mac = Hmac::<Sha256>::new(&mac_key);
+        mac.update(&sealed);
+        sealed.extend_from_slice(&mac.finalize());
+        Ok(sealed)
+    }
+    
+    /// Verify the trailing tag before decrypting anything
+    /// Verificar la etiqueta final antes de descifrar nada
+    pub fn open_with_mac(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let mac_key = self.subkeys()?.mac;
+        if data.len() < Sha256::OUTPUT_LEN {
+            return Err(CipherError::MalformedInput {
+                position: data.len(),
+                reason: "missing HMAC tag".to_string(),
+            });
+        }
+        let (ciphertext, tag) = data.split_at(data.len() - Sha256::OUTPUT_LEN);
+        let mut mac = Hmac::<Sha256>::new(&mac_key);
+        mac.update(ciphertext);
+        mac.verify(tag)?;
+        self.decrypt(ciphertext)
+    }
+    
+    /// Stream a file into a self-describing container with a fresh nonce, in bounded memory
+    /// Cifrar un archivo por partes en un contenedor autodescriptivo con un nonce nuevo, con memoria acotada
+    pub fn encrypt_file(&self, input_path: &str, output_path: &str) -> Result<(), CipherError> {
+        let algorithm = self
+            .algorithm
+            .as_ref()
+            .ok_or_else(|| CipherError::UnknownAlgorithm(self.algorithm_name().to_string()))?;
+        let master_key = self.container_key();
+        let header = ContainerHeader {
+            version: container::VERSION_CHUNKED,
+            algorithm: container::with_fresh_nonce(algorithm, &mut SecureRandom::new()?),
+            kdf: self.kdf.as_ref().map(KeyDerivation::params),
+            salt: self.kdf.as_ref().map(|kdf| kdf.salt().to_vec()).unwrap_or_default(),
+            integrity: if master_key.is_empty() { Integrity::Checksum } else { Integrity::Hmac },
+        };
+        
+        let mut input = BufReader::new(File::open(input_path)?);
+        replace_file(output_path, |output| {
+            let mut encryptor = StreamEncryptor::new(BufWriter::new(output), header, master_key.expose())?;
+            io::copy(&mut input, &mut encryptor)?;
+            encryptor.finish()?;
+            Ok(())
+        })
+    }
+    
+    /// Decrypt a container, taking the algorithm from its header
+    /// Descifrar un contenedor, tomando el algoritmo de su cabecera
+    pub fn decrypt_file(&self, input_path: &str, output_path: &str) -> Result<(), CipherError> {
+        decrypt_container_file(input_path, output_path, |header| match (&header.kdf, &self.kdf) {
+            (None, _) => Ok(self.container_key()),
+            (Some(params), Some(own)) if own.params() == *params && own.salt() == header.salt => {
+                Ok(self.key.duplicate())
+            }
+            (Some(_), _) => Err(CipherError::InvalidKey(
+                "file was encrypted with a password and its own salt; use decrypt_file_with_password".to_string(),
+            )),
+        })
+    }
+    
+    /// Decrypt a password-protected container using the salt and cost stored in it
+    /// Descifrar un contenedor protegido con contraseña usando la sal y el coste guardados en él
+    pub fn decrypt_file_with_password(password: &str, input_path: &str, output_path: &str) -> Result<(), CipherError> {
+        decrypt_container_file(input_path, output_path, |header| {
+            let params: KdfParams = header
+                .kdf
+                .ok_or_else(|| CipherError::InvalidKey("file is not password protected".to_string()))?;
+            KeyDerivation::with_salt(params, &header.salt).derive(password.as_bytes(), SUBKEY_LEN).map(SecretKey::from)
+        })
+    }
+    
+    /// Legacy mode: raw ciphertext with no header / Modo heredado: texto cifrado sin cabecera
+    pub fn encrypt_file_legacy(&self, input_path: &str, output_path: &str) -> Result<(), CipherError> {
+        let data = fs::read(input_path)?;
+        let encrypted = self.encrypt(&data)?;
+        fs::write(output_path, encrypted)?;
+        Ok(())
+    }
+    
+    /// Legacy mode for headerless files / Modo heredado para archivos sin cabecera
+    pub fn decrypt_file_legacy(&self, input_path: &str, output_path: &str) -> Result<(), CipherError> {
+        let data = fs::read(input_path)?;
+        let decrypted = self.decrypt(&data)?;
+        fs::write(output_path, decrypted)?;
+        Ok(())
+    }
+    
+    /// Master key for containers; AES/ChaCha20 managers built with `new` use their embedded key, keyless classical
+    /// ciphers their `parameter_key`
+    /// Clave maestra para contenedores; los gestores AES/ChaCha20 creados con `new` usan su clave incluida, los
+    /// cifrados clásicos sin clave su `parameter_key`
+    fn container_key(&self) -> SecretKey {
+        if !self.key.is_empty() {
+            return self.key.duplicate();
+        }
+        if let Some(key) = self.algorithm.as_ref().and_then(CipherType::parameter_key) {
+            return key;
+        }
+        match &self.algorithm {
+            Some(CipherType::ChaCha20Poly1305 { key, .. })
+            | Some(CipherType::AesEcb { key })
+            | Some(CipherType::AesCbc { key, .. })
+            | Some(CipherType::AesCtr { key, .. })
+            | Some(CipherType::AesGcm { key, .. }) => key.duplicate(),
+            _ => SecretKey::new(&[]),
+        }
+    }
+}
+
+/// Container cipher and MAC key; only ciphers with no key at all, like Atbash, go without a MAC
+/// Cifrado y clave MAC del contenedor; solo los cifrados sin ninguna clave, como Atbash, prescinden del MAC
+fn container_cipher(
+    algorithm: &CipherType,
+    master_key: &[u8],
+) -> Result<(Box<dyn Cipher>, Option<SubKeys>), CipherError> {
+    if master_key.is_empty() {
+        return Ok((algorithm.clone().into_cipher(&[])?, None));
+    }
+    let subkeys = SubKeys::derive(master_key)?;
+    let cipher = algorithm.clone().with_key(&subkeys.encryption).into_cipher(&subkeys.encryption)?;
+    Ok((cipher, Some(subkeys)))
+}
+
+/// Write `output_path` through a sibling temporary file that is renamed over it on success, so the
+/// output may be the input being read, and a failure leaves whatever was there before untouched
+/// Escribir `output_path` mediante un archivo temporal hermano que se renombra sobre él si todo va bien,
+/// así la salida puede ser la misma entrada que se lee, y un fallo deja intacto lo que hubiera antes
+fn replace_file(
+    output_path: &str,
+    write: impl FnOnce(&mut File) -> Result<(), CipherError>,
+) -> Result<(), CipherError> {
+    let temp_path = format!("{}.{}.tmp", output_path, std::process::id());
+    let result = File::create_new(&temp_path)
+        .map_err(CipherError::from)
+        .and_then(|mut file| write(&mut file))
+        .and_then(|_| Ok(fs::rename(&temp_path, output_path)?));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Read the header, pick the master key for it, then decrypt into a temporary file renamed over the output
+/// Leer la cabecera, elegir su clave maestra y descifrar en un archivo temporal renombrado sobre la salida
+fn decrypt_container_file(
+    input_path: &str,
+    output_path: &str,
+    master_key_for: impl FnOnce(&ContainerHeader) -> Result<SecretKey, CipherError>,
+) -> Result<(), CipherError> {
+    let mut input = BufReader::new(File::open(input_path)?);
+    let header = ContainerHeader::read_from(&mut input)?;
+    let master_key = master_key_for(&header)?;
+    
+    if header.version == container::VERSION_WHOLE {
+        // Version 1 has a single trailing tag, so it is read whole / La versión 1 tiene una sola etiqueta final
+        let mut data = header.to_bytes()?;
+        input.read_to_end(&mut data)?;
+        let plain = open_container(&Container::parse(&data)?, master_key.expose())?;
+        return replace_file(output_path, |output| Ok(output.write_all(&plain)?));
+    }
+    
+    let mut decryptor = StreamDecryptor::new(input, header, master_key.expose())?;
+    replace_file(output_path, |output| {
+        let mut output = BufWriter::new(output);
+        io::copy(&mut decryptor, &mut output)?;
+        Ok(output.flush()?)
+    })
+}
+
+/// Verify the tag, then decrypt / Verificar la etiqueta y luego descifrar
+fn open_container(container: &Container, master_key: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let (cipher, subkeys) = container_cipher(&container.header.algorithm, master_key)?;
+    container.verify(subkeys.as_ref().map(|k| &k.mac[..]))?;
+    cipher.decrypt(container.ciphertext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::{CipherParams, CipherRegistry};
+
+    #[test]
+    fn test_encryption_manager_caesar() {
+        let manager = EncryptionManager::new(CipherType::Caesar(5), "").unwrap();
+        let original = b"Hello World";
+        let encrypted = manager.encrypt(original).unwrap();
+        let decrypted = manager.decrypt(&encrypted).unwrap();
+        assert_eq!(original, &decrypted[..]);
+    }
+
+    #[test]
+    fn test_encryption_manager_xor() {
+        let manager = EncryptionManager::new(CipherType::XOR, "testkey").unwrap();
+        let original = b"Secret message for testing";
+        let encrypted = manager.encrypt(original).unwrap();
+        let decrypted = manager.decrypt(&encrypted).unwrap();
+        assert_eq!(original, &decrypted[..]);
+    }
+
+    #[test]
+    fn test_encryption_manager_vigenere() {
+        let manager = EncryptionManager::new(CipherType::Vigenere, "KEY").unwrap();
+        let original = b"Hello World";
+        let encrypted = manager.encrypt(original).unwrap();
+        let decrypted = manager.decrypt(&encrypted).unwrap();
+        assert_eq!(original, &decrypted[..]);
+    }
+
+    #[test]
+    fn test_encryption_manager_classical_suite() {
+        let original = b"Meet me at the old oak tree, 9 PM! (jazz)";
+        let algorithms = [
+            (CipherType::Enigma("II IV V B BUL BLA AV BS CG DL FU HZ IN KM OW RX".parse().unwrap()), ""),
+            (CipherType::Affine { a: 5, b: 8 }, ""),
+            (CipherType::Atbash, ""),
+            (CipherType::Playfair, "MONARCHY"),
+            (CipherType::Hill { matrix: "GYBNQKURP".to_string() }, ""),
+            (CipherType::RailFence(3), ""),
+            (CipherType::Columnar, "ZEBRAS"),
+        ];
+        for (algorithm, key) in algorithms {
+            let expected = algorithm.round_trip(original);
+            let manager = EncryptionManager::new(algorithm.clone(), key).unwrap();
+            let encrypted = manager.encrypt(original).unwrap();
+            assert_ne!(encrypted, original, "{:?}", algorithm);
+            assert_eq!(manager.decrypt(&encrypted).unwrap(), expected, "{:?}", algorithm);
+            
+            let derived = EncryptionManager::with_derived_keys(algorithm, b"master").unwrap();
+            assert_eq!(derived.decrypt(&derived.encrypt(original).unwrap()).unwrap(), expected);
+        }
+        
+        assert!(matches!(EncryptionManager::new(CipherType::Affine { a: 13, b: 0 }, ""), Err(CipherError::InvalidKey(_))));
+        assert!(matches!(
+            EncryptionManager::new(CipherType::Hill { matrix: "AAAA".to_string() }, ""),
+            Err(CipherError::InvalidKey(_))
+        ));
+        assert!(matches!(EncryptionManager::new(CipherType::RailFence(1), ""), Err(CipherError::InvalidParam { .. })));
+        assert!(matches!(EncryptionManager::new(CipherType::Playfair, ""), Err(CipherError::EmptyKey)));
+        assert!(matches!(EncryptionManager::new(CipherType::Columnar, ""), Err(CipherError::EmptyKey)));
+    }
+
+    #[test]
+    fn test_different_algorithms_produce_different_results() {
+        let original = b"Test message";
+        
+        let caesar = EncryptionManager::new(CipherType::Caesar(3), "").unwrap();
+        let xor = EncryptionManager::new(CipherType::XOR, "key").unwrap();
+        let vigenere = EncryptionManager::new(CipherType::Vigenere, "KEY").unwrap();
+        
+        let caesar_encrypted = caesar.encrypt(original).unwrap();
+        let xor_encrypted = xor.encrypt(original).unwrap();
+        let vigenere_encrypted = vigenere.encrypt(original).unwrap();
+        
+        // All should be different from original
+        assert_ne!(original, &caesar_encrypted[..]);
+        assert_ne!(original, &xor_encrypted[..]);
+        assert_ne!(original, &vigenere_encrypted[..]);
+        
+        // All should be different from each other
+        assert_ne!(caesar_encrypted, xor_encrypted);
+        assert_ne!(caesar_encrypted, vigenere_encrypted);
+        assert_ne!(xor_encrypted, vigenere_encrypted);
+    }
+
+    #[test]
+    fn test_encryption_manager_with_registered_cipher() {
+        let registry = CipherRegistry::with_defaults();
+        let mut params = CipherParams::new();
+        params.set("key", "KEY");
+        let manager = EncryptionManager::with_cipher(registry.build("vigenere", &params).unwrap());
+        let legacy = EncryptionManager::new(CipherType::Vigenere, "KEY").unwrap();
+        let original = b"Hello World";
+        assert_eq!(manager.algorithm_name(), "Vigenere");
+        assert_eq!(manager.encrypt(original).unwrap(), legacy.encrypt(original).unwrap());
+    }
+
+    #[test]
+    fn test_encryption_manager_rejects_bad_parameters() {
+        assert!(matches!(EncryptionManager::new(CipherType::XOR, ""), Err(CipherError::EmptyKey)));
+        assert!(matches!(EncryptionManager::new(CipherType::Vigenere, ""), Err(CipherError::EmptyKey)));
+        assert!(matches!(
+            EncryptionManager::new(CipherType::Caesar(27), ""),
+            Err(CipherError::ShiftOutOfRange(27))
+        ));
+    }
+
+    #[test]
+    fn test_encrypt_file_missing_input_is_io_error() {
+        let manager = EncryptionManager::new(CipherType::XOR, "key").unwrap();
+        let result = manager.encrypt_file("definitely_missing_input.txt", "unused_output.txt");
+        assert!(matches!(result, Err(CipherError::Io(_))));
+    }
+
+    #[test]
+    fn test_encryption_manager_chacha20poly1305() {
+        let algorithm = CipherType::ChaCha20Poly1305 { key: SecretKey::new(&[7; 32]), nonce: [1; 12] };
+        let manager = EncryptionManager::new(algorithm, "").unwrap();
+        let original = b"Authenticated secret";
+        let mut encrypted = manager.encrypt(original).unwrap();
+        assert_eq!(encrypted.len(), original.len() + chacha20poly1305::TAG_LEN);
+        assert_eq!(manager.decrypt(&encrypted).unwrap(), original);
+        
+        encrypted[3] ^= 0x80;
+        assert!(matches!(manager.decrypt(&encrypted), Err(CipherError::AuthenticationFailed)));
+    }
+
+    #[test]
+    fn test_encryption_manager_aes_modes_round_trip_files() {
+        let algorithms = [
+            CipherType::AesEcb { key: vec![1; 16].into() },
+            CipherType::AesCbc { key: vec![2; 24].into(), iv: [3; 16] },
+            CipherType::AesCtr { key: vec![4; 32].into(), counter: [5; 16] },
+            CipherType::AesGcm { key: vec![6; 16].into(), nonce: [7; 12] },
+        ];
+        let dir = std::env::temp_dir();
+        let input = dir.join("xyz_example2_aes_input.txt");
+        let encrypted = dir.join("xyz_example2_aes_encrypted.bin");
+        let decrypted = dir.join("xyz_example2_aes_decrypted.txt");
+        let content = b"File contents that are not a multiple of the block size.";
+        fs::write(&input, content).unwrap();
+        
+        for algorithm in algorithms {
+            let manager = EncryptionManager::new(algorithm, "").unwrap();
+            manager.encrypt_file(input.to_str().unwrap(), encrypted.to_str().unwrap()).unwrap();
+            assert_ne!(fs::read(&encrypted).unwrap(), content);
+            manager.decrypt_file(encrypted.to_str().unwrap(), decrypted.to_str().unwrap()).unwrap();
+            assert_eq!(fs::read(&decrypted).unwrap(), content);
+        }
+        
+        for path in [input, encrypted, decrypted] {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    #[test]
+    fn test_encryption_manager_rejects_bad_aes_key() {
+        let result = EncryptionManager::new(CipherType::AesCbc { key: vec![0; 10].into(), iv: [0; 16] }, "");
+        assert!(matches!(result, Err(CipherError::InvalidKey(_))));
+    }
+
+    #[test]
+    fn test_subkeys_are_distinct_and_deterministic() {
+        let manager = EncryptionManager::new(CipherType::XOR, "passphrase").unwrap();
+        let keys = manager.subkeys().unwrap();
+        assert_ne!(keys.encryption, keys.mac);
+        assert_eq!(keys.encryption, SubKeys::derive(b"passphrase").unwrap().encryption);
+        assert_ne!(keys.mac, SubKeys::derive(b"passphrasf").unwrap().mac);
+        
+        let bare = EncryptionManager::with_cipher(Box::new(XorCipher::new(b"k").unwrap()));
+        assert!(matches!(bare.subkeys(), Err(CipherError::EmptyKey)));
+    }
+
+    #[test]
+    fn test_derived_keys_replace_raw_passphrase() {
+        let original = b"Hello World";
+        let raw = EncryptionManager::new(CipherType::XOR, "pass").unwrap();
+        let derived = EncryptionManager::with_derived_keys(CipherType::XOR, b"pass").unwrap();
+        let encrypted = derived.encrypt(original).unwrap();
+        assert_ne!(encrypted, raw.encrypt(original).unwrap());
+        assert_eq!(derived.decrypt(&encrypted).unwrap(), original);
+        
+        // Vigenère accepts arbitrary key bytes / Vigenère acepta bytes de clave arbitrarios
+        let vigenere = EncryptionManager::with_derived_keys(CipherType::Vigenere, b"pass").unwrap();
+        assert_eq!(vigenere.decrypt(&vigenere.encrypt(original).unwrap()).unwrap(), original);
+    }
+
+    #[test]
+    fn test_encrypt_then_mac_round_trip_and_tamper() {
+        let manager = EncryptionManager::with_derived_keys(CipherType::Vigenere, b"KEY").unwrap();
+        let original = b"Attack at dawn";
+        let mut sealed = manager.seal_with_mac(original).unwrap();
+        assert_eq!(sealed.len(), original.len() + 32);
+        assert_eq!(manager.open_with_mac(&sealed).unwrap(), original);
+        
+        sealed[0] ^= 1;
+        assert!(matches!(manager.open_with_mac(&sealed), Err(CipherError::AuthenticationFailed)));
+        assert!(matches!(manager.open_with_mac(&sealed[..10]), Err(CipherError::MalformedInput { .. })));
+    }
+
+    #[test]
+    fn test_from_password_reproduces_key_with_recorded_salt() {
+        use crate::kdf::{Argon2Params, KdfParams};
+        
+        let params = KdfParams::Argon2id(Argon2Params { memory_kib: 32, iterations: 1, parallelism: 1 });
+        let algorithm = CipherType::AesGcm { key: vec![0; 16].into(), nonce: [9; 12] };
+        let sender = EncryptionManager::from_password(algorithm.clone(), "hunter2", KeyDerivation::new(params).unwrap()).unwrap();
+        let original = b"Password protected";
+        let encrypted = sender.encrypt(original).unwrap();
+        
+        // The embedded zero key must have been replaced / La clave cero incluida debe haberse reemplazado
+        let zero_key = EncryptionManager::new(algorithm.clone(), "").unwrap();
+        assert_ne!(encrypted, zero_key.encrypt(original).unwrap());
+        
+        let recorded = sender.key_derivation().unwrap();
+        let replay = KeyDerivation::with_salt(recorded.params(), recorded.salt());
+        let receiver = EncryptionManager::from_password(algorithm.clone(), "hunter2", replay.clone()).unwrap();
+        assert_eq!(receiver.decrypt(&encrypted).unwrap(), original);
+        
+        let wrong = EncryptionManager::from_password(algorithm, "hunter3", replay).unwrap();
+        assert!(matches!(wrong.decrypt(&encrypted), Err(CipherError::AuthenticationFailed)));
+        assert!(EncryptionManager::new(CipherType::XOR, "k").unwrap().key_derivation().is_none());
+    }
+
+    #[test]
+    fn test_random_keys_round_trip_for_every_cipher_type() {
+        let mut rng = SecureRandom::new().unwrap();
+        let algorithms = [
+            CipherType::Caesar(0),
+            CipherType::XOR,
+            CipherType::Vigenere,
+            CipherType::Enigma(EnigmaSettings::default()),
+            CipherType::Affine { a: 1, b: 0 },
+            CipherType::Atbash,
+            CipherType::Playfair,
+            CipherType::Hill { matrix: "HILL".to_string() },
+            CipherType::RailFence(2),
+            CipherType::Columnar,
+            CipherType::ChaCha20Poly1305 { key: SecretKey::new(&[0; 32]), nonce: [0; 12] },
+            CipherType::AesEcb { key: vec![0; 16].into() },
+            CipherType::AesCbc { key: vec![0; 24].into(), iv: [0; 16] },
+            CipherType::AesCtr { key: Vec::new().into(), counter: [0; 16] },
+            CipherType::AesGcm { key: vec![0; 32].into(), nonce: [0; 12] },
+        ];
+        let original = b"Randomly keyed message";
+        for algorithm in algorithms {
+            let (first, key) = algorithm.clone().with_random_key(&mut rng);
+            let (second, other_key) = algorithm.with_random_key(&mut rng);
+            let expected = first.round_trip(original);
+            let manager = EncryptionManager::new(first, &key).unwrap();
+            let encrypted = manager.encrypt(original).unwrap();
+            assert_eq!(manager.decrypt(&encrypted).unwrap(), expected);
+            
+            // Small key spaces may repeat, and Atbash has no key / Los espacios de clave pequeños pueden repetirse, y Atbash no tiene clave
+            if !["Caesar", "Affine", "Atbash", "RailFence"].contains(&manager.algorithm_name()) {
+                let other = EncryptionManager::new(second, &other_key).unwrap();
+                assert_ne!(other.encrypt(original).unwrap(), encrypted);
+            }
+        }
+    }
+
+    fn temp_paths(name: &str) -> [std::path::PathBuf; 3] {
+        let dir = std::env::temp_dir();
+        ["input.txt", "encrypted.bin", "decrypted.txt"].map(|suffix| dir.join(format!("xyz_example2_{}_{}", name, suffix)))
+    }
+
+    #[test]
+    fn test_container_detects_algorithm_wrong_key_and_corruption() {
+        let [input, encrypted, decrypted] = temp_paths("container");
+        let [input, encrypted, decrypted] = [&input, &encrypted, &decrypted].map(|p| p.to_str().unwrap().to_string());
+        let content = b"Self-describing file";
+        fs::write(&input, content).unwrap();
+        
+        let sender = EncryptionManager::new(CipherType::AesGcm { key: vec![0; 16].into(), nonce: [0; 12] }, "shared secret").unwrap();
+        sender.encrypt_file(&input, &encrypted).unwrap();
+        assert!(container::is_container(&fs::read(&encrypted).unwrap()));
+        
+        // Any algorithm works for the receiver: the header decides / Cualquier algoritmo sirve: decide la cabecera
+        let receiver = EncryptionManager::new(CipherType::XOR, "shared secret").unwrap();
+        receiver.decrypt_file(&encrypted, &decrypted).unwrap();
+        assert_eq!(fs::read(&decrypted).unwrap(), content);
+        
+        let wrong = EncryptionManager::new(CipherType::XOR, "wrong secret").unwrap();
+        assert!(matches!(wrong.decrypt_file(&encrypted, &decrypted), Err(CipherError::AuthenticationFailed)));
+        
+        let mut corrupted = fs::read(&encrypted).unwrap();
+        corrupted[30] ^= 0x40;
+        fs::write(&encrypted, &corrupted).unwrap();
+        assert!(receiver.decrypt_file(&encrypted, &decrypted).is_err());
+        
+        for path in [input, encrypted, decrypted] {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    #[test]
+    fn test_files_can_be_encrypted_and_decrypted_in_place() {
+        let [path, ..] = temp_paths("in_place");
+        let path = path.to_str().unwrap();
+        let content = b"Encrypted over itself, then back";
+        fs::write(path, content).unwrap();
+        
+        let manager = EncryptionManager::new(CipherType::ChaCha20Poly1305 { key: vec![9; 32].into(), nonce: [0; 12] }, "").unwrap();
+        manager.encrypt_file(path, path).unwrap();
+        let sealed = fs::read(path).unwrap();
+        assert!(container::is_container(&sealed));
+        
+        // A failed decryption leaves the container as it was / Un descifrado fallido deja el contenedor como estaba
+        let wrong = EncryptionManager::new(CipherType::ChaCha20Poly1305 { key: vec![8; 32].into(), nonce: [0; 12] }, "").unwrap();
+        assert!(matches!(wrong.decrypt_file(path, path), Err(CipherError::AuthenticationFailed)));
+        assert_eq!(fs::read(path).unwrap(), sealed);
+        
+        manager.decrypt_file(path, path).unwrap();
+        assert_eq!(fs::read(path).unwrap(), content);
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_container_password_and_legacy_modes() {
+        let [input, encrypted, decrypted] = temp_paths("password");
+        let [input, encrypted, decrypted] = [&input, &encrypted, &decrypted].map(|p| p.to_str().unwrap().to_string());
+        let content = b"Password protected file";
+        fs::write(&input, content).unwrap();
+        
+        let kdf = KeyDerivation::new(KdfParams::Pbkdf2Sha256 { iterations: 10 }).unwrap();
+        let sender = EncryptionManager::from_password(CipherType::Vigenere, "open sesame", kdf).unwrap();
+        sender.encrypt_file(&input, &encrypted).unwrap();
+        sender.decrypt_file(&encrypted, &decrypted).unwrap();
+        assert_eq!(fs::read(&decrypted).unwrap(), content);
+        
+        EncryptionManager::decrypt_file_with_password("open sesame", &encrypted, &decrypted).unwrap();
+        assert_eq!(fs::read(&decrypted).unwrap(), content);
+        assert!(matches!(
+            EncryptionManager::decrypt_file_with_password("open sesamo", &encrypted, &decrypted),
+            Err(CipherError::AuthenticationFailed)
+        ));
+        let other = EncryptionManager::new(CipherType::Vigenere, "open sesame").unwrap();
+        assert!(matches!(other.decrypt_file(&encrypted, &decrypted), Err(CipherError::InvalidKey(_))));
+        
+        // Headerless files need the explicit legacy mode / Los archivos sin cabecera necesitan el modo heredado
+        let legacy = EncryptionManager::new(CipherType::Caesar(3), "").unwrap();
+        legacy.encrypt_file_legacy(&input, &encrypted).unwrap();
+        assert!(matches!(legacy.decrypt_file(&encrypted, &decrypted), Err(CipherError::MalformedInput { position: 0, .. })));
+        legacy.decrypt_file_legacy(&encrypted, &decrypted).unwrap();
+        assert_eq!(fs::read(&decrypted).unwrap(), content);
+        
+        // Keyless Caesar is keyed by its shift, which stays out of the header
+        // César sin clave usa su desplazamiento como clave, que no se escribe en la cabecera
+        legacy.encrypt_file(&input, &encrypted).unwrap();
+        let header = ContainerHeader::parse(&fs::read(&encrypted).unwrap()).unwrap().0;
+        assert_eq!((header.integrity, header.algorithm.parameter_key().unwrap().expose()), (Integrity::Hmac, &[0][..]));
+        legacy.decrypt_file(&encrypted, &decrypted).unwrap();
+        assert_eq!(fs::read(&decrypted).unwrap(), content);
+        for other in [CipherType::Caesar(4), CipherType::Atbash] {
+            let other = EncryptionManager::new(other, "").unwrap();
+            assert!(other.decrypt_file(&encrypted, &decrypted).is_err());
+        }
+        
+        for path in [input, encrypted, decrypted] {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    #[test]
+    fn test_enigma_settings_stay_out_of_the_container() {
+        let [input, encrypted, decrypted] = temp_paths("enigma");
+        let [input, encrypted, decrypted] = [&input, &encrypted, &decrypted].map(|p| p.to_str().unwrap().to_string());
+        let content = b"Wetterbericht fuer heute";
+        fs::write(&input, content).unwrap();
+        
+        let settings = "II IV V B BUL BLA AV BS CG DL FU HZ IN KM OW RX";
+        let sender = EncryptionManager::new(CipherType::Enigma(settings.parse().unwrap()), "").unwrap();
+        sender.encrypt_file(&input, &encrypted).unwrap();
+        let sealed = fs::read(&encrypted).unwrap();
+        assert!(!sealed.windows(11).any(|w| w == b"AV BS CG DL"));
+        
+        // Only the same settings open it / Solo la misma configuración lo abre
+        let unrelated = EncryptionManager::new(CipherType::Atbash, "").unwrap();
+        assert!(unrelated.decrypt_file(&encrypted, &decrypted).is_err());
+        let other = EncryptionManager::new(CipherType::Enigma(EnigmaSettings::default()), "").unwrap();
+        assert!(matches!(other.decrypt_file(&encrypted, &decrypted), Err(CipherError::AuthenticationFailed)));
+        let receiver = EncryptionManager::new(CipherType::Enigma(settings.parse().unwrap()), "").unwrap();
+        receiver.decrypt_file(&encrypted, &decrypted).unwrap();
+        assert_eq!(fs::read(&decrypted).unwrap(), content);
+        
+        for path in [input, encrypted, decrypted] {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    #[test]
+    fn test_version_1_containers_still_decrypt() {
+        let [input, encrypted, decrypted] = temp_paths("version1");
+        let content = b"Written before chunked containers";
+        let header = ContainerHeader {
+            version: container::VERSION_WHOLE,
+            algorithm: CipherType::AesCbc { key: vec![0; 16].into(), iv: [4; 16] },
+            kdf: None,
+            salt: Vec::new(),
+            integrity: Integrity::Hmac,
+        };
+        let (cipher, subkeys) = container_cipher(&header.algorithm, b"old key").unwrap();
+        let sealed = container::seal(&header, &cipher.encrypt(content).unwrap(), Some(&subkeys.unwrap().mac)).unwrap();
+        fs::write(&encrypted, sealed).unwrap();
+        
+        let manager = EncryptionManager::new(CipherType::XOR, "old key").unwrap();
+        manager.decrypt_file(encrypted.to_str().unwrap(), decrypted.to_str().unwrap()).unwrap();
+        assert_eq!(fs::read(&decrypted).unwrap(), content);
+        
+        for path in [input, encrypted, decrypted] {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    #[test]
+    fn test_debug_never_shows_keys() {
+        let aes = CipherType::AesGcm { key: vec![0xab; 16].into(), nonce: [7; 12] };
+        assert_eq!(format!("{:?}", aes), "AesGcm { key: [REDACTED; 16 bytes], nonce: [7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7] }");
+        assert_eq!(format!("{:?}", CipherType::Caesar(3)), "Caesar([REDACTED])");
+        assert_eq!(format!("{:?}", CipherType::Affine { a: 5, b: 8 }), "Affine { a: [REDACTED], b: [REDACTED] }");
+        let hill = CipherType::Hill { matrix: "HILL".to_string() };
+        assert_eq!(format!("{:?}", hill), "Hill { matrix: [REDACTED] }");
+        let settings = EnigmaSettings { positions: [1, 2, 3], ..EnigmaSettings::default() };
+        assert_eq!(format!("{:?}", CipherType::Enigma(settings.clone())), "Enigma([REDACTED])");
+        assert_eq!(format!("{:?}", CipherType::RailFence(7)), "RailFence([REDACTED])");
+        
+        assert_eq!(format!("{:?}", AffineCipher::new(5, 8).unwrap()), "AffineCipher { a: [REDACTED], b: [REDACTED], .. }");
+        assert_eq!(format!("{:?}", HillCipher::new(b"HILL").unwrap()), "HillCipher { size: 2, matrix: [REDACTED], .. }");
+        assert_eq!(format!("{:?}", PlayfairCipher::new(b"MONARCHY").unwrap()), "PlayfairCipher { square: [REDACTED], .. }");
+        assert_eq!(format!("{:?}", EnigmaCipher::new(settings).unwrap()), "EnigmaCipher { settings: [REDACTED], .. }");
+        assert_eq!(format!("{:?}", RailFenceCipher::new(3).unwrap()), "RailFenceCipher { rails: [REDACTED] }");
+        assert!(format!("{:?}", CaesarCipher::new(3).unwrap()).starts_with("CaesarCipher { shift: [REDACTED], alphabet: "));
+        let xor = XorCipher::new(b"hunter2").unwrap();
+        assert_eq!(format!("{:?}", xor), "XorCipher { key: SecretKey([REDACTED; 7 bytes]), start: 0 }");
+    }
+
+    #[test]
+    fn test_cipher_type_keys_are_secret_keys() {
+        let aes = CipherType::AesCtr { key: vec![5; 16].into(), counter: [0; 16] };
+        let copy = aes.clone();
+        match (&aes, &copy) {
+            (CipherType::AesCtr { key, .. }, CipherType::AesCtr { key: copied, .. }) => {
+                assert_eq!(key.expose(), copied.expose());
+                assert_ne!(key.expose().as_ptr(), copied.expose().as_ptr());
+            }
+            _ => unreachable!(),
+        }
+        
+        let short = CipherType::ChaCha20Poly1305 { key: SecretKey::new(&[1; 16]), nonce: [0; 12] };
+        assert!(matches!(EncryptionManager::new(short, ""), Err(CipherError::InvalidKey(_))));
+    }
+
+    #[test]
+    fn test_property_every_cipher_type_round_trips() {
+        use crate::property::{check, ensure_eq, Bytes, CipherTypes};
+        
+        check("every cipher type round-trips", 3000, &(CipherTypes, Bytes { min: 0, max: 512 }), |((algorithm, key), data)| {
+            let manager = EncryptionManager::new(algorithm.clone(), key)?;
+            ensure_eq(manager.decrypt(&manager.encrypt(data)?)?, algorithm.round_trip(data))
+        });
+    }
+
+    #[test]
+    fn test_property_derived_keys_and_mac_round_trip() {
+        use crate::property::{check, ensure_eq, Bytes, CipherTypes};
+        
+        let strategy = (CipherTypes, Bytes { min: 0, max: 256 }, Bytes { min: 1, max: 64 });
+        check("derived keys and MAC round-trip", 1000, &strategy, |((algorithm, _), data, master)| {
+            let manager = EncryptionManager::with_derived_keys(algorithm.clone(), master)?;
+            ensure_eq(manager.decrypt(&manager.encrypt(data)?)?, algorithm.round_trip(data))?;
+            ensure_eq(manager.open_with_mac(&manager.seal_with_mac(data)?)?, algorithm.round_trip(data))
+        });
+    }
+
+    #[test]
+    fn test_property_every_file_mode_round_trips() {
+        use crate::property::{check, ensure_eq, Bytes, CipherTypes, Ints};
+        
+        let [input, encrypted, decrypted] = temp_paths("property");
+        let [input, encrypted, decrypted] = [&input, &encrypted, &decrypted].map(|p| p.to_str().unwrap().to_string());
+        // 0: container, 1: password container, 2: legacy / 0: contenedor, 1: contenedor con contraseña, 2: heredado
+        let strategy = (CipherTypes, Bytes { min: 0, max: 1024 }, Ints { lo: 0, hi: 2 });
+        check("every file mode round-trips", 300, &strategy, |((algorithm, key), data, mode)| {
+            fs::write(&input, data)?;
+            match mode {
+                0 => {
+                    let manager = EncryptionManager::new(algorithm.clone(), key)?;
+                    manager.encrypt_file(&input, &encrypted)?;
+                    manager.decrypt_file(&encrypted, &decrypted)?;
+                }
+                1 => {
+                    let kdf = KeyDerivation::new(KdfParams::Pbkdf2Sha256 { iterations: 1 })?;
+                    let manager = EncryptionManager::from_password(algorithm.clone(), "open sesame", kdf)?;
+                    manager.encrypt_file(&input, &encrypted)?;
+                    EncryptionManager::decrypt_file_with_password("open sesame", &encrypted, &decrypted)?;
+                }
+                _ => {
+                    let manager = EncryptionManager::new(algorithm.clone(), key)?;
+                    manager.encrypt_file_legacy(&input, &encrypted)?;
+                    manager.decrypt_file_legacy(&encrypted, &decrypted)?;
+                }
+            }
+            // Inputs stay within one chunk / Las entradas caben en un fragmento
+            ensure_eq(fs::read(&decrypted)?, algorithm.round_trip(data))
+        });
+        
+        for path in [input, encrypted, decrypted] {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
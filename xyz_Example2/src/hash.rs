@@ -0,0 +1,540 @@
+// SHA-2 Hash Family (FIPS 180-4): SHA-224, SHA-256, SHA-384, SHA-512
+// Familia de Hash SHA-2 (FIPS 180-4): SHA-224, SHA-256, SHA-384, SHA-512
+
+use std::fs::File;
+use std::io::{BufReader, Read};
+
+use crate::encoding::bytes_to_hex;
+use crate::error::CipherError;
+
+/// Incremental hash function: feed data with `update`, then `finalize`
+/// Función hash incremental: alimentar datos con `update` y luego `finalize`
+pub trait Digest: Clone {
+    /// Algorithm name / Nombre del algoritmo
+    const NAME: &'static str;
+    /// Digest size in bytes / Tamaño del resumen en bytes
+    const OUTPUT_LEN: usize;
+    /// Internal block size in bytes (needed by HMAC) / Tamaño de bloque interno en bytes (lo necesita HMAC)
+    const BLOCK_LEN: usize;
+
+    fn new() -> Self;
+
+    fn update(&mut self, data: &[u8]);
+
+    fn finalize(self) -> Vec<u8>;
+
+    /// One-shot digest / Resumen en un solo paso
+    fn digest(data: &[u8]) -> Vec<u8> {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+/// One-shot digest printed as lowercase hex / Resumen en un solo paso como hex en minúsculas
+pub fn digest_hex<D: Digest>(data: &[u8]) -> String {
+    bytes_to_hex(&D::digest(data))
+}
+
+/// Hash everything a reader yields in fixed-size chunks
+/// Calcular el hash de todo lo que produce un lector en fragmentos de tamaño fijo
+pub fn hash_reader<D: Digest, R: Read>(mut reader: R) -> Result<Vec<u8>, CipherError> {
+    let mut hasher = D::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(hasher.finalize())
+}
+
+/// Hash a file without loading it into memory / Calcular el hash de un archivo sin cargarlo en memoria
+pub fn hash_file<D: Digest>(path: &str) -> Result<Vec<u8>, CipherError> {
+    hash_reader::<D, _>(BufReader::new(File::open(path)?))
+}
+
+// === SHA-256 / SHA-224 ===
+
+// First 32 bits of the fractional parts of the cube roots of the first 64 primes
+// Primeros 32 bits de la parte fraccionaria de las raíces cúbicas de los primeros 64 primos
+const K256: [u32; 64] = [
+    0x428a_2f98, 0x7137_4491, 0xb5c0_fbcf, 0xe9b5_dba5,
+    0x3956_c25b, 0x59f1_11f1, 0x923f_82a4, 0xab1c_5ed5,
+    0xd807_aa98, 0x1283_5b01, 0x2431_85be, 0x550c_7dc3,
+    0x72be_5d74, 0x80de_b1fe, 0x9bdc_06a7, 0xc19b_f174,
+    0xe49b_69c1, 0xefbe_4786, 0x0fc1_9dc6, 0x240c_a1cc,
+    0x2de9_2c6f, 0x4a74_84aa, 0x5cb0_a9dc, 0x76f9_88da,
+    0x983e_5152, 0xa831_c66d, 0xb003_27c8, 0xbf59_7fc7,
+    0xc6e0_0bf3, 0xd5a7_9147, 0x06ca_6351, 0x1429_2967,
+    0x27b7_0a85, 0x2e1b_2138, 0x4d2c_6dfc, 0x5338_0d13,
+    0x650a_7354, 0x766a_0abb, 0x81c2_c92e, 0x9272_2c85,
+    0xa2bf_e8a1, 0xa81a_664b, 0xc24b_8b70, 0xc76c_51a3,
+    0xd192_e819, 0xd699_0624, 0xf40e_3585, 0x106a_a070,
+    0x19a4_c116, 0x1e37_6c08, 0x2748_774c, 0x34b0_bcb5,
+    0x391c_0cb3, 0x4ed8_aa4a, 0x5b9c_ca4f, 0x682e_6ff3,
+    0x748f_82ee, 0x78a5_636f, 0x84c8_7814, 0x8cc7_0208,
+    0x90be_fffa, 0xa450_6ceb, 0xbef9_a3f7, 0xc671_78f2,
+];
+
+const H256: [u32; 8] = [
+    0x6a09_e667, 0xbb67_ae85, 0x3c6e_f372, 0xa54f_f53a,
+    0x510e_527f, 0x9b05_688c, 0x1f83_d9ab, 0x5be0_cd19,
+];
+
+const H224: [u32; 8] = [
+    0xc105_9ed8, 0x367c_d507, 0x3070_dd17, 0xf70e_5939,
+    0xffc0_0b31, 0x6858_1511, 0x64f9_8fa7, 0xbefa_4fa4,
+];
+
+/// Shared SHA-256 compression state / Estado de compresión compartido de SHA-256
+#[derive(Clone)]
+struct Sha256Core {
+    state: [u32; 8],
+    buffer: [u8; 64],
+    buffered: usize,
+    length: u64,
+}
+
+impl Sha256Core {
+    fn new(iv: [u32; 8]) -> Self {
+        Self { state: iv, buffer: [0; 64], buffered: 0, length: 0 }
+    }
+
+    fn compress(&mut self, block: &[u8]) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(K256[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (s, v) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *s = s.wrapping_add(v);
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.length = self.length.wrapping_add(data.len() as u64);
+        if self.buffered > 0 {
+            let take = (64 - self.buffered).min(data.len());
+            self.buffer[self.buffered..self.buffered + take].copy_from_slice(&data[..take]);
+            self.buffered += take;
+            data = &data[take..];
+            if self.buffered < 64 {
+                return;
+            }
+            let block = self.buffer;
+            self.compress(&block);
+            self.buffered = 0;
+        }
+        let mut blocks = data.chunks_exact(64);
+        for block in &mut blocks {
+            self.compress(block);
+        }
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+    }
+
+    fn finalize(mut self, output_len: usize) -> Vec<u8> {
+        let bit_length = self.length.wrapping_mul(8);
+        // 0x80, zeros, then the 64-bit length / 0x80, ceros y luego la longitud de 64 bits
+        let pad_len = if self.buffered < 56 { 56 - self.buffered } else { 120 - self.buffered };
+        let mut padding = vec![0u8; pad_len + 8];
+        padding[0] = 0x80;
+        padding[pad_len..].copy_from_slice(&bit_length.to_be_bytes());
+        self.update(&padding);
+
+        self.state.iter().flat_map(|w| w.to_be_bytes()).take(output_len).collect()
+    }
+}
+
+/// SHA-256 hasher / Hasher SHA-256
+#[derive(Clone)]
+pub struct Sha256(Sha256Core);
+
+/// SHA-224 hasher (SHA-256 with a different IV, truncated) / Hasher SHA-224
+#[derive(Clone)]
+pub struct Sha224(Sha256Core);
+
+impl Digest for Sha256 {
+    const NAME: &'static str = "SHA-256";
+    const OUTPUT_LEN: usize = 32;
+    const BLOCK_LEN: usize = 64;
+
+    fn new() -> Self {
+        Self(Sha256Core::new(H256))
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.0.finalize(Self::OUTPUT_LEN)
+    }
+}
+
+impl Digest for Sha224 {
+    const NAME: &'static str = "SHA-224";
+    const OUTPUT_LEN: usize = 28;
+    const BLOCK_LEN: usize = 64;
+
+    fn new() -> Self {
+        Self(Sha256Core::new(H224))
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.0.finalize(Self::OUTPUT_LEN)
+    }
+}
+
+// === SHA-512 / SHA-384 ===
+
+// First 64 bits of the fractional parts of the cube roots of the first 80 primes
+// Primeros 64 bits de la parte fraccionaria de las raíces cúbicas de los primeros 80 primos
+const K512: [u64; 80] = [
+    0x428a_2f98_d728_ae22, 0x7137_4491_23ef_65cd,
+    0xb5c0_fbcf_ec4d_3b2f, 0xe9b5_dba5_8189_dbbc,
+    0x3956_c25b_f348_b538, 0x59f1_11f1_b605_d019,
+    0x923f_82a4_af19_4f9b, 0xab1c_5ed5_da6d_8118,
+    0xd807_aa98_a303_0242, 0x1283_5b01_4570_6fbe,
+    0x2431_85be_4ee4_b28c, 0x550c_7dc3_d5ff_b4e2,
+    0x72be_5d74_f27b_896f, 0x80de_b1fe_3b16_96b1,
+    0x9bdc_06a7_25c7_1235, 0xc19b_f174_cf69_2694,
+    0xe49b_69c1_9ef1_4ad2, 0xefbe_4786_384f_25e3,
+    0x0fc1_9dc6_8b8c_d5b5, 0x240c_a1cc_77ac_9c65,
+    0x2de9_2c6f_592b_0275, 0x4a74_84aa_6ea6_e483,
+    0x5cb0_a9dc_bd41_fbd4, 0x76f9_88da_8311_53b5,
+    0x983e_5152_ee66_dfab, 0xa831_c66d_2db4_3210,
+    0xb003_27c8_98fb_213f, 0xbf59_7fc7_beef_0ee4,
+    0xc6e0_0bf3_3da8_8fc2, 0xd5a7_9147_930a_a725,
+    0x06ca_6351_e003_826f, 0x1429_2967_0a0e_6e70,
+    0x27b7_0a85_46d2_2ffc, 0x2e1b_2138_5c26_c926,
+    0x4d2c_6dfc_5ac4_2aed, 0x5338_0d13_9d95_b3df,
+    0x650a_7354_8baf_63de, 0x766a_0abb_3c77_b2a8,
+    0x81c2_c92e_47ed_aee6, 0x9272_2c85_1482_353b,
+    0xa2bf_e8a1_4cf1_0364, 0xa81a_664b_bc42_3001,
+    0xc24b_8b70_d0f8_9791, 0xc76c_51a3_0654_be30,
+    0xd192_e819_d6ef_5218, 0xd699_0624_5565_a910,
+    0xf40e_3585_5771_202a, 0x106a_a070_32bb_d1b8,
+    0x19a4_c116_b8d2_d0c8, 0x1e37_6c08_5141_ab53,
+    0x2748_774c_df8e_eb99, 0x34b0_bcb5_e19b_48a8,
+    0x391c_0cb3_c5c9_5a63, 0x4ed8_aa4a_e341_8acb,
+    0x5b9c_ca4f_7763_e373, 0x682e_6ff3_d6b2_b8a3,
+    0x748f_82ee_5def_b2fc, 0x78a5_636f_4317_2f60,
+    0x84c8_7814_a1f0_ab72, 0x8cc7_0208_1a64_39ec,
+    0x90be_fffa_2363_1e28, 0xa450_6ceb_de82_bde9,
+    0xbef9_a3f7_b2c6_7915, 0xc671_78f2_e372_532b,
+    0xca27_3ece_ea26_619c, 0xd186_b8c7_21c0_c207,
+    0xeada_7dd6_cde0_eb1e, 0xf57d_4f7f_ee6e_d178,
+    0x06f0_67aa_7217_6fba, 0x0a63_7dc5_a2c8_98a6,
+    0x113f_9804_bef9_0dae, 0x1b71_0b35_131c_471b,
+    0x28db_77f5_2304_7d84, 0x32ca_ab7b_40c7_2493,
+    0x3c9e_be0a_15c9_bebc, 0x431d_67c4_9c10_0d4c,
+    0x4cc5_d4be_cb3e_42b6, 0x597f_299c_fc65_7e2a,
+    0x5fcb_6fab_3ad6_faec, 0x6c44_198c_4a47_5817,
+];
+
+const H512: [u64; 8] = [
+    0x6a09_e667_f3bc_c908, 0xbb67_ae85_84ca_a73b,
+    0x3c6e_f372_fe94_f82b, 0xa54f_f53a_5f1d_36f1,
+    0x510e_527f_ade6_82d1, 0x9b05_688c_2b3e_6c1f,
+    0x1f83_d9ab_fb41_bd6b, 0x5be0_cd19_137e_2179,
+];
+
+const H384: [u64; 8] = [
+    0xcbbb_9d5d_c105_9ed8, 0x629a_292a_367c_d507,
+    0x9159_015a_3070_dd17, 0x152f_ecd8_f70e_5939,
+    0x6733_2667_ffc0_0b31, 0x8eb4_4a87_6858_1511,
+    0xdb0c_2e0d_64f9_8fa7, 0x47b5_481d_befa_4fa4,
+];
+
+/// Shared SHA-512 compression state / Estado de compresión compartido de SHA-512
+#[derive(Clone)]
+struct Sha512Core {
+    state: [u64; 8],
+    buffer: [u8; 128],
+    buffered: usize,
+    length: u128,
+}
+
+impl Sha512Core {
+    fn new(iv: [u64; 8]) -> Self {
+        Self { state: iv, buffer: [0; 128], buffered: 0, length: 0 }
+    }
+
+    fn compress(&mut self, block: &[u8]) {
+        let mut w = [0u64; 80];
+        for (i, word) in block.chunks_exact(8).enumerate() {
+            w[i] = u64::from_be_bytes(word.try_into().unwrap());
+        }
+        for i in 16..80 {
+            let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+            let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for i in 0..80 {
+            let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(K512[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (s, v) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *s = s.wrapping_add(v);
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.length = self.length.wrapping_add(data.len() as u128);
+        if self.buffered > 0 {
+            let take = (128 - self.buffered).min(data.len());
+            self.buffer[self.buffered..self.buffered + take].copy_from_slice(&data[..take]);
+            self.buffered += take;
+            data = &data[take..];
+            if self.buffered < 128 {
+                return;
+            }
+            let block = self.buffer;
+            self.compress(&block);
+            self.buffered = 0;
+        }
+        let mut blocks = data.chunks_exact(128);
+        for block in &mut blocks {
+            self.compress(block);
+        }
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+    }
+
+    fn finalize(mut self, output_len: usize) -> Vec<u8> {
+        let bit_length = self.length.wrapping_mul(8);
+        // 0x80, zeros, then the 128-bit length / 0x80, ceros y luego la longitud de 128 bits
+        let pad_len = if self.buffered < 112 { 112 - self.buffered } else { 240 - self.buffered };
+        let mut padding = vec![0u8; pad_len + 16];
+        padding[0] = 0x80;
+        padding[pad_len..].copy_from_slice(&bit_length.to_be_bytes());
+        self.update(&padding);
+
+        self.state.iter().flat_map(|w| w.to_be_bytes()).take(output_len).collect()
+    }
+}
+
+/// SHA-512 hasher / Hasher SHA-512
+#[derive(Clone)]
+pub struct Sha512(Sha512Core);
+
+/// SHA-384 hasher (SHA-512 with a different IV, truncated) / Hasher SHA-384
+#[derive(Clone)]
+pub struct Sha384(Sha512Core);
+
+impl Digest for Sha512 {
+    const NAME: &'static str = "SHA-512";
+    const OUTPUT_LEN: usize = 64;
+    const BLOCK_LEN: usize = 128;
+
+    fn new() -> Self {
+        Self(Sha512Core::new(H512))
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.0.finalize(Self::OUTPUT_LEN)
+    }
+}
+
+impl Digest for Sha384 {
+    const NAME: &'static str = "SHA-384";
+    const OUTPUT_LEN: usize = 48;
+    const BLOCK_LEN: usize = 128;
+
+    fn new() -> Self {
+        Self(Sha512Core::new(H384))
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.0.finalize(Self::OUTPUT_LEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWO_BLOCK_256: &[u8] = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+    const TWO_BLOCK_512: &[u8] = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+
+    // NIST FIPS 180-4 example vectors / Vectores de ejemplo de NIST FIPS 180-4
+    #[test]
+    fn test_sha224_short_messages() {
+        assert_eq!(digest_hex::<Sha224>(b""), "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f");
+        assert_eq!(digest_hex::<Sha224>(b"abc"), "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7");
+        assert_eq!(digest_hex::<Sha224>(TWO_BLOCK_256), "75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525");
+    }
+
+    #[test]
+    fn test_sha224_million_a() {
+        let mut hasher = Sha224::new();
+        for _ in 0..1000 {
+            hasher.update(&[b'a'; 1000]);
+        }
+        assert_eq!(bytes_to_hex(&hasher.finalize()), "20794655980c91d8bbb4c1ea97618a4bf03f42581948b2ee4ee7ad67");
+    }
+
+    #[test]
+    fn test_sha256_short_messages() {
+        assert_eq!(digest_hex::<Sha256>(b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(digest_hex::<Sha256>(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(digest_hex::<Sha256>(TWO_BLOCK_256), "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1");
+    }
+
+    #[test]
+    fn test_sha256_million_a() {
+        let mut hasher = Sha256::new();
+        for _ in 0..1000 {
+            hasher.update(&[b'a'; 1000]);
+        }
+        assert_eq!(bytes_to_hex(&hasher.finalize()), "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0");
+    }
+
+    #[test]
+    fn test_sha384_short_messages() {
+        assert_eq!(
+            digest_hex::<Sha384>(b""),
+            "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da\
+             274edebfe76f65fbd51ad2f14898b95b"
+        );
+        assert_eq!(
+            digest_hex::<Sha384>(b"abc"),
+            "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed\
+             8086072ba1e7cc2358baeca134c825a7"
+        );
+        assert_eq!(
+            digest_hex::<Sha384>(TWO_BLOCK_512),
+            "09330c33f71147e83d192fc782cd1b4753111b173b3b05d22fa08086e3b0f712\
+             fcc7c71a557e2db966c3e9fa91746039"
+        );
+    }
+
+    #[test]
+    fn test_sha384_million_a() {
+        let mut hasher = Sha384::new();
+        for _ in 0..1000 {
+            hasher.update(&[b'a'; 1000]);
+        }
+        assert_eq!(
+            bytes_to_hex(&hasher.finalize()),
+            "9d0e1809716474cb086e834e310a4a1ced149e9c00f248527972cec5704c2a5b\
+             07b8b3dc38ecc4ebae97ddd87f3d8985"
+        );
+    }
+
+    #[test]
+    fn test_sha512_short_messages() {
+        assert_eq!(
+            digest_hex::<Sha512>(b""),
+            "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce\
+             47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"
+        );
+        assert_eq!(
+            digest_hex::<Sha512>(b"abc"),
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+        assert_eq!(
+            digest_hex::<Sha512>(TWO_BLOCK_512),
+            "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018\
+             501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909"
+        );
+    }
+
+    #[test]
+    fn test_sha512_million_a() {
+        let mut hasher = Sha512::new();
+        for _ in 0..1000 {
+            hasher.update(&[b'a'; 1000]);
+        }
+        assert_eq!(
+            bytes_to_hex(&hasher.finalize()),
+            "e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973eb\
+             de0ff244877ea60a4cb0432ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b"
+        );
+    }
+
+    #[test]
+    fn test_incremental_updates_match_one_shot() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        for split in [0, 1, 55, 56, 63, 64, 65, 111, 112, 128, 999] {
+            let mut a = Sha256::new();
+            a.update(&data[..split]);
+            a.update(&data[split..]);
+            assert_eq!(a.finalize(), Sha256::digest(&data));
+
+            let mut b = Sha512::new();
+            b.update(&data[..split]);
+            b.update(&data[split..]);
+            assert_eq!(b.finalize(), Sha512::digest(&data));
+        }
+    }
+
+    #[test]
+    fn test_hash_reader_streams_input() {
+        let data = vec![b'x'; 200_000];
+        let streamed = hash_reader::<Sha256, _>(&data[..]).unwrap();
+        assert_eq!(streamed, Sha256::digest(&data));
+    }
+
+    #[test]
+    fn test_hash_file_missing_is_io_error() {
+        assert!(matches!(hash_file::<Sha256>("definitely_missing_file.bin"), Err(CipherError::Io(_))));
+    }
+}
@@ -0,0 +1,254 @@
+// HMAC (RFC 2104) and HKDF (RFC 5869) over the crate's `Digest` trait
+// HMAC (RFC 2104) y HKDF (RFC 5869) sobre el trait `Digest` del crate
+
+use crate::error::CipherError;
+use crate::hash::Digest;
+use crate::secret::ct_eq;
+
+// === HMAC ===
+
+/// Keyed message authentication code generic over any `Digest`
+/// Código de autenticación de mensajes con clave, genérico sobre cualquier `Digest`
+#[derive(Clone)]
+pub struct Hmac<D: Digest> {
+    inner: D,
+    outer: D,
+}
+
+impl<D: Digest> Hmac<D> {
+    /// Keys longer than a block are hashed first / Las claves más largas que un bloque se resumen primero
+    pub fn new(key: &[u8]) -> Self {
+        let mut block_key = if key.len() > D::BLOCK_LEN { D::digest(key) } else { key.to_vec() };
+        block_key.resize(D::BLOCK_LEN, 0);
+
+        let mut inner = D::new();
+        inner.update(&block_key.iter().map(|b| b ^ 0x36).collect::<Vec<u8>>());
+        let mut outer = D::new();
+        outer.update(&block_key.iter().map(|b| b ^ 0x5c).collect::<Vec<u8>>());
+        Self { inner, outer }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        let mut outer = self.outer;
+        outer.update(&self.inner.finalize());
+        outer.finalize()
+    }
+
+    /// Recompute the tag and compare it without early exit
+    /// Recalcular la etiqueta y compararla sin salida anticipada
+    pub fn verify(self, tag: &[u8]) -> Result<(), CipherError> {
+        if !ct_eq(&self.finalize(), tag) {
+            return Err(CipherError::AuthenticationFailed);
+        }
+        Ok(())
+    }
+}
+
+/// One-shot HMAC / HMAC en un solo paso
+pub fn hmac<D: Digest>(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<D>::new(key);
+    mac.update(data);
+    mac.finalize()
+}
+
+// === HKDF ===
+
+/// HKDF with the pseudorandom key from the extract step already computed
+/// HKDF con la clave pseudoaleatoria del paso de extracción ya calculada
+#[derive(Clone)]
+pub struct Hkdf<D: Digest> {
+    prk: Vec<u8>,
+    _digest: std::marker::PhantomData<D>,
+}
+
+impl<D: Digest> Hkdf<D> {
+    /// Extract step; an empty salt means `OUTPUT_LEN` zero bytes
+    /// Paso de extracción; una sal vacía equivale a `OUTPUT_LEN` bytes cero
+    pub fn extract(salt: &[u8], ikm: &[u8]) -> Self {
+        let zeros;
+        let salt = if salt.is_empty() {
+            zeros = vec![0u8; D::OUTPUT_LEN];
+            &zeros[..]
+        } else {
+            salt
+        };
+        Self::from_prk(&hmac::<D>(salt, ikm))
+    }
+
+    /// Skip extraction when the key is already uniformly random
+    /// Omitir la extracción cuando la clave ya es uniformemente aleatoria
+    pub fn from_prk(prk: &[u8]) -> Self {
+        Self { prk: prk.to_vec(), _digest: std::marker::PhantomData }
+    }
+
+    pub fn prk(&self) -> &[u8] {
+        &self.prk
+    }
+
+    /// Expand step; at most 255 digest blocks can be produced
+    /// Paso de expansión; se pueden producir como máximo 255 bloques del resumen
+    pub fn expand(&self, info: &[u8], length: usize) -> Result<Vec<u8>, CipherError> {
+        if length > 255 * D::OUTPUT_LEN {
+            return Err(CipherError::InvalidParam {
+                name: "length".to_string(),
+                value: length.to_string(),
+            });
+        }
+
+        let mut okm = Vec::with_capacity(length);
+        let mut previous = Vec::new();
+        let mut counter = 1u8;
+        while okm.len() < length {
+            let mut mac = Hmac::<D>::new(&self.prk);
+            mac.update(&previous);
+            mac.update(info);
+            mac.update(&[counter]);
+            previous = mac.finalize();
+            let take = (length - okm.len()).min(previous.len());
+            okm.extend_from_slice(&previous[..take]);
+            counter = counter.wrapping_add(1);
+        }
+        Ok(okm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{bytes_to_hex, hex_to_bytes};
+    use crate::hash::{Sha224, Sha256, Sha384, Sha512};
+
+    fn hex(s: &str) -> Vec<u8> {
+        hex_to_bytes(s).unwrap()
+    }
+
+    fn check_all(key: &[u8], data: &[u8], expected: [&str; 4]) {
+        assert_eq!(bytes_to_hex(&hmac::<Sha224>(key, data)), expected[0]);
+        assert_eq!(bytes_to_hex(&hmac::<Sha256>(key, data)), expected[1]);
+        assert_eq!(bytes_to_hex(&hmac::<Sha384>(key, data)), expected[2]);
+        assert_eq!(bytes_to_hex(&hmac::<Sha512>(key, data)), expected[3]);
+    }
+
+    #[test]
+    fn test_hmac_rfc4231_case_1() {
+        check_all(&[0x0b; 20], b"Hi There", [
+            "896fb1128abbdf196832107cd49df33f47b4b1169912ba4f53684b22",
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+            "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59cfaea9ea9076ede7f4af152e8b2fa9cb6",
+            "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+        ]);
+    }
+
+    #[test]
+    fn test_hmac_rfc4231_case_2() {
+        check_all(b"Jefe", b"what do ya want for nothing?", [
+            "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44",
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649",
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+        ]);
+    }
+
+    #[test]
+    fn test_hmac_rfc4231_case_3() {
+        check_all(&[0xaa; 20], &[0xdd; 50], [
+            "7fb3cb3588c6c1f6ffa9694d7d6ad2649365b0c1f65d69d1ec8333ea",
+            "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+            "88062608d3e6ad8a0aa2ace014c8a86f0aa635d947ac9febe83ef4e55966144b2a5ab39dc13814b94e3ab6e101a34f27",
+            "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb",
+        ]);
+    }
+
+    #[test]
+    fn test_hmac_rfc4231_case_4() {
+        let key: Vec<u8> = (1..=25).collect();
+        check_all(&key, &[0xcd; 50], [
+            "6c11506874013cac6a2abc1bb382627cec6a90d86efc012de7afec5a",
+            "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+            "3e8a69b7783c25851933ab6290af6ca77a9981480850009cc5577c6e1f573b4e6801dd23c4a7d679ccf8a386c674cffb",
+            "b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3dba91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd",
+        ]);
+    }
+
+    #[test]
+    fn test_hmac_rfc4231_case_6_long_key() {
+        check_all(&[0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First", [
+            "95e9a0db962095adaebe9b2d6f0dbce2d499f112f2d2b7273fa6870e",
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+            "4ece084485813e9088d2c63a041bc5b44f9ef1012a2b588f3cd11f05033ac4c60c2ef6ab4030fe8296248df163f44952",
+            "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+        ]);
+    }
+
+    #[test]
+    fn test_hmac_rfc4231_case_7_long_key_and_data() {
+        let data = b"This is a test using a larger than block-size key and a larger than block-size data. \
+                     The key needs to be hashed before being used by the HMAC algorithm.";
+        check_all(&[0xaa; 131], data, [
+            "3a854166ac5d9f023f54d517d0b39dbd946770db9c2b95c9f6f565d1",
+            "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+            "6617178e941f020d351e2f254e8fd32c602420feb0b8fb9adccebb82461e99c5a678cc31e799176d3860e6110c46523e",
+            "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58",
+        ]);
+    }
+
+    #[test]
+    fn test_hmac_verify() {
+        let tag = hmac::<Sha256>(b"key", b"message");
+        let mut mac = Hmac::<Sha256>::new(b"key");
+        mac.update(b"mess");
+        mac.update(b"age");
+        assert!(mac.clone().verify(&tag).is_ok());
+        assert!(matches!(mac.clone().verify(&tag[..16]), Err(CipherError::AuthenticationFailed)));
+        let mut bad = tag.clone();
+        bad[31] ^= 1;
+        assert!(matches!(mac.verify(&bad), Err(CipherError::AuthenticationFailed)));
+    }
+
+    #[test]
+    fn test_hkdf_rfc5869_case_1() {
+        let hkdf = Hkdf::<Sha256>::extract(&hex("000102030405060708090a0b0c"), &[0x0b; 22]);
+        assert_eq!(bytes_to_hex(hkdf.prk()), "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5");
+        let okm = hkdf.expand(&hex("f0f1f2f3f4f5f6f7f8f9"), 42).unwrap();
+        assert_eq!(
+            bytes_to_hex(&okm),
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+        );
+    }
+
+    #[test]
+    fn test_hkdf_rfc5869_case_2_long_inputs() {
+        let salt: Vec<u8> = (0x60..=0xaf).collect();
+        let ikm: Vec<u8> = (0x00..=0x4f).collect();
+        let info: Vec<u8> = (0xb0..=0xff).collect();
+        let hkdf = Hkdf::<Sha256>::extract(&salt, &ikm);
+        assert_eq!(bytes_to_hex(hkdf.prk()), "06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244");
+        assert_eq!(
+            bytes_to_hex(&hkdf.expand(&info, 82).unwrap()),
+            "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c\
+             59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71\
+             cc30c58179ec3e87c14c01d5c1f3434f1d87"
+        );
+    }
+
+    #[test]
+    fn test_hkdf_rfc5869_case_3_empty_salt_and_info() {
+        let hkdf = Hkdf::<Sha256>::extract(&[], &[0x0b; 22]);
+        assert_eq!(bytes_to_hex(hkdf.prk()), "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04");
+        assert_eq!(
+            bytes_to_hex(&hkdf.expand(&[], 42).unwrap()),
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8"
+        );
+    }
+
+    #[test]
+    fn test_hkdf_rejects_oversized_output() {
+        let hkdf = Hkdf::<Sha256>::from_prk(&[1; 32]);
+        assert!(hkdf.expand(b"", 255 * 32).is_ok());
+        assert!(matches!(hkdf.expand(b"", 255 * 32 + 1), Err(CipherError::InvalidParam { .. })));
+    }
+}
@@ -0,0 +1,523 @@
+// Password-Based Key Derivation: PBKDF2-HMAC-SHA256 (RFC 8018) and Argon2id (RFC 9106)
+// Derivación de Claves a partir de Contraseñas: PBKDF2-HMAC-SHA256 (RFC 8018) y Argon2id (RFC 9106)
+
+use crate::blake2b::Blake2b;
+use crate::error::CipherError;
+use crate::hash::{Digest, Sha256};
+use crate::hmac::Hmac;
+use crate::random::SecureRandom;
+
+pub const SALT_LEN: usize = 16;
+
+// Upper bounds, a few times the recommended costs, so a hostile header cannot hang or abort decryption
+// Límites superiores, unas veces los costes recomendados, para que una cabecera hostil no bloquee ni aborte el descifrado
+pub const MAX_PBKDF2_ITERATIONS: u32 = 2_000_000;
+pub const MAX_ARGON2_MEMORY_KIB: u32 = 1024 * 1024; // 1 GiB
+pub const MAX_ARGON2_ITERATIONS: u32 = 10;
+pub const MAX_ARGON2_PARALLELISM: u32 = 16;
+
+// === PBKDF2 ===
+
+/// PBKDF2 with HMAC over any `Digest` / PBKDF2 con HMAC sobre cualquier `Digest`
+pub fn pbkdf2<D: Digest>(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    length: usize,
+) -> Result<Vec<u8>, CipherError> {
+    if iterations == 0 || iterations > MAX_PBKDF2_ITERATIONS {
+        return Err(CipherError::InvalidParam { name: "iterations".to_string(), value: iterations.to_string() });
+    }
+
+    // Key the HMAC once and clone it for every block / Preparar el HMAC una vez y clonarlo en cada bloque
+    let prf = Hmac::<D>::new(password);
+    let mut output = Vec::with_capacity(length);
+    let mut block_index = 1u32;
+    while output.len() < length {
+        let mut mac = prf.clone();
+        mac.update(salt);
+        mac.update(&block_index.to_be_bytes());
+        let mut u = mac.finalize();
+        let mut t = u.clone();
+        for _ in 1..iterations {
+            let mut mac = prf.clone();
+            mac.update(&u);
+            u = mac.finalize();
+            for (t, u) in t.iter_mut().zip(&u) {
+                *t ^= u;
+            }
+        }
+        let take = (length - output.len()).min(t.len());
+        output.extend_from_slice(&t[..take]);
+        block_index += 1;
+    }
+    Ok(output)
+}
+
+// === ARGON2ID ===
+
+const ARGON2_VERSION: u32 = 0x13;
+const ARGON2ID_TYPE: u32 = 2;
+const BLOCK_WORDS: usize = 128; // 1 KiB blocks / Bloques de 1 KiB
+const SYNC_POINTS: usize = 4;
+
+type Block = [u64; BLOCK_WORDS];
+
+/// Argon2id cost parameters / Parámetros de coste de Argon2id
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Argon2Params {
+    pub memory_kib: u32,  // Memory in KiB / Memoria en KiB
+    pub iterations: u32,  // Passes over memory / Pasadas sobre la memoria
+    pub parallelism: u32, // Lanes / Carriles
+}
+
+impl Argon2Params {
+    fn validate(&self) -> Result<(), CipherError> {
+        let invalid = |name: &str, value: u32| CipherError::InvalidParam {
+            name: name.to_string(),
+            value: value.to_string(),
+        };
+        if self.parallelism == 0 || self.parallelism > MAX_ARGON2_PARALLELISM {
+            return Err(invalid("parallelism", self.parallelism));
+        }
+        if self.iterations == 0 || self.iterations > MAX_ARGON2_ITERATIONS {
+            return Err(invalid("iterations", self.iterations));
+        }
+        if self.memory_kib < 8 * self.parallelism || self.memory_kib > MAX_ARGON2_MEMORY_KIB {
+            return Err(invalid("memory_kib", self.memory_kib));
+        }
+        Ok(())
+    }
+}
+
+/// Argon2id with the given cost / Argon2id con el coste indicado
+pub fn argon2id(
+    password: &[u8],
+    salt: &[u8],
+    params: Argon2Params,
+    length: usize,
+) -> Result<Vec<u8>, CipherError> {
+    argon2id_with_secret(password, salt, &[], &[], params, length)
+}
+
+/// Full Argon2id including the optional secret and associated data
+/// Argon2id completo, incluyendo el secreto y los datos asociados opcionales
+pub fn argon2id_with_secret(
+    password: &[u8],
+    salt: &[u8],
+    secret: &[u8],
+    associated_data: &[u8],
+    params: Argon2Params,
+    length: usize,
+) -> Result<Vec<u8>, CipherError> {
+    params.validate()?;
+    if length < 4 {
+        return Err(CipherError::InvalidParam { name: "length".to_string(), value: length.to_string() });
+    }
+    if salt.len() < 8 {
+        return Err(CipherError::InvalidParam { name: "salt".to_string(), value: format!("{} bytes", salt.len()) });
+    }
+
+    let mut h0_input = Vec::new();
+    for value in [params.parallelism, length as u32, params.memory_kib, params.iterations, ARGON2_VERSION, ARGON2ID_TYPE] {
+        h0_input.extend_from_slice(&value.to_le_bytes());
+    }
+    for field in [password, salt, secret, associated_data] {
+        h0_input.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        h0_input.extend_from_slice(field);
+    }
+    let h0 = Blake2b::hash(64, &h0_input);
+
+    let lanes = params.parallelism as usize;
+    let segment_len = params.memory_kib as usize / (SYNC_POINTS * lanes);
+    let lane_len = segment_len * SYNC_POINTS;
+    let mut memory = Memory { blocks: vec![[0u64; BLOCK_WORDS]; lane_len * lanes], lanes, lane_len, segment_len };
+
+    for lane in 0..lanes {
+        for column in 0..2u32 {
+            let mut seed = h0.clone();
+            seed.extend_from_slice(&column.to_le_bytes());
+            seed.extend_from_slice(&(lane as u32).to_le_bytes());
+            memory.blocks[lane * lane_len + column as usize] = bytes_to_block(&variable_hash(1024, &seed));
+        }
+    }
+
+    // Segments of one slice are independent, so lanes can run in sequence
+    // Los segmentos de un corte son independientes, así que los carriles pueden ir en secuencia
+    for pass in 0..params.iterations as usize {
+        for slice in 0..SYNC_POINTS {
+            for lane in 0..lanes {
+                memory.fill_segment(pass, slice, lane, params.iterations);
+            }
+        }
+    }
+
+    let mut last = memory.blocks[lane_len - 1];
+    for lane in 1..lanes {
+        xor_into(&mut last, &memory.blocks[lane * lane_len + lane_len - 1]);
+    }
+    let tag = variable_hash(length, &block_to_bytes(&last));
+
+    // Do not leave the password-derived memory around / No dejar la memoria derivada de la contraseña
+    for block in memory.blocks.iter_mut() {
+        block.fill(0);
+    }
+    Ok(tag)
+}
+
+struct Memory {
+    blocks: Vec<Block>,
+    lanes: usize,
+    lane_len: usize,
+    segment_len: usize,
+}
+
+impl Memory {
+    fn fill_segment(&mut self, pass: usize, slice: usize, lane: usize, passes: u32) {
+        // Argon2id: data-independent addressing for the first half of the first pass
+        // Argon2id: direccionamiento independiente de los datos en la primera mitad de la primera pasada
+        let data_independent = pass == 0 && slice < SYNC_POINTS / 2;
+        let mut address_input = [0u64; BLOCK_WORDS];
+        let mut addresses = [0u64; BLOCK_WORDS];
+        if data_independent {
+            address_input[..6].copy_from_slice(&[
+                pass as u64,
+                lane as u64,
+                slice as u64,
+                self.blocks.len() as u64,
+                passes as u64,
+                ARGON2ID_TYPE as u64,
+            ]);
+        }
+
+        let start = if pass == 0 && slice == 0 { 2 } else { 0 };
+        if data_independent && start != 0 {
+            next_addresses(&mut address_input, &mut addresses);
+        }
+
+        for index in start..self.segment_len {
+            let column = slice * self.segment_len + index;
+            let current = lane * self.lane_len + column;
+            let previous = if column == 0 { current + self.lane_len - 1 } else { current - 1 };
+
+            let pseudo_random = if data_independent {
+                if index % BLOCK_WORDS == 0 {
+                    next_addresses(&mut address_input, &mut addresses);
+                }
+                addresses[index % BLOCK_WORDS]
+            } else {
+                self.blocks[previous][0]
+            };
+
+            let ref_lane = if pass == 0 && slice == 0 {
+                lane
+            } else {
+                ((pseudo_random >> 32) % self.lanes as u64) as usize
+            };
+            let ref_column = self.reference_column(pass, slice, index, pseudo_random as u32, ref_lane == lane);
+            let reference = ref_lane * self.lane_len + ref_column;
+
+            let mut next = compress(&self.blocks[previous], &self.blocks[reference]);
+            if pass > 0 {
+                xor_into(&mut next, &self.blocks[current]);
+            }
+            self.blocks[current] = next;
+        }
+    }
+
+    /// Map J1 onto the blocks this position may reference (RFC 9106 section 3.4.1.2)
+    /// Proyectar J1 sobre los bloques que esta posición puede referenciar (RFC 9106 sección 3.4.1.2)
+    fn reference_column(&self, pass: usize, slice: usize, index: usize, j1: u32, same_lane: bool) -> usize {
+        let finished = if pass == 0 { slice * self.segment_len } else { self.lane_len - self.segment_len };
+        let area = if same_lane {
+            finished + index - 1
+        } else if index == 0 {
+            finished - 1
+        } else {
+            finished
+        } as u64;
+
+        let x = (j1 as u64 * j1 as u64) >> 32;
+        let relative = area - 1 - ((area * x) >> 32);
+        let start = if pass == 0 || slice == SYNC_POINTS - 1 { 0 } else { (slice + 1) * self.segment_len };
+        (start + relative as usize) % self.lane_len
+    }
+}
+
+fn next_addresses(input: &mut Block, addresses: &mut Block) {
+    input[6] += 1;
+    let zero = [0u64; BLOCK_WORDS];
+    *addresses = compress(&zero, &compress(&zero, input));
+}
+
+/// Compression function G / Función de compresión G
+fn compress(x: &Block, y: &Block) -> Block {
+    let mut r = *x;
+    xor_into(&mut r, y);
+    let mut z = r;
+
+    for row in 0..8 {
+        let mut indices = [0usize; 16];
+        for (i, slot) in indices.iter_mut().enumerate() {
+            *slot = 16 * row + i;
+        }
+        permute(&mut z, indices);
+    }
+    for column in 0..8 {
+        let mut indices = [0usize; 16];
+        for (i, slot) in indices.iter_mut().enumerate() {
+            *slot = 2 * column + 16 * (i / 2) + i % 2;
+        }
+        permute(&mut z, indices);
+    }
+
+    xor_into(&mut z, &r);
+    z
+}
+
+/// BLAKE2b round with the multiplication-hardened mixer / Ronda BLAKE2b con el mezclador reforzado por multiplicación
+fn permute(block: &mut Block, idx: [usize; 16]) {
+    let mut v = idx.map(|i| block[i]);
+    mix(&mut v, 0, 4, 8, 12);
+    mix(&mut v, 1, 5, 9, 13);
+    mix(&mut v, 2, 6, 10, 14);
+    mix(&mut v, 3, 7, 11, 15);
+    mix(&mut v, 0, 5, 10, 15);
+    mix(&mut v, 1, 6, 11, 12);
+    mix(&mut v, 2, 7, 8, 13);
+    mix(&mut v, 3, 4, 9, 14);
+    for (i, value) in idx.into_iter().zip(v) {
+        block[i] = value;
+    }
+}
+
+fn mix(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize) {
+    let blamka = |x: u64, y: u64| {
+        x.wrapping_add(y).wrapping_add(2u64.wrapping_mul((x as u32 as u64) * (y as u32 as u64)))
+    };
+    v[a] = blamka(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = blamka(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = blamka(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = blamka(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+/// H' from RFC 9106: BLAKE2b stretched to any output length
+/// H' de RFC 9106: BLAKE2b extendido a cualquier longitud de salida
+fn variable_hash(length: usize, input: &[u8]) -> Vec<u8> {
+    let mut prefixed = (length as u32).to_le_bytes().to_vec();
+    prefixed.extend_from_slice(input);
+    if length <= 64 {
+        return Blake2b::hash(length, &prefixed);
+    }
+
+    let mut output = Vec::with_capacity(length);
+    let mut v = Blake2b::hash(64, &prefixed);
+    loop {
+        output.extend_from_slice(&v[..32]);
+        let remaining = length - output.len();
+        if remaining <= 64 {
+            v = Blake2b::hash(remaining, &v);
+            break;
+        }
+        v = Blake2b::hash(64, &v);
+    }
+    output.extend_from_slice(&v);
+    output
+}
+
+fn xor_into(target: &mut Block, other: &Block) {
+    for (t, o) in target.iter_mut().zip(other) {
+        *t ^= o;
+    }
+}
+
+fn bytes_to_block(bytes: &[u8]) -> Block {
+    let mut block = [0u64; BLOCK_WORDS];
+    for (word, chunk) in block.iter_mut().zip(bytes.chunks_exact(8)) {
+        *word = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    block
+}
+
+fn block_to_bytes(block: &Block) -> Vec<u8> {
+    block.iter().flat_map(|w| w.to_le_bytes()).collect()
+}
+
+// === KEY DERIVATION / DERIVACIÓN DE CLAVES ===
+
+/// Which password hash to run and how expensive it is
+/// Qué hash de contraseña ejecutar y cuán costoso es
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KdfParams {
+    Pbkdf2Sha256 { iterations: u32 }, // PBKDF2-HMAC-SHA256
+    Argon2id(Argon2Params),           // Argon2id (memory-hard / resistente en memoria)
+}
+
+impl KdfParams {
+    /// Check the cost is within bounds before running anything
+    /// Comprobar que el coste está dentro de los límites antes de ejecutar nada
+    pub fn validate(&self) -> Result<(), CipherError> {
+        match self {
+            KdfParams::Pbkdf2Sha256 { iterations } if *iterations == 0 || *iterations > MAX_PBKDF2_ITERATIONS => {
+                Err(CipherError::InvalidParam { name: "iterations".to_string(), value: iterations.to_string() })
+            }
+            KdfParams::Pbkdf2Sha256 { .. } => Ok(()),
+            KdfParams::Argon2id(params) => params.validate(),
+        }
+    }
+
+    /// OWASP-recommended PBKDF2 cost / Coste de PBKDF2 recomendado por OWASP
+    pub fn pbkdf2_default() -> Self {
+        KdfParams::Pbkdf2Sha256 { iterations: 600_000 }
+    }
+
+    /// OWASP-recommended Argon2id cost (19 MiB, 2 passes) / Coste de Argon2id recomendado por OWASP
+    pub fn argon2id_default() -> Self {
+        KdfParams::Argon2id(Argon2Params { memory_kib: 19 * 1024, iterations: 2, parallelism: 1 })
+    }
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self::argon2id_default()
+    }
+}
+
+/// A password hash plus the salt it was run with; store both next to the ciphertext
+/// Un hash de contraseña junto con la sal usada; guarda ambos junto al texto cifrado
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyDerivation {
+    params: KdfParams,
+    salt: Vec<u8>,
+}
+
+impl KeyDerivation {
+    /// Fresh random salt, for encrypting / Sal aleatoria nueva, para cifrar
+    pub fn new(params: KdfParams) -> Result<Self, CipherError> {
+        let salt = SecureRandom::new()?.bytes(SALT_LEN);
+        Ok(Self { params, salt })
+    }
+
+    /// Recorded salt, for decrypting / Sal registrada, para descifrar
+    pub fn with_salt(params: KdfParams, salt: &[u8]) -> Self {
+        Self { params, salt: salt.to_vec() }
+    }
+
+    pub fn params(&self) -> KdfParams {
+        self.params
+    }
+
+    pub fn salt(&self) -> &[u8] {
+        &self.salt
+    }
+
+    /// Stretch a password into `length` key bytes / Estirar una contraseña en `length` bytes de clave
+    pub fn derive(&self, password: &[u8], length: usize) -> Result<Vec<u8>, CipherError> {
+        match self.params {
+            KdfParams::Pbkdf2Sha256 { iterations } => pbkdf2::<Sha256>(password, &self.salt, iterations, length),
+            KdfParams::Argon2id(params) => argon2id(password, &self.salt, params, length),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::bytes_to_hex;
+
+    fn small_argon2() -> Argon2Params {
+        Argon2Params { memory_kib: 64, iterations: 2, parallelism: 1 }
+    }
+
+    // RFC 7914 section 11 / RFC 7914 sección 11
+    #[test]
+    fn test_pbkdf2_sha256_known_answers() {
+        assert_eq!(
+            bytes_to_hex(&pbkdf2::<Sha256>(b"passwd", b"salt", 1, 64).unwrap()),
+            "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc\
+             49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783"
+        );
+        assert_eq!(
+            bytes_to_hex(&pbkdf2::<Sha256>(b"password", b"salt", 4096, 32).unwrap()),
+            "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a"
+        );
+        assert!(matches!(pbkdf2::<Sha256>(b"p", b"s", 0, 32), Err(CipherError::InvalidParam { .. })));
+    }
+
+    // RFC 9106 section 5.3 / RFC 9106 sección 5.3
+    #[test]
+    fn test_argon2id_rfc9106_vector() {
+        let params = Argon2Params { memory_kib: 32, iterations: 3, parallelism: 4 };
+        let tag = argon2id_with_secret(&[1; 32], &[2; 16], &[3; 8], &[4; 12], params, 32).unwrap();
+        assert_eq!(bytes_to_hex(&tag), "0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659");
+    }
+
+    #[test]
+    fn test_argon2id_plain_and_long_output() {
+        let tag = argon2id(b"password", b"somesalt", small_argon2(), 32).unwrap();
+        assert_eq!(bytes_to_hex(&tag), "16a1a498734609dd01456da406de9f3d9da93e6c86c300a12fc1465214ce4922");
+
+        let params = Argon2Params { memory_kib: 16, iterations: 1, parallelism: 2 };
+        let long = argon2id(b"pw", b"saltsaltsalt", params, 100).unwrap();
+        assert_eq!(
+            bytes_to_hex(&long),
+            "1c491ba975085a504c78a4c9fe786faf25751640904725733fdf8b8184f84e19\
+             37a5d5eef8a37f775b85d76efb7a8b708a7915841fb846dfc64b7de9810fedc3\
+             d346b028f841b3cfc552ffc97c735556c17daf8ef07c23b9548fdb4fbebc0675\
+             4fb00663"
+        );
+    }
+
+    #[test]
+    fn test_argon2id_rejects_bad_parameters() {
+        let bad = Argon2Params { memory_kib: 4, iterations: 1, parallelism: 1 };
+        assert!(matches!(argon2id(b"pw", b"saltsalt", bad, 32), Err(CipherError::InvalidParam { .. })));
+        // Rejected before any memory is allocated / Rechazados antes de reservar memoria
+        for huge in [
+            Argon2Params { memory_kib: u32::MAX, iterations: 1, parallelism: 1 },
+            Argon2Params { memory_kib: 64, iterations: u32::MAX, parallelism: 1 },
+            Argon2Params { memory_kib: u32::MAX, iterations: 1, parallelism: 0x00ff_ffff },
+        ] {
+            assert!(matches!(argon2id(b"pw", b"saltsalt", huge, 32), Err(CipherError::InvalidParam { .. })));
+        }
+        assert!(matches!(pbkdf2::<Sha256>(b"p", b"s", u32::MAX, 32), Err(CipherError::InvalidParam { .. })));
+        assert!(KdfParams::Pbkdf2Sha256 { iterations: u32::MAX }.validate().is_err());
+
+        // Each cap is allowed and one more is not / Cada límite se admite y uno más no
+        let pbkdf2_at = |iterations| KdfParams::Pbkdf2Sha256 { iterations }.validate().is_ok();
+        assert!(pbkdf2_at(MAX_PBKDF2_ITERATIONS) && !pbkdf2_at(MAX_PBKDF2_ITERATIONS + 1));
+        let at_cap = Argon2Params { memory_kib: MAX_ARGON2_MEMORY_KIB, iterations: MAX_ARGON2_ITERATIONS, parallelism: MAX_ARGON2_PARALLELISM };
+        assert!(KdfParams::Argon2id(at_cap).validate().is_ok());
+        for above in [
+            Argon2Params { memory_kib: MAX_ARGON2_MEMORY_KIB + 1, ..at_cap },
+            Argon2Params { iterations: MAX_ARGON2_ITERATIONS + 1, ..at_cap },
+            Argon2Params { parallelism: MAX_ARGON2_PARALLELISM + 1, ..at_cap },
+        ] {
+            assert!(matches!(KdfParams::Argon2id(above).validate(), Err(CipherError::InvalidParam { .. })));
+        }
+        assert!(KdfParams::argon2id_default().validate().is_ok());
+        assert!(KdfParams::pbkdf2_default().validate().is_ok());
+        assert!(matches!(argon2id(b"pw", b"short", small_argon2(), 32), Err(CipherError::InvalidParam { .. })));
+    }
+
+    #[test]
+    fn test_key_derivation_salt_handling() {
+        let params = KdfParams::Pbkdf2Sha256 { iterations: 10 };
+        let first = KeyDerivation::new(params).unwrap();
+        let second = KeyDerivation::new(params).unwrap();
+        assert_eq!(first.salt().len(), SALT_LEN);
+        assert_ne!(first.salt(), second.salt());
+        assert_ne!(first.derive(b"pw", 32).unwrap(), second.derive(b"pw", 32).unwrap());
+
+        // Recording params and salt reproduces the key / Registrar parámetros y sal reproduce la clave
+        let replay = KeyDerivation::with_salt(first.params(), first.salt());
+        assert_eq!(first.derive(b"pw", 32).unwrap(), replay.derive(b"pw", 32).unwrap());
+
+        let argon = KeyDerivation::with_salt(KdfParams::Argon2id(small_argon2()), b"somesalt");
+        assert_eq!(argon.derive(b"password", 32).unwrap(), argon2id(b"password", b"somesalt", small_argon2(), 32).unwrap());
+    }
+}
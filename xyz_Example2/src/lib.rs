@@ -0,0 +1,33 @@
+// Data Encryption Library: Ciphers, Encodings and File Encryption
+// Librería de Cifrado de Datos: Cifrados, Codificaciones y Cifrado de Archivos
+
+//! Reusable encryption building blocks shared by the demo binary and other crates.
+//! Bloques de cifrado reutilizables compartidos por el binario de demo y otros crates.
+
+pub mod aes;
+pub mod alphabet;
+pub mod bench;
+pub mod blake2b;
+pub mod chacha20poly1305;
+pub mod cipher;
+pub mod classical;
+pub mod container;
+pub mod cryptanalysis;
+pub mod encoding;
+pub mod error;
+pub mod file;
+pub mod hash;
+pub mod hmac;
+pub mod kdf;
+pub mod modes;
+#[cfg(test)]
+mod property;
+pub mod random;
+pub mod secret;
+pub mod stream;
+
+pub use cipher::{Cipher, CipherFactory, CipherParams, CipherRegistry};
+pub use classical::SimpleEncryption;
+pub use error::CipherError;
+pub use file::{CipherType, EncryptionManager};
+pub use secret::{ct_eq, SecretKey};
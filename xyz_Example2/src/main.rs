@@ -1,284 +1,469 @@
 // Data Encryption Examples in Rust
 // Ejemplos de Cifrado de Datos en Rust
 
-use std::collections::HashMap;
-use std::io::{self, Write};
-
 mod advanced;
+mod cli;
+mod console;
+mod messages;
+
+use std::io::{self, BufRead, Write};
+use std::{env, process};
+
+use console::Console;
+use messages::Messages;
+use xyz_example2::alphabet::Alphabet;
+use xyz_example2::classical::{caesar_decrypt, caesar_encrypt, caesar_encrypt_with, rot13, xor_decrypt, xor_encrypt};
+use xyz_example2::encoding::{bytes_to_hex, simple_base64_decode, simple_base64_encode, Base64};
+use xyz_example2::CipherError;
 
 fn main() {
-    println!("=== DATA ENCRYPTION EXAMPLES / EJEMPLOS DE CIFRADO DE DATOS ===\n");
+    // `--lang` works with or without a subcommand / `--lang` funciona con o sin subcomando
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let flag = match messages::take_lang_flag(&mut args) {
+        Ok(flag) => flag,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(cli::EXIT_USAGE);
+        }
+    };
+    let locale = messages::resolve_locale(flag.as_deref(), |name| env::var(name).ok());
+    let messages = Messages::load(&locale).unwrap_or_else(|e| {
+        eprintln!("error: {} ({})", e, locale);
+        Messages::english()
+    });
     
-    loop {
-        println!("Select demo / Selecciona demo:");
-        println!("1. Basic encryption examples / Ejemplos básicos de cifrado");
-        println!("2. Advanced encryption demo / Demo de cifrado avanzado");
-        println!("3. Interactive encryption CLI / CLI de cifrado interactivo");
-        println!("4. Exit / Salir");
-        
-        print!("Enter choice (1-4) / Ingresa opción (1-4): ");
-        io::stdout().flush().unwrap();
-        
-        let mut input = String::new();
-        io::stdin().read_line(&mut input).unwrap();
+    // Any other argument selects the scriptable subcommands / Cualquier otro argumento elige los subcomandos automatizables
+    if !args.is_empty() {
+        let code = cli::run(&args, &messages, &mut io::stdin().lock(), &mut io::stdout().lock(), &mut io::stderr());
+        process::exit(code);
+    }
+    
+    let mut console = Console::with_messages(io::stdin().lock(), io::stdout(), messages);
+    if let Err(e) = run_menu(&mut console) {
+        eprintln!("Error: {}", e);
+    }
+}
+
+/// Screens of the menu; running one yields the next / Pantallas del menú; ejecutar una da la siguiente
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Screen {
+    Menu,
+    BasicExamples,
+    AdvancedDemo,
+    EncryptionCli,
+    HashFile,
+    Cryptanalysis,
+    Benchmark,
+    Exit,
+}
+
+impl Screen {
+    /// Menu entries in order, with their catalog keys / Entradas del menú en orden, con sus claves del catálogo
+    const CHOICES: [(Screen, &'static str); 7] = [
+        (Screen::BasicExamples, "menu.basic"),
+        (Screen::AdvancedDemo, "menu.advanced"),
+        (Screen::EncryptionCli, "menu.encrypt"),
+        (Screen::HashFile, "menu.hash"),
+        (Screen::Cryptanalysis, "menu.crack"),
+        (Screen::Benchmark, "menu.bench"),
+        (Screen::Exit, "menu.exit"),
+    ];
+    
+    /// Run this screen; a failing demo reports its error and goes back to the menu
+    /// Ejecutar esta pantalla; una demo que falla muestra su error y vuelve al menú
+    fn run<R: BufRead, W: Write>(self, console: &mut Console<R, W>) -> Result<Screen, CipherError> {
+        let result = match self {
+            Screen::Menu => return main_menu(console),
+            Screen::Exit => return Ok(Screen::Exit),
+            Screen::BasicExamples => basic_encryption_examples(console),
+            Screen::AdvancedDemo => advanced::advanced_encryption_demo(console),
+            Screen::EncryptionCli => advanced::interactive_encryption_cli(console),
+            Screen::HashFile => advanced::hash_file_cli(console),
+            Screen::Cryptanalysis => advanced::cryptanalysis_cli(console),
+            Screen::Benchmark => advanced::benchmark_cli(console),
+        };
         
-        match input.trim() {
-            "1" => basic_encryption_examples(),
-            "2" => advanced::advanced_encryption_demo(),
-            "3" => advanced::interactive_encryption_cli(),
-            "4" => {
-                println!("Goodbye! / ¡Adiós!");
-                break;
-            },
-            _ => println!("Invalid choice / Opción inválida\n"),
+        if let Err(e) = result {
+            console.say("error", &[("error", &e)])?;
+        }
+        writeln!(console, "\n{}\n", "=".repeat(60))?;
+        Ok(Screen::Menu)
+    }
+}
+
+/// Show the menu and read a choice; end of input leaves / Mostrar el menú y leer una opción; el fin de la entrada sale
+fn main_menu<R: BufRead, W: Write>(console: &mut Console<R, W>) -> Result<Screen, CipherError> {
+    console.say("menu.select", &[])?;
+    for (i, (_, key)) in Screen::CHOICES.iter().enumerate() {
+        let label = console.text(key, &[]);
+        writeln!(console, "{}. {}", i + 1, label)?;
+    }
+    
+    let choice = match console.ask("menu.prompt", &[("count", &Screen::CHOICES.len())]) {
+        Ok(choice) => choice,
+        Err(e) => {
+            writeln!(console)?;
+            console.say("error", &[("error", &e)])?;
+            return Ok(Screen::Exit);
+        }
+    };
+    
+    match choice.parse::<usize>() {
+        Ok(n) if (1..=Screen::CHOICES.len()).contains(&n) => {
+            let screen = Screen::CHOICES[n - 1].0;
+            if screen == Screen::Exit {
+                console.say("menu.goodbye", &[])?;
+            }
+            Ok(screen)
+        }
+        _ => {
+            console.say("menu.invalid", &[])?;
+            writeln!(console)?;
+            Ok(Screen::Menu)
         }
-        
-        println!("\n{}\n", "=".repeat(60));
     }
 }
 
-fn basic_encryption_examples() {
-    println!("=== BASIC ENCRYPTION EXAMPLES / EJEMPLOS BÁSICOS DE CIFRADO ===\n");
+/// Drive the screens until the user exits / Recorrer las pantallas hasta que el usuario salga
+fn run_menu<R: BufRead, W: Write>(console: &mut Console<R, W>) -> Result<(), CipherError> {
+    console.say("menu.title", &[])?;
+    writeln!(console)?;
+    
+    let mut screen = Screen::Menu;
+    while screen != Screen::Exit {
+        screen = screen.run(console)?;
+    }
+    Ok(())
+}
+
+fn basic_encryption_examples<R, W: Write>(console: &mut Console<R, W>) -> Result<(), CipherError> {
+    console.say("basic.title", &[])?;
+    writeln!(console)?;
 
     // 1. Caesar Cipher Example / Ejemplo de Cifrado César
-    caesar_cipher_demo();
+    caesar_cipher_demo(console)?;
     
     // 2. Simple XOR Cipher / Cifrado XOR Simple
-    xor_cipher_demo();
+    xor_cipher_demo(console)?;
     
     // 3. ROT13 Implementation / Implementación ROT13
-    rot13_demo();
+    rot13_demo(console)?;
     
     // 4. Base64 Encoding (not encryption but encoding) / Codificación Base64
-    base64_demo();
+    base64_demo(console)
 }
 
 // === CAESAR CIPHER / CIFRADO CÉSAR ===
-fn caesar_cipher_demo() {
-    println!("1. === CAESAR CIPHER / CIFRADO CÉSAR ===");
+fn caesar_cipher_demo<R, W: Write>(console: &mut Console<R, W>) -> Result<(), CipherError> {
+    console.say("basic.caesar", &[])?;
     
     let message = "Hello Rust World!";
     let shift = 3;
     
-    let encrypted = caesar_encrypt(message, shift);
-    let decrypted = caesar_decrypt(&encrypted, shift);
+    let encrypted = caesar_encrypt(message, shift)?;
+    let decrypted = caesar_decrypt(&encrypted, shift)?;
     
-    println!("Original message / Mensaje original: {}", message);
-    println!("Encrypted / Cifrado: {}", encrypted);
-    println!("Decrypted / Descifrado: {}", decrypted);
-    println!();
-}
-
-fn caesar_encrypt(text: &str, shift: u8) -> String {
-    text.chars()
-        .map(|c| {
-            if c.is_ascii_alphabetic() {
-                let base = if c.is_ascii_lowercase() { b'a' } else { b'A' };
-                let shifted = (((c as u8 - base) + shift) % 26) + base;
-                shifted as char
-            } else {
-                c // Non-alphabetic characters remain unchanged
-            }
-        })
-        .collect()
-}
-
-fn caesar_decrypt(text: &str, shift: u8) -> String {
-    caesar_encrypt(text, 26 - shift) // Reverse the shift
+    console.say("common.original_message", &[("text", &message)])?;
+    console.say("common.encrypted", &[("text", &encrypted)])?;
+    console.say("common.decrypted", &[("text", &decrypted)])?;
+    
+    // Same shift over the 27-letter Spanish alphabet / Mismo desplazamiento sobre el alfabeto español de 27 letras
+    let spanish = Alphabet::spanish();
+    let message = "El niño sueña con España";
+    let encrypted = caesar_encrypt_with(message, shift.into(), &spanish)?;
+    console.say("basic.spanish", &[("text", &message), ("encrypted", &encrypted)])?;
+    writeln!(console)?;
+    Ok(())
 }
 
 // === XOR CIPHER / CIFRADO XOR ===
-fn xor_cipher_demo() {
-    println!("2. === XOR CIPHER / CIFRADO XOR ===");
+fn xor_cipher_demo<R, W: Write>(console: &mut Console<R, W>) -> Result<(), CipherError> {
+    console.say("basic.xor", &[])?;
     
     let message = "Secret Message";
     let key = "MyKey";
     
-    let encrypted = xor_encrypt(message.as_bytes(), key.as_bytes());
-    let decrypted = xor_decrypt(&encrypted, key.as_bytes());
+    let encrypted = xor_encrypt(message.as_bytes(), key.as_bytes())?;
+    let decrypted = xor_decrypt(&encrypted, key.as_bytes())?;
     let decrypted_str = String::from_utf8_lossy(&decrypted);
     
-    println!("Original message / Mensaje original: {}", message);
-    println!("Key / Clave: {}", key);
-    println!("Encrypted (hex) / Cifrado (hex): {}", bytes_to_hex(&encrypted));
-    println!("Decrypted / Descifrado: {}", decrypted_str);
-    println!();
-}
-
-fn xor_encrypt(data: &[u8], key: &[u8]) -> Vec<u8> {
-    data.iter()
-        .enumerate()
-        .map(|(i, &byte)| byte ^ key[i % key.len()])
-        .collect()
-}
-
-fn xor_decrypt(encrypted_data: &[u8], key: &[u8]) -> Vec<u8> {
-    // XOR decryption is the same as encryption
-    // El descifrado XOR es igual que el cifrado
-    xor_encrypt(encrypted_data, key)
+    console.say("common.original_message", &[("text", &message)])?;
+    console.say("common.key", &[("key", &key)])?;
+    console.say("common.encrypted_hex", &[("text", &bytes_to_hex(&encrypted))])?;
+    console.say("common.decrypted", &[("text", &decrypted_str)])?;
+    writeln!(console)?;
+    Ok(())
 }
 
 // === ROT13 CIPHER / CIFRADO ROT13 ===
-fn rot13_demo() {
-    println!("3. === ROT13 CIPHER / CIFRADO ROT13 ===");
+fn rot13_demo<R, W: Write>(console: &mut Console<R, W>) -> Result<(), CipherError> {
+    console.say("basic.rot13", &[])?;
     
     let message = "This is a ROT13 example!";
-    let encoded = rot13(message);
-    let decoded = rot13(&encoded); // ROT13 is its own inverse
+    let encoded = rot13(message)?;
+    let decoded = rot13(&encoded)?; // ROT13 is its own inverse
     
-    println!("Original message / Mensaje original: {}", message);
-    println!("ROT13 encoded / Codificado ROT13: {}", encoded);
-    println!("ROT13 decoded / Decodificado ROT13: {}", decoded);
-    println!();
-}
-
-fn rot13(text: &str) -> String {
-    text.chars()
-        .map(|c| {
-            match c {
-                'a'..='z' => ((c as u8 - b'a' + 13) % 26 + b'a') as char,
-                'A'..='Z' => ((c as u8 - b'A' + 13) % 26 + b'A') as char,
-                _ => c,
-            }
-        })
-        .collect()
+    console.say("common.original_message", &[("text", &message)])?;
+    console.say("basic.rot13_encoded", &[("text", &encoded)])?;
+    console.say("basic.rot13_decoded", &[("text", &decoded)])?;
+    writeln!(console)?;
+    Ok(())
 }
 
 // === BASE64 ENCODING / CODIFICACIÓN BASE64 ===
-fn base64_demo() {
-    println!("4. === BASE64 ENCODING / CODIFICACIÓN BASE64 ===");
+fn base64_demo<R, W: Write>(console: &mut Console<R, W>) -> Result<(), CipherError> {
+    console.say("basic.base64", &[])?;
     
     let message = "Hello, Base64 World!";
-    let encoded = simple_base64_encode(message.as_bytes());
-    let decoded = simple_base64_decode(&encoded);
+    let encoded = simple_base64_encode(message.as_bytes())?;
+    let decoded = simple_base64_decode(&encoded)?;
     let decoded_str = String::from_utf8_lossy(&decoded);
     
-    println!("Original message / Mensaje original: {}", message);
-    println!("Base64 encoded / Codificado Base64: {}", encoded);
-    println!("Base64 decoded / Decodificado Base64: {}", decoded_str);
-    println!();
+    console.say("common.original_message", &[("text", &message)])?;
+    console.say("basic.base64_encoded", &[("text", &encoded)])?;
+    console.say("basic.base64_decoded", &[("text", &decoded_str)])?;
+    console.say("basic.base64_url", &[("text", &Base64::URL_SAFE_NO_PAD.encode(message.as_bytes()))])?;
+    writeln!(console)?;
+    Ok(())
 }
 
-// Simple Base64 implementation (for educational purposes)
-// Implementación simple de Base64 (para propósitos educativos)
-fn simple_base64_encode(data: &[u8]) -> String {
-    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-    let mut result = String::new();
-    
-    for chunk in data.chunks(3) {
-        let mut buf = [0u8; 3];
-        for (i, &byte) in chunk.iter().enumerate() {
-            buf[i] = byte;
-        }
-        
-        let b = ((buf[0] as u32) << 16) | ((buf[1] as u32) << 8) | (buf[2] as u32);
-        
-        result.push(ALPHABET[((b >> 18) & 63) as usize] as char);
-        result.push(ALPHABET[((b >> 12) & 63) as usize] as char);
-        result.push(if chunk.len() > 1 { ALPHABET[((b >> 6) & 63) as usize] as char } else { '=' });
-        result.push(if chunk.len() > 2 { ALPHABET[(b & 63) as usize] as char } else { '=' });
-    }
-    
-    result
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console::script::{assert_transcript, assert_transcript_in};
 
-fn simple_base64_decode(encoded: &str) -> Vec<u8> {
-    let mut decode_table = HashMap::new();
-    let alphabet = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-    
-    for (i, c) in alphabet.chars().enumerate() {
-        decode_table.insert(c, i as u8);
-    }
-    
-    let mut result = Vec::new();
-    let chars: Vec<char> = encoded.chars().filter(|&c| c != '=').collect();
-    
-    for chunk in chars.chunks(4) {
-        if chunk.len() < 2 { break; }
-        
-        let b1 = decode_table.get(&chunk[0]).unwrap_or(&0);
-        let b2 = decode_table.get(&chunk[1]).unwrap_or(&0);
-        let b3 = if chunk.len() > 2 { decode_table.get(&chunk[2]).unwrap_or(&0) } else { &0 };
-        let b4 = if chunk.len() > 3 { decode_table.get(&chunk[3]).unwrap_or(&0) } else { &0 };
-        
-        let combined = ((*b1 as u32) << 18) | ((*b2 as u32) << 12) | ((*b3 as u32) << 6) | (*b4 as u32);
-        
-        result.push((combined >> 16) as u8);
-        if chunk.len() > 2 {
-            result.push((combined >> 8) as u8);
-        }
-        if chunk.len() > 3 {
-            result.push(combined as u8);
-        }
+    #[test]
+    fn test_menu_rejects_unknown_choices_and_exits() {
+        assert_transcript(
+            "
+            Select demo:
+            7. Exit
+            > 9
+            Invalid choice
+            > 7
+            Goodbye!
+            ",
+            |console| run_menu(console).unwrap(),
+        );
+        // End of input leaves instead of looping / El fin de la entrada sale en lugar de repetir
+        assert_transcript("Enter choice (1-7)\nError: I/O error: end of input", |console| run_menu(console).unwrap());
     }
-    
-    result
-}
-
-// === UTILITY FUNCTIONS / FUNCIONES UTILITARIAS ===
-fn bytes_to_hex(bytes: &[u8]) -> String {
-    bytes.iter()
-        .map(|b| format!("{:02x}", b))
-        .collect::<Vec<String>>()
-        .join("")
-}
-
-// === ADVANCED ENCRYPTION STRUCT / ESTRUCTURA DE CIFRADO AVANZADA ===
-pub struct SimpleEncryption {
-    key: Vec<u8>,
-}
 
-impl SimpleEncryption {
-    pub fn new(key: &str) -> Self {
-        Self {
-            key: key.as_bytes().to_vec(),
-        }
+    #[test]
+    fn test_basic_examples_screen() {
+        assert_transcript(
+            "
+            > 1
+            Encrypted: Khoor Uxvw Zruog!
+            Spanish alphabet: El niño sueña con España -> Hñ plqr vxhqd frp Hvsdqd
+            ROT13 encoded: Guvf vf n EBG13 rknzcyr!
+            Base64 encoded: SGVsbG8sIEJhc2U2NCBXb3JsZCE=
+            ============
+            Select demo:
+            > 7
+            ",
+            |console| run_menu(console).unwrap(),
+        );
     }
-    
-    pub fn encrypt(&self, data: &[u8]) -> Vec<u8> {
-        xor_encrypt(data, &self.key)
+
+    #[test]
+    fn test_encryption_session_rejects_bad_shift() {
+        // A bad shift is reported, never replaced by a default / Un desplazamiento malo se informa, nunca se sustituye
+        assert_transcript(
+            "
+            > 3
+            1. Caesar Cipher
+            > 1
+            Generate a random key?
+            > n
+            Alphabet
+            >
+            Enter shift value
+            > 30
+            Error: shift 30 is out of range
+            Select demo:
+            > 3
+            > 1
+            > n
+            >
+            > three
+            Error: invalid value 'three' for parameter 'shift'
+            > 3
+            > 1
+            > n
+            >
+            > 3
+            Enter message to encrypt
+            > Hello
+            Select encoding
+            > 1
+            Results (Caesar):
+            Encrypted (Hex): 4b686f6f72
+            Decrypted: Hello
+            > 7
+            ",
+            |console| run_menu(console).unwrap(),
+        );
     }
-    
-    pub fn decrypt(&self, encrypted_data: &[u8]) -> Vec<u8> {
-        xor_decrypt(encrypted_data, &self.key)
+
+    #[test]
+    fn test_invalid_choices_are_asked_again() {
+        // A wrong number never picks a default / Un número erróneo nunca elige un valor por defecto
+        assert_transcript(
+            "
+            > 3
+            Select algorithm
+            > 0
+            Invalid selection, choose a listed algorithm
+            Select algorithm
+            > 1
+            > n
+            >
+            > 3
+            > Hello
+            Select encoding
+            > base64
+            Invalid selection, choose a listed encoding
+            Select encoding
+            > 2
+            Results (Caesar):
+            Encrypted (Base32): JNUG633S
+            Decrypted: Hello
+            > 7
+            ",
+            |console| run_menu(console).unwrap(),
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_enigma_session_shows_the_trace() {
+        assert_transcript(
+            "
+            > 3
+            4. Enigma I / M3 Machine
+            > 4
+            > n
+            Rotors, reflector, rings, start and plugboard
+            > I II III B AAA AAA
+            > AAAAA
+            > 1
+            Encrypted (Hex): 42445a474f
+            Show the signal path of each letter?
+            > y
+            [AAB] A > plugboard A > III C > II D > I F > UKW-B S > I S > II E > III B > plugboard B = B
+            [AAF] A
+            > 7
+            ",
+            |console| run_menu(console).unwrap(),
+        );
+    }
 
     #[test]
-    fn test_caesar_cipher() {
-        let original = "Hello";
-        let encrypted = caesar_encrypt(original, 3);
-        let decrypted = caesar_decrypt(&encrypted, 3);
-        assert_eq!(original, decrypted);
+    fn test_hash_and_cryptanalysis_screens() {
+        let path = env::temp_dir().join("xyz_example2_menu_hash.txt");
+        std::fs::write(&path, "abc").unwrap();
+        let transcript = format!(
+            "
+            > 4
+            Select hash (1-4)
+            > 5
+            Invalid selection, choose a listed hash
+            Select hash (1-4)
+            > 2
+            > {}
+            SHA-256 ({}): ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad
+            > 5
+            > 1
+            Enter ciphertext
+            > Wkh txlfn eurzq ira mxpsv ryhu wkh odcb grj
+            1. Shift 3 (ROT-3)
+            The quick brown fox jumps over the lazy dog
+            > 7
+            ",
+            path.display(),
+            path.display()
+        );
+        assert_transcript(&transcript, |console| run_menu(console).unwrap());
+        let _ = std::fs::remove_file(path);
     }
 
     #[test]
-    fn test_xor_cipher() {
-        let original = b"Secret Message";
-        let key = b"Key";
-        let encrypted = xor_encrypt(original, key);
-        let decrypted = xor_decrypt(&encrypted, key);
-        assert_eq!(original, &decrypted[..]);
+    fn test_benchmark_screen() {
+        assert_transcript(
+            "
+            > 6
+            === THROUGHPUT BENCHMARK ===
+            Buffer sizes
+            > 1K
+            Algorithms or encodings
+            > caesar, base64
+            Algorithm            Operation       Size    MB/s median      MB/s best
+            Caesar               encrypt        1 KiB
+            Caesar               decrypt        1 KiB
+            Base64               encode         1 KiB
+            Base64               decode         1 KiB
+            Select demo:
+            > 6
+            > 2K
+            > Rot47
+            Error: unknown algorithm 'Rot47'
+            > 7
+            ",
+            |console| run_menu(console).unwrap(),
+        );
     }
 
     #[test]
-    fn test_rot13() {
-        let original = "Hello World";
-        let encoded = rot13(original);
-        let decoded = rot13(&encoded);
-        assert_eq!(original, decoded);
+    fn test_advanced_demo_reports_files_in_spanish() {
+        let output = assert_transcript_in(
+            Messages::load("es").unwrap(),
+            "
+            Ingresa opción (1-7):
+            > 2
+            6. Demo de Cifrado de Archivos:
+            Archivo cifrado:
+            xyz_example2_sample_encrypted.txt
+            Archivo descifrado:
+            xyz_example2_sample_decrypted.txt
+            El contenido descifrado coincide con el original: true
+            > 7
+            ¡Adiós!
+            ",
+            |console| run_menu(console).unwrap(),
+        );
+        assert!(!output.contains("File encrypted") && !output.contains("File decrypted"));
     }
 
     #[test]
-    fn test_simple_encryption_struct() {
-        let cipher = SimpleEncryption::new("MySecretKey");
-        let original = b"This is a test message";
-        let encrypted = cipher.encrypt(original);
-        let decrypted = cipher.decrypt(&encrypted);
-        assert_eq!(original, &decrypted[..]);
+    fn test_menu_in_spanish() {
+        let spanish = Messages::load("es_ES.UTF-8").unwrap();
+        let output = assert_transcript_in(
+            spanish,
+            "
+            === EJEMPLOS DE CIFRADO DE DATOS ===
+            7. Salir
+            Ingresa opción (1-7):
+            > 3
+            1. Cifrado César
+            > 1
+            ¿Generar una clave aleatoria? (s/n):
+            > n
+            Alfabeto (ascii/spanish/greek o sus letras) [ascii]:
+            >
+            Ingresa valor de desplazamiento (1-25):
+            > 3
+            Ingresa mensaje a cifrar:
+            > Hola
+            > 1
+            Resultados (Caesar):
+            Cifrado (Hex): 4b726f64
+            Descifrado: Hola
+            > 7
+            ¡Adiós!
+            ",
+            |console| run_menu(console).unwrap(),
+        );
+        // One language per line now / Ahora un solo idioma por línea
+        assert!(!output.contains("Select demo"));
     }
-}
\ No newline at end of file
+}
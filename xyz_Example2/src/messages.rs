@@ -0,0 +1,231 @@
+// Message Catalogs: Every User-Facing String, One File per Language
+// Catálogos de Mensajes: Cada Texto para el Usuario, un Archivo por Idioma
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::{env, fs, io};
+
+use xyz_example2::CipherError;
+
+/// Language every other catalog falls back to / Idioma al que vuelven todos los demás catálogos
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Directory searched for `<code>.txt` before the built-in catalogs, so new languages need no rebuild
+/// Directorio donde se busca `<código>.txt` antes que los catálogos integrados, así un idioma nuevo no requiere recompilar
+pub const LOCALE_DIR_VAR: &str = "XYZ_LOCALE_DIR";
+
+/// Catalogs compiled into the binary / Catálogos compilados en el binario
+const BUILT_IN: [(&str, &str); 2] = [
+    ("en", include_str!("../locales/en.txt")),
+    ("es", include_str!("../locales/es.txt")),
+];
+
+/// Opening and closing line of a multi-line value / Línea de apertura y cierre de un valor de varias líneas
+const BLOCK: &str = "\"\"\"";
+
+/// Parse a catalog: `key = text` lines, `#` comments and `key = """` blocks that end at a `"""` line
+/// Analizar un catálogo: líneas `clave = texto`, comentarios `#` y bloques `clave = """` que terminan en una línea `"""`
+pub fn parse_catalog(text: &str) -> Result<HashMap<String, String>, CipherError> {
+    let malformed = |position: usize, reason: &str| CipherError::MalformedInput { position, reason: reason.to_string() };
+    let mut entries = HashMap::new();
+    let mut lines = text.lines().enumerate();
+
+    while let Some((number, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        // Positions are 1-based line numbers / Las posiciones son números de línea desde 1
+        let (key, value) = line.split_once('=').ok_or_else(|| malformed(number + 1, "expected `key = text`"))?;
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(malformed(number + 1, "keys cannot be empty or contain spaces"));
+        }
+
+        let value = match value.trim() {
+            BLOCK => {
+                let mut block = Vec::new();
+                loop {
+                    match lines.next() {
+                        Some((_, line)) if line.trim_end() == BLOCK => break,
+                        Some((_, line)) => block.push(line.trim_end()),
+                        None => return Err(malformed(number + 1, "unterminated \"\"\" block")),
+                    }
+                }
+                block.join("\n")
+            }
+            value => value.to_string(),
+        };
+        if entries.insert(key.to_string(), value).is_some() {
+            return Err(malformed(number + 1, "duplicate key"));
+        }
+    }
+    Ok(entries)
+}
+
+/// Language code from a locale name: "es_ES.UTF-8" -> "es"; "C" and "POSIX" mean English
+/// Código de idioma de un nombre de locale: "es_ES.UTF-8" -> "es"; "C" y "POSIX" significan inglés
+pub fn language_code(locale: &str) -> String {
+    let code = locale.split(['_', '-', '.', '@']).next().unwrap_or("").trim().to_ascii_lowercase();
+    match code.as_str() {
+        "" | "c" | "posix" => DEFAULT_LOCALE.to_string(),
+        _ => code,
+    }
+}
+
+/// Pick the language: the `--lang` flag, then `LC_ALL`, `LC_MESSAGES` and `LANG`, then English
+/// Elegir el idioma: la opción `--lang`, luego `LC_ALL`, `LC_MESSAGES` y `LANG`, luego inglés
+pub fn resolve_locale(flag: Option<&str>, var: impl Fn(&str) -> Option<String>) -> String {
+    let from_env = ["LC_ALL", "LC_MESSAGES", "LANG"].iter().filter_map(|name| var(name)).find(|v| !v.is_empty());
+    match flag.map(str::to_string).or(from_env) {
+        Some(locale) => language_code(&locale),
+        None => DEFAULT_LOCALE.to_string(),
+    }
+}
+
+/// Remove `--lang <code>` or `--lang=<code>` from the arguments and return its value
+/// Quitar `--lang <código>` o `--lang=<código>` de los argumentos y devolver su valor
+pub fn take_lang_flag(args: &mut Vec<String>) -> Result<Option<String>, CipherError> {
+    let Some(at) = args.iter().position(|arg| arg == "--lang" || arg.starts_with("--lang=")) else {
+        return Ok(None);
+    };
+    let flag = args.remove(at);
+    match flag.strip_prefix("--lang=") {
+        Some(code) => Ok(Some(code.to_string())),
+        None if at < args.len() => Ok(Some(args.remove(at))),
+        None => Err(CipherError::MissingParam("lang")),
+    }
+}
+
+/// Translated strings for one language, with English behind every missing key
+/// Textos traducidos de un idioma, con el inglés detrás de cada clave que falte
+#[derive(Clone, Debug)]
+pub struct Messages {
+    texts: HashMap<String, String>,
+    english: HashMap<String, String>,
+}
+
+impl Messages {
+    /// The built-in English catalog / El catálogo integrado en inglés
+    pub fn english() -> Self {
+        Self::with_catalog(HashMap::new())
+    }
+
+    /// Load a language from `$XYZ_LOCALE_DIR/<code>.txt` or the built-in catalogs; unknown languages get English
+    /// Cargar un idioma de `$XYZ_LOCALE_DIR/<código>.txt` o de los catálogos integrados; los desconocidos reciben inglés
+    pub fn load(locale: &str) -> Result<Self, CipherError> {
+        let code = language_code(locale);
+        if let Some(dir) = env::var_os(LOCALE_DIR_VAR) {
+            match fs::read_to_string(std::path::Path::new(&dir).join(format!("{}.txt", code))) {
+                Ok(text) => return Ok(Self::with_catalog(parse_catalog(&text)?)),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        match BUILT_IN.iter().find(|(built_in, _)| *built_in == code) {
+            Some((_, text)) => Ok(Self::with_catalog(parse_catalog(text)?)),
+            None => Ok(Self::english()),
+        }
+    }
+
+    /// Messages for `texts`, falling back to the built-in English catalog
+    /// Mensajes para `texts`, volviendo al catálogo integrado en inglés
+    pub fn with_catalog(texts: HashMap<String, String>) -> Self {
+        let english = parse_catalog(BUILT_IN[0].1).expect("the built-in English catalog parses");
+        Self { texts, english }
+    }
+
+    /// The text for `key` in this language, else in English / El texto de `key` en este idioma, si no en inglés
+    pub fn lookup(&self, key: &str) -> Option<&str> {
+        self.texts.get(key).or_else(|| self.english.get(key)).map(String::as_str)
+    }
+
+    /// Like `lookup`, showing the key itself when no catalog has it / Como `lookup`, mostrando la clave si ningún catálogo la tiene
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.lookup(key).unwrap_or(key)
+    }
+
+    /// The text for `key` with each `{name}` replaced by its value / El texto de `key` con cada `{name}` sustituido por su valor
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        args.iter().fold(self.get(key).to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), &value.to_string())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `{name}` placeholders in a text / Marcadores `{name}` de un texto
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    #[test]
+    fn test_parse_catalog() {
+        let catalog = parse_catalog("# comment\n\ngreeting = Hello, {name}!\nusage = \"\"\"\n  indented\n\nlast\n\"\"\"\n").unwrap();
+        assert_eq!(catalog["greeting"], "Hello, {name}!");
+        assert_eq!(catalog["usage"], "  indented\n\nlast");
+
+        for (text, line) in [("ok = 1\nno equals sign", 2), ("a = 1\na = 2", 2), ("bad key = x", 1), ("block = \"\"\"\nnever closed", 1)] {
+            match parse_catalog(text) {
+                Err(CipherError::MalformedInput { position, .. }) => assert_eq!(position, line, "{:?}", text),
+                other => panic!("{:?} parsed as {:?}", text, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_locale_resolution() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(var, _)| *var == name).map(|(_, value)| value.to_string())
+        };
+        assert_eq!(resolve_locale(None, env(&[("LANG", "es_ES.UTF-8")])), "es");
+        assert_eq!(resolve_locale(Some("pt-BR"), env(&[("LANG", "es_ES.UTF-8")])), "pt");
+        assert_eq!(resolve_locale(None, env(&[("LC_ALL", "de_DE"), ("LANG", "es_ES")])), "de");
+        assert_eq!(resolve_locale(None, env(&[("LC_ALL", ""), ("LANG", "C.UTF-8")])), "en");
+        assert_eq!(resolve_locale(None, env(&[])), "en");
+
+        let mut args: Vec<String> = ["--lang", "es", "hash", "--lang=fr"].iter().map(|a| a.to_string()).collect();
+        assert_eq!(take_lang_flag(&mut args).unwrap().as_deref(), Some("es"));
+        assert_eq!(take_lang_flag(&mut args).unwrap().as_deref(), Some("fr"));
+        assert_eq!(args, ["hash"]);
+        assert!(take_lang_flag(&mut vec!["--lang".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_missing_keys_fall_back_to_english() {
+        let texts = parse_catalog("menu.exit = Sortir").unwrap();
+        let french = Messages::with_catalog(texts);
+        assert_eq!(french.get("menu.exit"), "Sortir");
+        assert_eq!(french.get("menu.goodbye"), "Goodbye!");
+        assert_eq!(french.get("no.such.key"), "no.such.key");
+        assert_eq!(french.format("menu.prompt", &[("count", &6)]), "Enter choice (1-6):");
+
+        assert_eq!(Messages::load("xx_XX").unwrap().get("menu.exit"), "Exit");
+        assert_eq!(Messages::load("es_MX.UTF-8").unwrap().get("menu.exit"), "Salir");
+    }
+
+    #[test]
+    fn test_built_in_catalogs_match_english() {
+        // Same keys and placeholders everywhere, so a translation never drops a value
+        // Mismas claves y marcadores en todos, así una traducción nunca pierde un valor
+        let english = parse_catalog(BUILT_IN[0].1).unwrap();
+        for (code, text) in &BUILT_IN[1..] {
+            let catalog = parse_catalog(text).unwrap();
+            for (key, value) in &english {
+                let translated = catalog.get(key).unwrap_or_else(|| panic!("{} lacks {}", code, key));
+                assert_eq!(placeholders(translated), placeholders(value), "{} {}", code, key);
+            }
+            assert!(catalog.keys().all(|key| english.contains_key(key)), "{} has keys English lacks", code);
+        }
+    }
+}
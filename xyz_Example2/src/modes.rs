@@ -0,0 +1,539 @@
+// Block Cipher Modes for AES: ECB, CBC, CTR and GCM
+// Modos de Cifrado por Bloques para AES: ECB, CBC, CTR y GCM
+
+use crate::aes::{Aes, BLOCK_LEN};
+use crate::cipher::{Cipher, CipherFactory, CipherParams, ParamKind, ParamSpec};
+use crate::encoding::hex_to_bytes;
+use crate::error::CipherError;
+use crate::secret::{ct_eq, zeroize};
+
+pub const GCM_NONCE_LEN: usize = 12;
+pub const GCM_TAG_LEN: usize = 16;
+
+// === PKCS#7 PADDING / RELLENO PKCS#7 ===
+
+/// Pad to a whole number of blocks; always adds 1-16 bytes
+/// Rellenar hasta un número entero de bloques; siempre añade 1-16 bytes
+pub fn pkcs7_pad(data: &[u8]) -> Vec<u8> {
+    let pad = BLOCK_LEN - data.len() % BLOCK_LEN;
+    let mut out = Vec::with_capacity(data.len() + pad);
+    out.extend_from_slice(data);
+    out.resize(data.len() + pad, pad as u8);
+    out
+}
+
+/// Strip and validate PKCS#7 padding / Quitar y validar el relleno PKCS#7
+pub fn pkcs7_unpad(data: &[u8]) -> Result<&[u8], CipherError> {
+    let malformed = |reason: &str| CipherError::MalformedInput {
+        position: data.len(),
+        reason: reason.to_string(),
+    };
+    if data.is_empty() || !data.len().is_multiple_of(BLOCK_LEN) {
+        return Err(malformed("length is not a whole number of blocks"));
+    }
+    let pad = data[data.len() - 1] as usize;
+    if pad == 0 || pad > BLOCK_LEN || data[data.len() - pad..].iter().any(|&b| b as usize != pad) {
+        return Err(malformed("invalid PKCS#7 padding"));
+    }
+    Ok(&data[..data.len() - pad])
+}
+
+fn check_block_multiple(data: &[u8]) -> Result<(), CipherError> {
+    if data.is_empty() || !data.len().is_multiple_of(BLOCK_LEN) {
+        return Err(CipherError::MalformedInput {
+            position: data.len(),
+            reason: "ciphertext is not a whole number of blocks".to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn xor_in_place(target: &mut [u8], other: &[u8]) {
+    for (t, o) in target.iter_mut().zip(other) {
+        *t ^= o;
+    }
+}
+
+// === ECB MODE (TEACHING ONLY) / MODO ECB (SOLO DIDÁCTICO) ===
+
+/// ECB leaks patterns: equal plaintext blocks give equal ciphertext blocks
+/// ECB filtra patrones: bloques de texto plano iguales dan bloques cifrados iguales
+pub struct AesEcb {
+    aes: Aes,
+}
+
+impl AesEcb {
+    pub fn new(key: &[u8]) -> Result<Self, CipherError> {
+        Ok(Self { aes: Aes::new(key)? })
+    }
+}
+
+impl Cipher for AesEcb {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let mut out = pkcs7_pad(data);
+        for chunk in out.chunks_exact_mut(BLOCK_LEN) {
+            self.aes.encrypt_block(chunk.try_into().unwrap());
+        }
+        Ok(out)
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        check_block_multiple(data)?;
+        let mut out = data.to_vec();
+        for chunk in out.chunks_exact_mut(BLOCK_LEN) {
+            self.aes.decrypt_block(chunk.try_into().unwrap());
+        }
+        Ok(pkcs7_unpad(&out)?.to_vec())
+    }
+}
+
+// === CBC MODE / MODO CBC ===
+
+/// CBC with PKCS#7 padding; the IV must be unpredictable for each message
+/// CBC con relleno PKCS#7; el IV debe ser impredecible para cada mensaje
+pub struct AesCbc {
+    aes: Aes,
+    iv: [u8; BLOCK_LEN],
+}
+
+impl AesCbc {
+    pub fn new(key: &[u8], iv: [u8; BLOCK_LEN]) -> Result<Self, CipherError> {
+        Ok(Self { aes: Aes::new(key)?, iv })
+    }
+}
+
+impl Cipher for AesCbc {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let mut out = pkcs7_pad(data);
+        let mut previous = self.iv;
+        for chunk in out.chunks_exact_mut(BLOCK_LEN) {
+            xor_in_place(chunk, &previous);
+            let block: &mut [u8; BLOCK_LEN] = chunk.try_into().unwrap();
+            self.aes.encrypt_block(block);
+            previous = *block;
+        }
+        Ok(out)
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        check_block_multiple(data)?;
+        let mut out = data.to_vec();
+        let mut previous = self.iv;
+        for chunk in out.chunks_exact_mut(BLOCK_LEN) {
+            let block: &mut [u8; BLOCK_LEN] = chunk.try_into().unwrap();
+            let saved = *block;
+            self.aes.decrypt_block(block);
+            xor_in_place(block, &previous);
+            previous = saved;
+        }
+        Ok(pkcs7_unpad(&out)?.to_vec())
+    }
+}
+
+// === CTR MODE / MODO CTR ===
+
+/// CTR mode: the whole 16-byte counter block is incremented as a big-endian integer
+/// Modo CTR: todo el bloque contador de 16 bytes se incrementa como entero big-endian
+pub struct AesCtr {
+    aes: Aes,
+    counter: [u8; BLOCK_LEN],
+}
+
+impl AesCtr {
+    pub fn new(key: &[u8], counter: [u8; BLOCK_LEN]) -> Result<Self, CipherError> {
+        Ok(Self { aes: Aes::new(key)?, counter })
+    }
+
+    fn apply(&self, data: &[u8]) -> Vec<u8> {
+        let mut out = data.to_vec();
+        let mut counter = u128::from_be_bytes(self.counter);
+        for chunk in out.chunks_mut(BLOCK_LEN) {
+            let mut keystream = counter.to_be_bytes();
+            self.aes.encrypt_block(&mut keystream);
+            xor_in_place(chunk, &keystream);
+            counter = counter.wrapping_add(1);
+        }
+        out
+    }
+}
+
+impl Cipher for AesCtr {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        Ok(self.apply(data))
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        Ok(self.apply(data))
+    }
+}
+
+// === GCM MODE / MODO GCM ===
+
+/// Multiply in GF(2^128) with GCM's reflected bit order
+/// Multiplicar en GF(2^128) con el orden de bits reflejado de GCM
+fn gf128_mul(x: u128, y: u128) -> u128 {
+    const R: u128 = 0xe1 << 120;
+    let mut z = 0u128;
+    let mut v = y;
+    for i in 0..128 {
+        if (x >> (127 - i)) & 1 == 1 {
+            z ^= v;
+        }
+        v = if v & 1 == 1 { (v >> 1) ^ R } else { v >> 1 };
+    }
+    z
+}
+
+/// AES-GCM; `encrypt` output is ciphertext followed by a 16-byte tag
+/// AES-GCM; la salida de `encrypt` es el texto cifrado seguido de una etiqueta de 16 bytes
+///
+/// A (key, nonce) pair must never encrypt two different messages.
+/// Un par (clave, nonce) nunca debe cifrar dos mensajes distintos.
+pub struct AesGcm {
+    aes: Aes,
+    h: [u8; BLOCK_LEN],
+    nonce: [u8; GCM_NONCE_LEN],
+    aad: Vec<u8>,
+}
+
+impl AesGcm {
+    pub fn new(key: &[u8], nonce: [u8; GCM_NONCE_LEN]) -> Result<Self, CipherError> {
+        let aes = Aes::new(key)?;
+        let mut h = [0u8; BLOCK_LEN];
+        aes.encrypt_block(&mut h);
+        Ok(Self { aes, h, nonce, aad: Vec::new() })
+    }
+
+    /// A second copy with the same key, nonce and AAD; deliberately not `Clone`
+    /// Una segunda copia con la misma clave, nonce y AAD; a propósito no es `Clone`
+    pub fn duplicate(&self) -> Self {
+        Self { aes: self.aes.duplicate(), h: self.h, nonce: self.nonce, aad: self.aad.clone() }
+    }
+
+    /// Associated data authenticated by `encrypt`/`decrypt` but not encrypted
+    /// Datos asociados autenticados por `encrypt`/`decrypt` pero no cifrados
+    pub fn with_aad(mut self, aad: &[u8]) -> Self {
+        self.aad = aad.to_vec();
+        self
+    }
+
+    fn ghash(&self, aad: &[u8], ciphertext: &[u8]) -> u128 {
+        let h = u128::from_be_bytes(self.h);
+        let mut y = 0u128;
+        for data in [aad, ciphertext] {
+            for chunk in data.chunks(BLOCK_LEN) {
+                let mut block = [0u8; BLOCK_LEN];
+                block[..chunk.len()].copy_from_slice(chunk);
+                y = gf128_mul(y ^ u128::from_be_bytes(block), h);
+            }
+        }
+        let lengths = ((aad.len() as u128 * 8) << 64) | (ciphertext.len() as u128 * 8);
+        gf128_mul(y ^ lengths, h)
+    }
+
+    /// Counter-mode keystream starting at inc32(J0) / Flujo en modo contador desde inc32(J0)
+    fn ctr32(&self, j0: u128, data: &mut [u8]) {
+        let prefix = j0 & !0xffff_ffffu128;
+        let mut counter = j0 as u32;
+        for chunk in data.chunks_mut(BLOCK_LEN) {
+            counter = counter.wrapping_add(1);
+            let mut keystream = (prefix | counter as u128).to_be_bytes();
+            self.aes.encrypt_block(&mut keystream);
+            xor_in_place(chunk, &keystream);
+        }
+    }
+
+    fn tag(&self, j0: u128, aad: &[u8], ciphertext: &[u8]) -> [u8; GCM_TAG_LEN] {
+        let mut mask = j0.to_be_bytes();
+        self.aes.encrypt_block(&mut mask);
+        (self.ghash(aad, ciphertext) ^ u128::from_be_bytes(mask)).to_be_bytes()
+    }
+
+    fn j0(nonce: &[u8; GCM_NONCE_LEN]) -> u128 {
+        let mut block = [0u8; BLOCK_LEN];
+        block[..GCM_NONCE_LEN].copy_from_slice(nonce);
+        block[BLOCK_LEN - 1] = 1;
+        u128::from_be_bytes(block)
+    }
+
+    fn check_length(len: usize) -> Result<(), CipherError> {
+        // At most 2^32 - 2 blocks per nonce / Como máximo 2^32 - 2 bloques por nonce
+        if len as u64 > (u32::MAX as u64 - 1) * BLOCK_LEN as u64 {
+            return Err(CipherError::MalformedInput {
+                position: 0,
+                reason: "message too long for a single nonce".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Encrypt and authenticate with an explicit nonce and associated data
+    /// Cifrar y autenticar con un nonce y datos asociados explícitos
+    pub fn seal(&self, nonce: &[u8; GCM_NONCE_LEN], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, CipherError> {
+        Self::check_length(plaintext.len())?;
+        let j0 = Self::j0(nonce);
+        let mut out = plaintext.to_vec();
+        self.ctr32(j0, &mut out);
+        let tag = self.tag(j0, aad, &out);
+        out.extend_from_slice(&tag);
+        Ok(out)
+    }
+
+    /// Verify the tag and decrypt; nothing is returned if verification fails
+    /// Verificar la etiqueta y descifrar; no se devuelve nada si la verificación falla
+    pub fn open(&self, nonce: &[u8; GCM_NONCE_LEN], aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>, CipherError> {
+        if sealed.len() < GCM_TAG_LEN {
+            return Err(CipherError::MalformedInput {
+                position: sealed.len(),
+                reason: "ciphertext shorter than the authentication tag".to_string(),
+            });
+        }
+        let (ciphertext, tag) = sealed.split_at(sealed.len() - GCM_TAG_LEN);
+        Self::check_length(ciphertext.len())?;
+        let j0 = Self::j0(nonce);
+        if !ct_eq(&self.tag(j0, aad, ciphertext), tag) {
+            return Err(CipherError::AuthenticationFailed);
+        }
+        let mut out = ciphertext.to_vec();
+        self.ctr32(j0, &mut out);
+        Ok(out)
+    }
+}
+
+/// H is derived from the key; the `Aes` field wipes itself / H se deriva de la clave; el campo `Aes` se borra solo
+impl Drop for AesGcm {
+    fn drop(&mut self) {
+        zeroize(&mut self.h);
+    }
+}
+
+impl Cipher for AesGcm {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        self.seal(&self.nonce, &self.aad, data)
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        self.open(&self.nonce, &self.aad, data)
+    }
+}
+
+// === REGISTRY PARAMETERS / PARÁMETROS DEL REGISTRO ===
+
+const KEY_PARAM: ParamSpec = ParamSpec {
+    name: "key",
+    prompt: "Enter AES key as 32, 48 or 64 hex digits / Ingresa clave AES como 32, 48 o 64 dígitos hex",
+    kind: ParamKind::Hex(32),
+};
+
+const IV_PARAM: ParamSpec = ParamSpec {
+    name: "iv",
+    prompt: "Enter 128-bit IV as 32 hex digits / Ingresa IV de 128 bits como 32 dígitos hex",
+    kind: ParamKind::Hex(16),
+};
+
+const COUNTER_PARAM: ParamSpec = ParamSpec {
+    name: "counter",
+    prompt: "Enter initial 128-bit counter as 32 hex digits / Ingresa contador inicial de 128 bits como 32 dígitos hex",
+    kind: ParamKind::Hex(16),
+};
+
+const NONCE_PARAM: ParamSpec = ParamSpec {
+    name: "nonce",
+    prompt: "Enter 96-bit nonce as 24 hex digits / Ingresa nonce de 96 bits como 24 dígitos hex",
+    kind: ParamKind::Hex(12),
+};
+
+const AAD_PARAM: ParamSpec = ParamSpec {
+    name: "aad",
+    prompt: "Enter associated data (optional) / Ingresa datos asociados (opcional)",
+    kind: ParamKind::Data,
+};
+
+fn key_param(params: &CipherParams) -> Result<Vec<u8>, CipherError> {
+    hex_to_bytes(params.require(KEY_PARAM.name)?.trim())
+}
+
+impl CipherFactory for AesEcb {
+    const NAME: &'static str = "AES-ECB";
+    const DESCRIPTION: &'static str = "AES-ECB (teaching only) / AES-ECB (solo didáctico)";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[KEY_PARAM]
+    }
+
+    fn from_params(params: &CipherParams) -> Result<Self, CipherError> {
+        Self::new(&key_param(params)?)
+    }
+}
+
+impl CipherFactory for AesCbc {
+    const NAME: &'static str = "AES-CBC";
+    const DESCRIPTION: &'static str = "AES-CBC with PKCS#7 / AES-CBC con PKCS#7";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[KEY_PARAM, IV_PARAM]
+    }
+
+    fn from_params(params: &CipherParams) -> Result<Self, CipherError> {
+        Self::new(&key_param(params)?, params.hex_array(IV_PARAM.name)?)
+    }
+}
+
+impl CipherFactory for AesCtr {
+    const NAME: &'static str = "AES-CTR";
+    const DESCRIPTION: &'static str = "AES-CTR / AES-CTR";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[KEY_PARAM, COUNTER_PARAM]
+    }
+
+    fn from_params(params: &CipherParams) -> Result<Self, CipherError> {
+        Self::new(&key_param(params)?, params.hex_array(COUNTER_PARAM.name)?)
+    }
+}
+
+impl CipherFactory for AesGcm {
+    const NAME: &'static str = "AES-GCM";
+    const DESCRIPTION: &'static str = "AES-GCM AEAD / AEAD AES-GCM";
+
+    fn schema() -> &'static [ParamSpec] {
+        &[KEY_PARAM, NONCE_PARAM, AAD_PARAM]
+    }
+
+    fn from_params(params: &CipherParams) -> Result<Self, CipherError> {
+        let cipher = Self::new(&key_param(params)?, params.hex_array(NONCE_PARAM.name)?)?;
+        Ok(cipher.with_aad(params.get(AAD_PARAM.name).unwrap_or("").as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // NIST SP 800-38A, Appendix F / NIST SP 800-38A, Apéndice F
+    const SP800_38A_KEY: &str = "2b7e151628aed2a6abf7158809cf4f3c";
+    const SP800_38A_PLAINTEXT: &str = "6bc1bee22e409f96e93d7e117393172a\
+                                       ae2d8a571e03ac9c9eb76fac45af8e51\
+                                       30c81c46a35ce411e5fbc1191a0a52ef\
+                                       f69f2445df4f9b17ad2b417be66c3710";
+
+    fn hex(s: &str) -> Vec<u8> {
+        hex_to_bytes(&s.replace(' ', "")).unwrap()
+    }
+
+    #[test]
+    fn test_pkcs7_padding() {
+        assert_eq!(pkcs7_pad(b"YELLOW SUBMARINE").len(), 32);
+        assert_eq!(pkcs7_pad(b"abc")[3..], [13; 13]);
+        assert_eq!(pkcs7_unpad(&pkcs7_pad(b"abc")).unwrap(), b"abc");
+
+        let mut bad = pkcs7_pad(b"abc");
+        bad[10] = 1;
+        assert!(matches!(pkcs7_unpad(&bad), Err(CipherError::MalformedInput { .. })));
+        assert!(pkcs7_unpad(&[0; 16]).is_err());
+    }
+
+    #[test]
+    fn test_ecb_sp800_38a_f_1_1() {
+        let ecb = AesEcb::new(&hex(SP800_38A_KEY)).unwrap();
+        let encrypted = ecb.encrypt(&hex(SP800_38A_PLAINTEXT)).unwrap();
+        let expected = hex(
+            "3ad77bb40d7a3660a89ecaf32466ef97f5d3d58503b9699de785895a96fdbaaf\
+             43b1cd7f598ece23881b00e3ed0306887b0c785e27e8ad3f8223207104725dd4",
+        );
+        // Trailing block is the full PKCS#7 padding block / El último bloque es el relleno completo
+        assert_eq!(encrypted[..64], expected[..]);
+        assert_eq!(ecb.decrypt(&encrypted).unwrap(), hex(SP800_38A_PLAINTEXT));
+    }
+
+    #[test]
+    fn test_cbc_sp800_38a_f_2_1() {
+        let iv: [u8; 16] = hex("000102030405060708090a0b0c0d0e0f").try_into().unwrap();
+        let cbc = AesCbc::new(&hex(SP800_38A_KEY), iv).unwrap();
+        let encrypted = cbc.encrypt(&hex(SP800_38A_PLAINTEXT)).unwrap();
+        let expected = hex(
+            "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2\
+             73bed6b8e3c1743b7116e69e222295163ff1caa1681fac09120eca307586e1a7",
+        );
+        assert_eq!(encrypted[..64], expected[..]);
+        assert_eq!(cbc.decrypt(&encrypted).unwrap(), hex(SP800_38A_PLAINTEXT));
+    }
+
+    #[test]
+    fn test_ctr_sp800_38a_f_5_1() {
+        let counter: [u8; 16] = hex("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff").try_into().unwrap();
+        let ctr = AesCtr::new(&hex(SP800_38A_KEY), counter).unwrap();
+        let encrypted = ctr.encrypt(&hex(SP800_38A_PLAINTEXT)).unwrap();
+        let expected = hex(
+            "874d6191b620e3261bef6864990db6ce9806f66b7970fdff8617187bb9fffdff\
+             5ae4df3edbd5d35e5b4f09020db03eab1e031dda2fbe03d1792170a0f3009cee",
+        );
+        assert_eq!(encrypted, expected);
+        assert_eq!(ctr.decrypt(&encrypted[..37]).unwrap(), hex(SP800_38A_PLAINTEXT)[..37]);
+    }
+
+    #[test]
+    fn test_gcm_test_case_2() {
+        let gcm = AesGcm::new(&[0; 16], [0; 12]).unwrap();
+        let sealed = gcm.encrypt(&[0; 16]).unwrap();
+        assert_eq!(sealed, hex("0388dace60b6a392f328c2b971b2fe78ab6e47d42cec13bdf53a67b21257bddf"));
+        assert_eq!(gcm.encrypt(&[]).unwrap(), hex("58e2fccefa7e3061367f1d57a4e7455a"));
+    }
+
+    #[test]
+    fn test_gcm_test_case_4_with_aad() {
+        let nonce: [u8; 12] = hex("cafebabefacedbaddecaf888").try_into().unwrap();
+        let aad = hex("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+        let gcm = AesGcm::new(&hex("feffe9928665731c6d6a8f9467308308"), nonce).unwrap().with_aad(&aad);
+        let plaintext = hex(
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+             1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+        );
+        let sealed = gcm.encrypt(&plaintext).unwrap();
+        let expected = hex(
+            "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e\
+             21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091\
+             5bc94fbc3221a5db94fae95ae7121a47",
+        );
+        assert_eq!(sealed, expected);
+        assert_eq!(gcm.decrypt(&sealed).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_gcm_rejects_tampering() {
+        let gcm = AesGcm::new(&[3; 32], [5; 12]).unwrap().with_aad(b"header");
+        let mut sealed = gcm.encrypt(b"attack at dawn").unwrap();
+        sealed[2] ^= 4;
+        assert!(matches!(gcm.decrypt(&sealed), Err(CipherError::AuthenticationFailed)));
+        sealed[2] ^= 4;
+        let other = gcm.duplicate().with_aad(b"other");
+        assert!(matches!(other.decrypt(&sealed), Err(CipherError::AuthenticationFailed)));
+        assert_eq!(gcm.decrypt(&sealed).unwrap(), b"attack at dawn");
+    }
+
+    #[test]
+    fn test_cbc_rejects_bad_length_and_padding() {
+        let cbc = AesCbc::new(&[1; 24], [0; 16]).unwrap();
+        assert!(matches!(cbc.decrypt(&[0; 15]), Err(CipherError::MalformedInput { .. })));
+        let other_key = AesCbc::new(&[2; 24], [0; 16]).unwrap();
+        let encrypted = cbc.encrypt(b"some message").unwrap();
+        // A wrong key almost always breaks the padding / Una clave incorrecta casi siempre rompe el relleno
+        assert!(other_key.decrypt(&encrypted).is_err());
+    }
+}
@@ -0,0 +1,423 @@
+// Property Testing: Seeded Generators, Thousands of Cases and Shrinking of Failures
+// Pruebas de Propiedades: Generadores con Semilla, Miles de Casos y Reducción de Fallos
+
+//! Test-only harness: a property runs over generated inputs and a failure is shrunk to a small case.
+//! Arnés solo para pruebas: una propiedad se ejecuta sobre entradas generadas y un fallo se reduce a un caso pequeño.
+
+use std::env;
+use std::error::Error;
+use std::fmt;
+
+use crate::classical::transposition::MAX_RAILS;
+use crate::file::CipherType;
+use crate::random::SecureRandom;
+
+/// Environment variable that replaces every property's seed, to replay a reported failure
+/// Variable de entorno que reemplaza la semilla de cada propiedad, para repetir un fallo reportado
+pub const SEED_VAR: &str = "XYZ_PROPTEST_SEED";
+
+/// Upper bound on accepted shrink steps, so a slow property cannot hang the suite
+/// Límite de pasos de reducción aceptados, así una propiedad lenta no bloquea la suite
+const MAX_SHRINKS: usize = 1000;
+
+/// What a property returns: `?` works on `CipherError` and on `String` messages
+/// Lo que devuelve una propiedad: `?` funciona con `CipherError` y con mensajes `String`
+pub type Outcome = Result<(), Box<dyn Error>>;
+
+/// SplitMix64: tiny, fast and fully determined by its seed; not for keys
+/// SplitMix64: pequeño, rápido y totalmente determinado por su semilla; no sirve para claves
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform-enough value in `0..bound` by multiply-shift / Valor suficientemente uniforme en `0..bound` por multiplicar y desplazar
+    pub fn below(&mut self, bound: u64) -> u64 {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    /// Value in `lo..=hi` / Valor en `lo..=hi`
+    pub fn between(&mut self, lo: u64, hi: u64) -> u64 {
+        match (hi - lo).checked_add(1) {
+            Some(span) => lo + self.below(span),
+            None => self.next_u64(),
+        }
+    }
+
+    /// Seed for a `SecureRandom`, so generated keys are reproducible too
+    /// Semilla para un `SecureRandom`, así las claves generadas también son reproducibles
+    pub fn seed(&mut self) -> [u8; 32] {
+        let mut seed = [0u8; 32];
+        for chunk in seed.chunks_mut(8) {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes());
+        }
+        seed
+    }
+}
+
+/// A generator of test inputs that also knows how to simplify them
+/// Un generador de entradas de prueba que también sabe simplificarlas
+pub trait Strategy {
+    type Value: Clone + fmt::Debug;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value;
+
+    /// Simpler variants of `value`, boldest first; empty when it cannot shrink
+    /// Variantes más simples de `value`, las más audaces primero; vacío si no puede reducirse
+    fn shrink(&self, _value: &Self::Value) -> Vec<Self::Value> {
+        Vec::new()
+    }
+}
+
+/// Integers in `lo..=hi`, with the bounds drawn more often; shrinks toward `lo`
+/// Enteros en `lo..=hi`, con los extremos más frecuentes; se reduce hacia `lo`
+pub struct Ints {
+    pub lo: u64,
+    pub hi: u64,
+}
+
+impl Strategy for Ints {
+    type Value = u64;
+
+    fn generate(&self, rng: &mut Rng) -> u64 {
+        match rng.below(8) {
+            0 => self.lo,
+            1 => self.hi,
+            _ => rng.between(self.lo, self.hi),
+        }
+    }
+
+    fn shrink(&self, &value: &u64) -> Vec<u64> {
+        let mut simpler = vec![self.lo, self.lo + (value - self.lo) / 2, value.saturating_sub(1)];
+        simpler.retain(|&v| v >= self.lo && v < value);
+        simpler.dedup();
+        simpler
+    }
+}
+
+/// Byte strings of `min..=max` bytes: text, arbitrary bytes or edge values, short ones more often
+/// Cadenas de `min..=max` bytes: texto, bytes arbitrarios o valores límite, las cortas con más frecuencia
+pub struct Bytes {
+    pub min: usize,
+    pub max: usize,
+}
+
+/// Bytes that tend to break byte and letter arithmetic / Bytes que suelen romper la aritmética de bytes y letras
+const EDGE_BYTES: [u8; 8] = [0, 0xff, 0x80, b'a', b'z', b'A', b'Z', b' '];
+
+/// Positions tried one by one when shrinking / Posiciones probadas una a una al reducir
+const SHRINK_POSITIONS: usize = 32;
+
+impl Strategy for Bytes {
+    type Value = Vec<u8>;
+
+    fn generate(&self, rng: &mut Rng) -> Vec<u8> {
+        let cap = rng.between(self.min as u64, self.max as u64);
+        let len = rng.between(self.min as u64, cap) as usize;
+        let style = rng.below(3);
+        (0..len)
+            .map(|_| match style {
+                0 => rng.between(0x20, 0x7e) as u8,
+                1 => rng.next_u64() as u8,
+                _ => EDGE_BYTES[rng.below(EDGE_BYTES.len() as u64) as usize],
+            })
+            .collect()
+    }
+
+    fn shrink(&self, value: &Vec<u8>) -> Vec<Vec<u8>> {
+        let mut simpler = Vec::new();
+        let len = value.len();
+        if len > self.min {
+            let half = (len / 2).max(self.min);
+            simpler.push(value[..self.min].to_vec());
+            simpler.push(value[..half].to_vec());
+            simpler.push(value[len - half..].to_vec());
+            for i in 0..len.min(SHRINK_POSITIONS) {
+                let mut shorter = value.clone();
+                shorter.remove(i);
+                simpler.push(shorter);
+            }
+        }
+        // Then lower single bytes, toward zero / Luego bajar bytes sueltos, hacia cero
+        for (i, &byte) in value.iter().enumerate().take(SHRINK_POSITIONS) {
+            for lower in [0, byte / 2, byte.saturating_sub(1)] {
+                if lower < byte {
+                    let mut candidate = value.clone();
+                    candidate[i] = lower;
+                    simpler.push(candidate);
+                }
+            }
+        }
+        simpler.dedup();
+        simpler
+    }
+}
+
+/// Printable ASCII keys of `min..=max` characters; shrinks to shorter keys of 'A's
+/// Claves ASCII imprimibles de `min..=max` caracteres; se reduce a claves más cortas de 'A'
+pub struct TextKeys {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Strategy for TextKeys {
+    type Value = String;
+
+    fn generate(&self, rng: &mut Rng) -> String {
+        let len = rng.between(self.min as u64, self.max as u64);
+        (0..len).map(|_| rng.between(0x21, 0x7e) as u8 as char).collect()
+    }
+
+    fn shrink(&self, value: &String) -> Vec<String> {
+        let mut simpler = Vec::new();
+        if value.len() > self.min {
+            simpler.push(value[..self.min].to_string());
+            for i in 0..value.len().min(SHRINK_POSITIONS) {
+                let mut shorter = value.clone();
+                shorter.remove(i);
+                simpler.push(shorter);
+            }
+        }
+        for i in value.bytes().enumerate().filter(|&(_, c)| c != b'A').map(|(i, _)| i).take(SHRINK_POSITIONS) {
+            let mut candidate = value.clone().into_bytes();
+            candidate[i] = b'A';
+            simpler.push(String::from_utf8(candidate).expect("ASCII stays UTF-8"));
+        }
+        simpler
+    }
+}
+
+/// Every `CipherType` with generated parameters, paired with the text key `EncryptionManager::new` needs
+/// Cada `CipherType` con parámetros generados, junto a la clave de texto que necesita `EncryptionManager::new`
+///
+/// Caesar shifts include 0, AES keys take every size; shrinking keeps the algorithm and simplifies its parameters.
+/// Los desplazamientos César incluyen 0 y las claves AES todos los tamaños; la reducción conserva el algoritmo.
+pub struct CipherTypes;
+
+const SHIFTS: Ints = Ints { lo: 0, hi: 25 };
+const RAILS: Ints = Ints { lo: 2, hi: MAX_RAILS as u64 };
+const OFFSETS: Ints = Ints { lo: 0, hi: 25 };
+const KEYS: TextKeys = TextKeys { min: 1, max: 24 };
+
+impl Strategy for CipherTypes {
+    type Value = (CipherType, String);
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        let aes_key_len = [16, 24, 32][rng.below(3) as usize];
+        let template = CipherType::variants(aes_key_len)[rng.below(15) as usize].clone();
+        let (algorithm, key) = template.with_random_key(&mut SecureRandom::from_seed(rng.seed()));
+        match algorithm {
+            CipherType::Caesar(_) => (CipherType::Caesar(SHIFTS.generate(rng) as u8), key),
+            CipherType::RailFence(_) => (CipherType::RailFence(RAILS.generate(rng) as u8), key),
+            CipherType::Affine { a, .. } => (CipherType::Affine { a, b: OFFSETS.generate(rng) as u8 }, key),
+            algorithm if !key.is_empty() => (algorithm, KEYS.generate(rng)),
+            algorithm => (algorithm, key),
+        }
+    }
+
+    fn shrink(&self, (algorithm, key): &Self::Value) -> Vec<Self::Value> {
+        let with = |algorithm: CipherType| (algorithm, key.clone());
+        match *algorithm {
+            CipherType::Caesar(shift) => SHIFTS.shrink(&shift.into()).into_iter().map(|s| with(CipherType::Caesar(s as u8))).collect(),
+            CipherType::RailFence(rails) => {
+                RAILS.shrink(&rails.into()).into_iter().map(|r| with(CipherType::RailFence(r as u8))).collect()
+            }
+            CipherType::Affine { a, b } => {
+                OFFSETS.shrink(&b.into()).into_iter().map(|b| with(CipherType::Affine { a, b: b as u8 })).collect()
+            }
+            _ if !key.is_empty() => KEYS.shrink(key).into_iter().map(|k| (algorithm.clone(), k)).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+macro_rules! tuple_strategy {
+    ($($name:ident $index:tt),+) => {
+        /// Each component generated independently and shrunk one at a time
+        /// Cada componente generado por separado y reducido de uno en uno
+        impl<$($name: Strategy),+> Strategy for ($($name,)+) {
+            type Value = ($($name::Value,)+);
+
+            fn generate(&self, rng: &mut Rng) -> Self::Value {
+                ($(self.$index.generate(rng),)+)
+            }
+
+            fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+                let mut simpler = Vec::new();
+                $(
+                    for component in self.$index.shrink(&value.$index) {
+                        let mut candidate = value.clone();
+                        candidate.$index = component;
+                        simpler.push(candidate);
+                    }
+                )+
+                simpler
+            }
+        }
+    };
+}
+
+tuple_strategy!(A 0, B 1);
+tuple_strategy!(A 0, B 1, C 2);
+
+/// A falsified property: the first failing input and the smallest one shrinking reached
+/// Una propiedad refutada: la primera entrada que falla y la más pequeña que alcanzó la reducción
+#[derive(Debug)]
+pub struct Failure<V> {
+    pub seed: u64,
+    pub case: usize,
+    pub original: V,
+    pub minimal: V,
+    pub reason: String,
+    pub shrinks: usize,
+}
+
+/// Run `cases` generated inputs from `seed`; on the first failure, shrink greedily while it keeps failing
+/// Ejecutar `cases` entradas generadas desde `seed`; en el primer fallo, reducir con avidez mientras siga fallando
+pub fn find_failure<S: Strategy>(
+    seed: u64,
+    cases: usize,
+    strategy: &S,
+    property: impl Fn(&S::Value) -> Outcome,
+) -> Option<Failure<S::Value>> {
+    let mut rng = Rng::new(seed);
+    for case in 0..cases {
+        let original = strategy.generate(&mut rng);
+        let Err(error) = property(&original) else { continue };
+        let (mut minimal, mut reason, mut shrinks) = (original.clone(), error.to_string(), 0);
+        while shrinks < MAX_SHRINKS {
+            let Some((simpler, error)) =
+                strategy.shrink(&minimal).into_iter().find_map(|c| property(&c).err().map(|e| (c, e)))
+            else {
+                break;
+            };
+            (minimal, reason, shrinks) = (simpler, error.to_string(), shrinks + 1);
+        }
+        return Some(Failure { seed, case, original, minimal, reason, shrinks });
+    }
+    None
+}
+
+/// Assert `property` over `cases` inputs; the seed comes from the name unless `XYZ_PROPTEST_SEED` is set
+/// Afirmar `property` sobre `cases` entradas; la semilla sale del nombre salvo que `XYZ_PROPTEST_SEED` esté definida
+pub fn check<S: Strategy>(name: &str, cases: usize, strategy: &S, property: impl Fn(&S::Value) -> Outcome) {
+    if let Some(failure) = find_failure(seed_for(name), cases, strategy, property) {
+        panic!(
+            "property `{}` failed at case {} (rerun with {}={})\n  original: {:?}\n  minimal after {} shrinks: {:?}\n  reason: {}",
+            name, failure.case, SEED_VAR, failure.seed, failure.original, failure.shrinks, failure.minimal, failure.reason
+        );
+    }
+}
+
+/// Fail with both values unless they are equal / Fallar con ambos valores salvo que sean iguales
+pub fn ensure_eq<T: PartialEq + fmt::Debug>(actual: T, expected: T) -> Outcome {
+    if actual == expected {
+        return Ok(());
+    }
+    Err(format!("expected {:?}, got {:?}", expected, actual).into())
+}
+
+/// FNV-1a of the name, so each property has its own stable sequence
+/// FNV-1a del nombre, así cada propiedad tiene su propia secuencia estable
+fn seed_for(name: &str) -> u64 {
+    if let Some(seed) = env::var(SEED_VAR).ok().and_then(|s| s.trim().parse().ok()) {
+        return seed;
+    }
+    name.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The original demo's Caesar pair: decrypt by `26 - shift`, in u8 arithmetic
+    /// El par César de la demo original: descifrar con `26 - shift`, en aritmética u8
+    fn legacy_caesar(data: &[u8], shift: u8) -> Vec<u8> {
+        data.iter()
+            .map(|&c| match c {
+                b'a'..=b'z' | b'A'..=b'Z' => {
+                    let base = if c.is_ascii_lowercase() { b'a' } else { b'A' };
+                    ((c - base).wrapping_add(shift) % 26) + base
+                }
+                _ => c,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_shrinking_finds_the_legacy_caesar_bug() {
+        let strategy = (Ints { lo: 0, hi: 255 }, Bytes { min: 0, max: 64 });
+        let property = |(shift, data): &(u64, Vec<u8>)| {
+            let encrypted = legacy_caesar(data, *shift as u8);
+            ensure_eq(legacy_caesar(&encrypted, 26u8.wrapping_sub(*shift as u8)), data.clone())
+        };
+        let failure = find_failure(1, 1000, &strategy, property).expect("the legacy decrypt is wrong for large shifts");
+        let (shift, data) = &failure.minimal;
+        assert!(*shift > 26, "{:?}", failure);
+        assert_eq!(data.len(), 1, "{:?}", failure);
+        assert!(property(&failure.minimal).is_err());
+
+        // Reducing the shift first fixes it / Reducir primero el desplazamiento lo corrige
+        let fixed = |(shift, data): &(u64, Vec<u8>)| {
+            let shift = (*shift % 26) as u8;
+            ensure_eq(legacy_caesar(&legacy_caesar(data, shift), 26 - shift), data.clone())
+        };
+        assert!(find_failure(1, 1000, &strategy, fixed).is_none());
+    }
+
+    #[test]
+    fn test_generation_is_deterministic_and_in_range() {
+        let strategy = (Ints { lo: 3, hi: 9 }, Bytes { min: 2, max: 40 }, TextKeys { min: 1, max: 5 });
+        let (mut a, mut b) = (Rng::new(42), Rng::new(42));
+        for _ in 0..500 {
+            let value = strategy.generate(&mut a);
+            assert_eq!(format!("{:?}", value), format!("{:?}", strategy.generate(&mut b)));
+            let (n, bytes, key) = value;
+            assert!((3..=9).contains(&n) && (2..=40).contains(&bytes.len()) && (1..=5).contains(&key.len()));
+            assert!(key.bytes().all(|c| c.is_ascii_graphic()));
+        }
+        assert_eq!(seed_for("same name"), seed_for("same name"));
+        assert_ne!(seed_for("one name"), seed_for("another name"));
+    }
+
+    #[test]
+    fn test_shrinking_reaches_a_minimal_byte_string() {
+        // Fails on any input holding a byte >= 100 / Falla con cualquier entrada que tenga un byte >= 100
+        let strategy = Bytes { min: 0, max: 200 };
+        let failure = find_failure(7, 1000, &strategy, |data: &Vec<u8>| match data.iter().any(|&b| b >= 100) {
+            true => Err("large byte".into()),
+            false => Ok(()),
+        })
+        .unwrap();
+        assert_eq!(failure.minimal, [100]);
+        assert!(strategy.shrink(&Vec::new()).is_empty());
+        assert!(Ints { lo: 5, hi: 10 }.shrink(&5).is_empty());
+    }
+
+    #[test]
+    fn test_cipher_types_cover_every_variant() {
+        let mut rng = Rng::new(3);
+        let mut names: Vec<String> = (0..2000)
+            .map(|_| format!("{:?}", CipherTypes.generate(&mut rng).0))
+            .map(|debug| debug.split([' ', '(']).next().unwrap().to_string())
+            .collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), 15, "{:?}", names);
+
+        let shrunk = CipherTypes.shrink(&(CipherType::Caesar(9), String::new()));
+        assert!(matches!(shrunk.first(), Some((CipherType::Caesar(0), _))));
+    }
+}
@@ -0,0 +1,284 @@
+// Secure Randomness: OS Entropy with a ChaCha20 DRBG Fallback
+// Aleatoriedad Segura: Entropía del SO con un DRBG ChaCha20 de Respaldo
+
+use std::fs::File;
+use std::io::{self, Read};
+
+use crate::chacha20poly1305::{chacha20_block, KEY_LEN, NONCE_LEN};
+use crate::cipher::ParamKind;
+use crate::classical::{EnigmaSettings, HillCipher};
+use crate::encoding::bytes_to_hex;
+use crate::error::CipherError;
+use crate::hash::Sha256;
+use crate::hmac::hmac;
+use crate::secret::zeroize;
+
+/// Length of generated text keys / Longitud de las claves de texto generadas
+pub const TEXT_KEY_LEN: usize = 16;
+
+/// Keeps `from_key` draws apart from other uses of the same key / Separa lo que sale de `from_key` de otros usos de la misma clave
+const FROM_KEY_INFO: &[u8] = b"xyz_example2 key parameters";
+
+/// Where `fill_bytes` currently reads from / De dónde lee actualmente `fill_bytes`
+enum Source {
+    Device(File), // /dev/urandom
+    Syscall,      // getrandom(2)
+    Drbg,         // Local ChaCha20 generator / Generador ChaCha20 local
+}
+
+/// Cryptographically secure random bytes for keys, nonces and salts
+/// Bytes aleatorios criptográficamente seguros para claves, nonces y sales
+pub struct SecureRandom {
+    source: Source,
+    drbg: ChaChaDrbg,
+}
+
+impl SecureRandom {
+    /// Use the OS source, keeping a DRBG seeded from it in case reads fail later
+    /// Usar la fuente del SO, manteniendo un DRBG sembrado desde ella por si fallan lecturas posteriores
+    pub fn new() -> Result<Self, CipherError> {
+        let mut seed = [0u8; KEY_LEN];
+        let source = match File::open("/dev/urandom") {
+            Ok(device) if (&device).read_exact(&mut seed).is_ok() => Source::Device(device),
+            _ if getrandom_fill(&mut seed) => Source::Syscall,
+            _ => {
+                return Err(io::Error::new(io::ErrorKind::NotFound, "no OS randomness source available").into())
+            }
+        };
+        Ok(Self { source, drbg: ChaChaDrbg { key: seed } })
+    }
+
+    /// Deterministic DRBG from a fixed seed; for tests and reproducible demos only, keys go through `from_key`
+    /// DRBG determinista a partir de una semilla fija; solo para pruebas y demos reproducibles, las claves usan `from_key`
+    pub fn from_seed(seed: [u8; KEY_LEN]) -> Self {
+        Self { source: Source::Drbg, drbg: ChaChaDrbg { key: seed } }
+    }
+
+    /// Deterministic DRBG seeded with HMAC-SHA256(key, label), for turning secret key material into cipher
+    /// parameters such as a Caesar shift or a Hill matrix; the same key always gives the same draws
+    /// DRBG determinista sembrado con HMAC-SHA256(clave, etiqueta), para convertir material de clave secreto en
+    /// parámetros de cifrado como un desplazamiento César o una matriz de Hill; la misma clave da siempre lo mismo
+    pub fn from_key(key: &[u8]) -> Self {
+        let mut seed = [0u8; KEY_LEN];
+        let mut tag = hmac::<Sha256>(key, FROM_KEY_INFO);
+        seed.copy_from_slice(&tag);
+        zeroize(&mut tag);
+        let rng = Self::from_seed(seed);
+        zeroize(&mut seed);
+        rng
+    }
+
+    /// Fill `buf`, switching to the DRBG if the OS source stops working
+    /// Llenar `buf`, cambiando al DRBG si la fuente del SO deja de funcionar
+    pub fn fill_bytes(&mut self, buf: &mut [u8]) {
+        let ok = match &mut self.source {
+            Source::Device(device) => device.read_exact(buf).is_ok(),
+            Source::Syscall => getrandom_fill(buf),
+            Source::Drbg => false,
+        };
+        if !ok {
+            self.source = Source::Drbg;
+            self.drbg.fill(buf);
+        }
+    }
+
+    pub fn bytes(&mut self, len: usize) -> Vec<u8> {
+        let mut buf = vec![0u8; len];
+        self.fill_bytes(&mut buf);
+        buf
+    }
+
+    pub fn array<const N: usize>(&mut self) -> [u8; N] {
+        let mut buf = [0u8; N];
+        self.fill_bytes(&mut buf);
+        buf
+    }
+
+    /// Uniform value in `0..bound` without modulo bias / Valor uniforme en `0..bound` sin sesgo de módulo
+    pub fn below(&mut self, bound: u32) -> u32 {
+        assert!(bound > 0, "bound must be positive");
+        let zone = u32::MAX - u32::MAX % bound;
+        loop {
+            let value = u32::from_le_bytes(self.array());
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+
+    /// Uniform shift in `1..modulus`, every one that changes the text for an alphabet of `modulus` letters
+    /// Desplazamiento uniforme en `1..modulus`, todos los que cambian el texto en un alfabeto de `modulus` letras
+    pub fn shift_below(&mut self, modulus: u32) -> u32 {
+        self.below(modulus - 1) + 1
+    }
+
+    /// Uniform value in `1..modulus` that shares no factor with it, such as an Affine multiplier
+    /// Valor uniforme en `1..modulus` sin factores comunes con él, como un multiplicador afín
+    pub fn coprime_below(&mut self, modulus: u32) -> u32 {
+        let gcd = |mut a: u32, mut b: u32| {
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            a
+        };
+        loop {
+            let value = self.below(modulus);
+            if gcd(value, modulus) == 1 {
+                return value;
+            }
+        }
+    }
+
+    /// Random uppercase key usable by XOR and Vigenère / Clave aleatoria en mayúsculas para XOR y Vigenère
+    pub fn text_key(&mut self, len: usize) -> String {
+        (0..len).map(|_| (b'A' + self.below(26) as u8) as char).collect()
+    }
+
+    /// Random value for a registry parameter; `None` for non-secret data
+    /// Valor aleatorio para un parámetro del registro; `None` para datos no secretos
+    pub fn param_value(&mut self, kind: ParamKind) -> Option<String> {
+        match kind {
+            // Any non-zero ASCII shift; `shift_below` fits other alphabets / Cualquier desplazamiento ASCII no nulo; `shift_below` se ajusta a otros alfabetos
+            ParamKind::Shift => Some(self.shift_below(26).to_string()),
+            ParamKind::Range(min, max) => Some((min + self.below(max - min + 1)).to_string()),
+            ParamKind::Coprime(modulus) => Some(self.coprime_below(modulus).to_string()),
+            ParamKind::Matrix(size) => Some(HillCipher::random_key(self, size)),
+            ParamKind::Enigma => Some(EnigmaSettings::random(self).to_string()),
+            ParamKind::Text => Some(self.text_key(TEXT_KEY_LEN)),
+            ParamKind::Hex(len) => Some(bytes_to_hex(&self.bytes(len))),
+            ParamKind::Data => None,
+        }
+    }
+}
+
+/// ChaCha20 keystream generator that rekeys itself after every request
+/// Generador de flujo ChaCha20 que cambia su clave tras cada petición
+struct ChaChaDrbg {
+    key: [u8; KEY_LEN],
+}
+
+impl ChaChaDrbg {
+    fn fill(&mut self, buf: &mut [u8]) {
+        let nonce = [0u8; NONCE_LEN];
+        // Block 0 becomes the next key, so earlier output cannot be recomputed
+        // El bloque 0 se convierte en la siguiente clave, así la salida anterior no puede recalcularse
+        let next_key = chacha20_block(&self.key, 0, &nonce);
+        for (i, chunk) in buf.chunks_mut(64).enumerate() {
+            let block = chacha20_block(&self.key, i as u32 + 1, &nonce);
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+        self.key.copy_from_slice(&next_key[..KEY_LEN]);
+    }
+}
+
+/// The state predicts every later draw, and `from_key` seeds it from a key / El estado predice lo que saldrá, y `from_key` lo siembra con una clave
+impl Drop for ChaChaDrbg {
+    fn drop(&mut self) {
+        zeroize(&mut self.key);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn getrandom_fill(buf: &mut [u8]) -> bool {
+    extern "C" {
+        fn getrandom(buf: *mut u8, buflen: usize, flags: u32) -> isize;
+    }
+    let mut filled = 0;
+    while filled < buf.len() {
+        // SAFETY: the pointer and length describe the unfilled tail of `buf`
+        let n = unsafe { getrandom(buf[filled..].as_mut_ptr(), buf.len() - filled, 0) };
+        if n <= 0 {
+            return false;
+        }
+        filled += n as usize;
+    }
+    true
+}
+
+#[cfg(not(target_os = "linux"))]
+fn getrandom_fill(_buf: &mut [u8]) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_os_source_produces_distinct_output() {
+        let mut rng = SecureRandom::new().unwrap();
+        let a: [u8; 32] = rng.array();
+        let b: [u8; 32] = rng.array();
+        assert_ne!(a, b);
+        assert_ne!(a, [0; 32]);
+    }
+
+    #[test]
+    fn test_getrandom_syscall() {
+        let mut buf = [0u8; 64];
+        if cfg!(target_os = "linux") {
+            assert!(getrandom_fill(&mut buf));
+            assert_ne!(buf, [0; 64]);
+        }
+    }
+
+    #[test]
+    fn test_drbg_is_chacha20_keystream_and_rekeys() {
+        let seed = [7u8; KEY_LEN];
+        let mut rng = SecureRandom::from_seed(seed);
+        let first = rng.bytes(100);
+        let expected: Vec<u8> = [chacha20_block(&seed, 1, &[0; 12]), chacha20_block(&seed, 2, &[0; 12])].concat();
+        assert_eq!(first, &expected[..100]);
+
+        // Same seed, same stream; next call uses the new key / Misma semilla, mismo flujo; la siguiente llamada usa la nueva clave
+        let second = rng.bytes(100);
+        assert_ne!(first, second);
+        let mut replay = SecureRandom::from_seed(seed);
+        assert_eq!(replay.bytes(100), first);
+        assert_eq!(replay.bytes(100), second);
+    }
+
+    #[test]
+    fn test_from_key_is_deterministic_and_separated_from_the_seed() {
+        let key = [9u8; KEY_LEN];
+        let first = SecureRandom::from_key(&key).bytes(64);
+        assert_eq!(SecureRandom::from_key(&key).bytes(64), first);
+        assert_ne!(SecureRandom::from_seed(key).bytes(64), first);
+        assert_ne!(SecureRandom::from_key(&[8; KEY_LEN]).bytes(64), first);
+        let seed: [u8; KEY_LEN] = hmac::<Sha256>(&key, FROM_KEY_INFO).try_into().unwrap();
+        assert_eq!(SecureRandom::from_seed(seed).bytes(64), first);
+    }
+
+    #[test]
+    fn test_below_and_text_key_stay_in_range() {
+        let mut rng = SecureRandom::from_seed([1; KEY_LEN]);
+        let mut seen = [false; 25];
+        for _ in 0..2000 {
+            seen[rng.below(25) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+        let key = rng.text_key(TEXT_KEY_LEN);
+        assert_eq!(key.len(), TEXT_KEY_LEN);
+        assert!(key.bytes().all(|b| b.is_ascii_uppercase()));
+    }
+
+    #[test]
+    fn test_param_values_match_kinds() {
+        let mut rng = SecureRandom::from_seed([2; KEY_LEN]);
+        let mut shifts = [false; 26];
+        for _ in 0..1000 {
+            let shift: usize = rng.param_value(ParamKind::Shift).unwrap().parse().unwrap();
+            shifts[shift] = true;
+        }
+        assert!(!shifts[0] && shifts[1..].iter().all(|&seen| seen));
+        assert!((0..200).map(|_| rng.shift_below(24)).all(|shift| (1..24).contains(&shift)));
+        assert_eq!(rng.param_value(ParamKind::Hex(12)).unwrap().len(), 24);
+        let rails: u32 = rng.param_value(ParamKind::Range(2, 10)).unwrap().parse().unwrap();
+        assert!((2..=10).contains(&rails));
+        let a: u32 = rng.param_value(ParamKind::Coprime(26)).unwrap().parse().unwrap();
+        assert!(a % 2 == 1 && a != 13);
+        assert_eq!(rng.param_value(ParamKind::Matrix(2)).unwrap().len(), 4);
+        assert!(rng.param_value(ParamKind::Enigma).unwrap().parse::<EnigmaSettings>().is_ok());
+        assert!(rng.param_value(ParamKind::Data).is_none());
+    }
+}
@@ -0,0 +1,118 @@
+// Secret Key Material: Wiped on Drop, Hidden from Logs, Compared in Constant Time
+// Material de Clave Secreta: Borrado al Liberarse, Oculto en Registros, Comparado en Tiempo Constante
+
+use std::fmt;
+use std::hint::black_box;
+use std::sync::atomic::{compiler_fence, Ordering};
+
+/// Overwrite `bytes` with zeros in a way the optimizer cannot drop as a dead store
+/// Sobrescribir `bytes` con ceros de forma que el optimizador no pueda quitarlo como escritura muerta
+///
+/// Best effort without `unsafe`: copies the program made earlier (moves, reallocations) are not reached.
+/// Mejor esfuerzo sin `unsafe`: las copias que el programa hizo antes (movimientos, realojos) no se alcanzan.
+pub fn zeroize(bytes: &mut [u8]) {
+    bytes.fill(0);
+    black_box(&mut *bytes);
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Compare two byte strings without an early exit, so timing does not reveal where they differ
+/// Comparar dos cadenas de bytes sin salida anticipada, así el tiempo no revela dónde difieren
+///
+/// Only the lengths may leak; tags and MACs have public, fixed lengths.
+/// Solo pueden filtrarse las longitudes; etiquetas y MACs tienen longitudes públicas y fijas.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    black_box(diff) == 0
+}
+
+/// Key bytes that are zeroed on drop, redacted in `Debug` and only copied through `duplicate`
+/// Bytes de clave que se ponen a cero al liberarse, se ocultan en `Debug` y solo se copian con `duplicate`
+pub struct SecretKey(Vec<u8>);
+
+impl SecretKey {
+    /// Copy `bytes` into a new secret / Copiar `bytes` en un nuevo secreto
+    pub fn new(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+
+    /// The key bytes; keep the borrow short / Los bytes de la clave; mantén el préstamo corto
+    pub fn expose(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// A second owned copy; deliberately not `Clone`, so every copy is visible in the code
+    /// Una segunda copia propia; a propósito no es `Clone`, así cada copia se ve en el código
+    pub fn duplicate(&self) -> Self {
+        Self::new(&self.0)
+    }
+}
+
+/// Take ownership without copying / Tomar posesión sin copiar
+impl From<Vec<u8>> for SecretKey {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        // Spare capacity may hold bytes from before a truncation / La capacidad sobrante puede guardar bytes previos
+        let capacity = self.0.capacity();
+        self.0.resize(capacity, 0);
+        zeroize(&mut self.0);
+    }
+}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretKey([REDACTED; {} bytes])", self.0.len())
+    }
+}
+
+/// Stand-in for a key field in hand-written `Debug` impls; prints `[REDACTED]`
+/// Sustituto de un campo de clave en impls `Debug` escritas a mano; imprime `[REDACTED]`
+pub struct Redacted;
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(ct_eq(b"", b""));
+        assert!(ct_eq(b"same tag", b"same tag"));
+        assert!(!ct_eq(b"same tag", b"same taG"));
+        assert!(!ct_eq(b"Same tag", b"same tag"));
+        assert!(!ct_eq(b"short", b"shorter"));
+    }
+
+    #[test]
+    fn test_secret_key_is_redacted_and_wiped() {
+        let key = SecretKey::from(b"hunter2".to_vec());
+        assert_eq!(format!("{:?}", key), "SecretKey([REDACTED; 7 bytes])");
+        assert_eq!(key.duplicate().expose(), b"hunter2");
+        assert_eq!((key.len(), SecretKey::new(&[]).is_empty()), (7, true));
+
+        let mut bytes = *b"key material";
+        zeroize(&mut bytes);
+        assert_eq!(bytes, [0; 12]);
+    }
+}
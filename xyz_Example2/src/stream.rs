@@ -0,0 +1,480 @@
+// Streaming Encryption: Chunked, Per-Chunk Authenticated Containers over `Read`/`Write`
+// Cifrado en Flujo: Contenedores por Fragmentos, Autenticados por Fragmento, sobre `Read`/`Write`
+
+use std::io::{self, Read, Write};
+
+use crate::aes::BLOCK_LEN;
+use crate::cipher::Cipher;
+use crate::classical::{VigenereCipher, XorCipher};
+use crate::container::{ContainerHeader, Integrity, VERSION_CHUNKED};
+use crate::error::CipherError;
+use crate::file::{CipherType, SubKeys};
+use crate::hash::{Digest, Sha256};
+use crate::hmac::Hmac;
+use crate::secret::{ct_eq, SecretKey};
+
+/// Plaintext bytes per chunk; memory use stays around this size
+/// Bytes de texto plano por fragmento; el uso de memoria se mantiene cerca de este tamaño
+pub const CHUNK_LEN: usize = 64 * 1024;
+const TAG_LEN: usize = 32;
+/// Room for block padding or an AEAD tag / Espacio para relleno de bloque o etiqueta AEAD
+const MAX_FRAME_LEN: usize = CHUNK_LEN + 64;
+/// Domain label for per-chunk CBC IVs / Etiqueta de dominio para los IVs CBC por fragmento
+const CHUNK_IV_INFO: &[u8] = b"xyz_example2 chunk iv";
+
+// Frame / Marco:  last flag(1) | ciphertext length(4, BE) | ciphertext | tag(32)
+// The tag covers header, chunk index, flag, length and ciphertext, so chunks cannot be
+// reordered, dropped or moved to another file.
+// La etiqueta cubre cabecera, índice, bandera, longitud y texto cifrado, así que los fragmentos
+// no pueden reordenarse, eliminarse ni moverse a otro archivo.
+
+/// Tag state already fed with the header bytes / Estado de la etiqueta ya alimentado con la cabecera
+#[derive(Clone)]
+enum Authenticator {
+    Hmac(Hmac<Sha256>),
+    Checksum(Sha256),
+}
+
+impl Authenticator {
+    fn tag(&self, index: u64, last: bool, ciphertext: &[u8]) -> Vec<u8> {
+        let mut frame_prefix = index.to_be_bytes().to_vec();
+        frame_prefix.push(last as u8);
+        frame_prefix.extend_from_slice(&(ciphertext.len() as u32).to_be_bytes());
+        match self.clone() {
+            Authenticator::Hmac(mut mac) => {
+                mac.update(&frame_prefix);
+                mac.update(ciphertext);
+                mac.finalize()
+            }
+            Authenticator::Checksum(mut hasher) => {
+                hasher.update(&frame_prefix);
+                hasher.update(ciphertext);
+                hasher.finalize()
+            }
+        }
+    }
+}
+
+/// Keys and algorithm shared by both directions / Claves y algoritmo comunes a ambas direcciones
+struct ChunkState {
+    algorithm: CipherType,
+    cipher_key: SecretKey,
+    authenticator: Authenticator,
+}
+
+impl ChunkState {
+    /// Same key schedule as whole-file containers; only ciphers with no key at all use a checksum
+    /// Mismo esquema de claves que los contenedores completos; solo los cifrados sin ninguna clave usan suma de control
+    fn new(header: &ContainerHeader, master_key: &[u8]) -> Result<Self, CipherError> {
+        let header_bytes = header.to_bytes()?;
+        match (header.integrity, master_key.is_empty()) {
+            (Integrity::Checksum, true) if header.algorithm.parameter_key().is_some() => Err(CipherError::EmptyKey),
+            (Integrity::Checksum, true) => {
+                let mut hasher = Sha256::new();
+                hasher.update(&header_bytes);
+                Ok(Self {
+                    algorithm: header.algorithm.clone(),
+                    cipher_key: SecretKey::new(&[]),
+                    authenticator: Authenticator::Checksum(hasher),
+                })
+            }
+            (Integrity::Hmac, false) => {
+                let subkeys = SubKeys::derive(master_key)?;
+                let mut mac = Hmac::<Sha256>::new(&subkeys.mac);
+                mac.update(&header_bytes);
+                Ok(Self {
+                    algorithm: header.algorithm.clone().with_key(&subkeys.encryption),
+                    cipher_key: SecretKey::new(&subkeys.encryption),
+                    authenticator: Authenticator::Hmac(mac),
+                })
+            }
+            (Integrity::Hmac, true) => Err(CipherError::EmptyKey),
+            (Integrity::Checksum, false) => Err(CipherError::AuthenticationFailed),
+        }
+    }
+
+    /// Cipher for one chunk: keystreams continue by position, nonces vary by index, CBC IVs come from `chunk_iv`
+    /// Cifrado para un fragmento: los flujos de clave siguen por posición, los nonces varían por índice, los IVs
+    /// CBC salen de `chunk_iv`
+    fn chunk_cipher(&self, index: u64) -> Result<Box<dyn Cipher>, CipherError> {
+        let position = index * CHUNK_LEN as u64;
+        let algorithm = match &self.algorithm {
+            CipherType::XOR => return Ok(Box::new(XorCipher::new(self.cipher_key.expose())?.starting_at(position))),
+            CipherType::Vigenere => {
+                return Ok(Box::new(VigenereCipher::new(self.cipher_key.expose())?.starting_at(position)))
+            }
+            CipherType::AesCtr { key, counter } => CipherType::AesCtr {
+                key: key.duplicate(),
+                counter: u128::from_be_bytes(*counter).wrapping_add((position / 16) as u128).to_be_bytes(),
+            },
+            CipherType::AesCbc { key, iv } => CipherType::AesCbc { key: key.duplicate(), iv: self.chunk_iv(iv, index) },
+            CipherType::AesGcm { key, nonce } => {
+                CipherType::AesGcm { key: key.duplicate(), nonce: chunk_nonce(nonce, index) }
+            }
+            CipherType::ChaCha20Poly1305 { key, nonce } => {
+                CipherType::ChaCha20Poly1305 { key: key.duplicate(), nonce: chunk_nonce(nonce, index) }
+            }
+            other => other.clone(),
+        };
+        algorithm.into_cipher(self.cipher_key.expose())
+    }
+
+    /// CBC needs unpredictable, unrelated IVs: HMAC-SHA256(encryption key, base IV ‖ index), truncated
+    /// CBC necesita IVs impredecibles y no relacionados: HMAC-SHA256(clave de cifrado, IV base ‖ índice), truncado
+    fn chunk_iv(&self, iv: &[u8; BLOCK_LEN], index: u64) -> [u8; BLOCK_LEN] {
+        let mut mac = Hmac::<Sha256>::new(self.cipher_key.expose());
+        mac.update(CHUNK_IV_INFO);
+        mac.update(iv);
+        mac.update(&index.to_be_bytes());
+        mac.finalize()[..BLOCK_LEN].try_into().unwrap()
+    }
+}
+
+/// XOR the chunk index into the last 8 bytes; fine for AEAD nonces, which only need to be unique
+/// Combinar con XOR el índice en los últimos 8 bytes; vale para nonces AEAD, que solo deben ser únicos
+fn chunk_nonce<const N: usize>(nonce: &[u8; N], index: u64) -> [u8; N] {
+    let mut out = *nonce;
+    for (b, i) in out[N - 8..].iter_mut().zip(index.to_be_bytes()) {
+        *b ^= i;
+    }
+    out
+}
+
+// === ENCRYPTOR / CIFRADOR ===
+
+/// Encrypts everything written to it; call `finish` to write the final chunk
+/// Cifra todo lo que se le escribe; llama a `finish` para escribir el fragmento final
+pub struct StreamEncryptor<W: Write> {
+    writer: W,
+    state: ChunkState,
+    buffer: Vec<u8>,
+    index: u64,
+}
+
+impl<W: Write> StreamEncryptor<W> {
+    /// Write the header (always as version 2) and get ready for data
+    /// Escribir la cabecera (siempre como versión 2) y prepararse para los datos
+    pub fn new(mut writer: W, header: ContainerHeader, master_key: &[u8]) -> Result<Self, CipherError> {
+        let header = ContainerHeader { version: VERSION_CHUNKED, ..header };
+        let state = ChunkState::new(&header, master_key)?;
+        writer.write_all(&header.to_bytes()?)?;
+        Ok(Self { writer, state, buffer: Vec::with_capacity(CHUNK_LEN), index: 0 })
+    }
+
+    fn write_frame(&mut self, last: bool) -> Result<(), CipherError> {
+        let ciphertext = self.state.chunk_cipher(self.index)?.encrypt(&self.buffer)?;
+        let tag = self.state.authenticator.tag(self.index, last, &ciphertext);
+        self.writer.write_all(&[last as u8])?;
+        self.writer.write_all(&(ciphertext.len() as u32).to_be_bytes())?;
+        self.writer.write_all(&ciphertext)?;
+        self.writer.write_all(&tag)?;
+        self.buffer.clear();
+        self.index += 1;
+        Ok(())
+    }
+
+    /// Write the final chunk and return the inner writer / Escribir el fragmento final y devolver el escritor
+    pub fn finish(mut self) -> Result<W, CipherError> {
+        self.write_frame(true)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> Write for StreamEncryptor<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // A full chunk is only sealed once more data arrives, so the last one can be flagged
+        // Un fragmento lleno solo se sella cuando llegan más datos, para poder marcar el último
+        if self.buffer.len() == CHUNK_LEN && !buf.is_empty() {
+            self.write_frame(false)?;
+        }
+        let take = (CHUNK_LEN - self.buffer.len()).min(buf.len());
+        self.buffer.extend_from_slice(&buf[..take]);
+        Ok(take)
+    }
+
+    /// Flushes the inner writer; buffered plaintext waits for a full chunk or `finish`
+    /// Vacía el escritor interno; el texto plano en búfer espera a un fragmento lleno o a `finish`
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+// === DECRYPTOR / DESCIFRADOR ===
+
+/// Verifies and decrypts one chunk at a time / Verifica y descifra un fragmento a la vez
+pub struct StreamDecryptor<R: Read> {
+    reader: R,
+    state: ChunkState,
+    index: u64,
+    position: usize, // Bytes consumed, for error positions / Bytes consumidos, para posiciones de error
+    plaintext: Vec<u8>,
+    offset: usize,
+    done: bool,
+}
+
+impl<R: Read> StreamDecryptor<R> {
+    /// Continue after `ContainerHeader::read_from` / Continuar tras `ContainerHeader::read_from`
+    pub fn new(reader: R, header: ContainerHeader, master_key: &[u8]) -> Result<Self, CipherError> {
+        let position = header.to_bytes()?.len();
+        if header.version != VERSION_CHUNKED {
+            return Err(CipherError::MalformedInput {
+                position: 4,
+                reason: format!("container version {} is not a chunked stream", header.version),
+            });
+        }
+        let state = ChunkState::new(&header, master_key)?;
+        Ok(Self { reader, state, index: 0, position, plaintext: Vec::new(), offset: 0, done: false })
+    }
+
+    fn read_exact_at(&mut self, len: usize) -> Result<Vec<u8>, CipherError> {
+        let mut buf = vec![0u8; len];
+        self.reader.read_exact(&mut buf).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => CipherError::MalformedInput {
+                position: self.position,
+                reason: "stream ended before the final chunk".to_string(),
+            },
+            _ => e.into(),
+        })?;
+        self.position += len;
+        Ok(buf)
+    }
+
+    /// Next verified plaintext chunk, or `None` after the final one
+    /// Siguiente fragmento verificado, o `None` después del final
+    pub fn read_chunk(&mut self) -> Result<Option<Vec<u8>>, CipherError> {
+        if self.done {
+            return Ok(None);
+        }
+        let frame_start = self.position;
+        let frame = self.read_exact_at(5)?;
+        let last = match frame[0] {
+            0 => false,
+            1 => true,
+            other => {
+                return Err(CipherError::MalformedInput {
+                    position: frame_start,
+                    reason: format!("invalid chunk flag {}", other),
+                })
+            }
+        };
+        let len = u32::from_be_bytes(frame[1..5].try_into().unwrap()) as usize;
+        if len > MAX_FRAME_LEN {
+            return Err(CipherError::MalformedInput {
+                position: frame_start + 1,
+                reason: format!("chunk length {} is too large", len),
+            });
+        }
+        let ciphertext = self.read_exact_at(len)?;
+        let tag = self.read_exact_at(TAG_LEN)?;
+
+        let expected = self.state.authenticator.tag(self.index, last, &ciphertext);
+        if !ct_eq(&expected, &tag) {
+            return Err(CipherError::AuthenticationFailed);
+        }
+        let plaintext = self.state.chunk_cipher(self.index)?.decrypt(&ciphertext)?;
+        self.index += 1;
+
+        if last {
+            self.done = true;
+            let mut extra = [0u8; 1];
+            if self.reader.read(&mut extra)? != 0 {
+                return Err(CipherError::MalformedInput {
+                    position: self.position,
+                    reason: "trailing data after the final chunk".to_string(),
+                });
+            }
+        }
+        Ok(Some(plaintext))
+    }
+}
+
+impl<R: Read> Read for StreamDecryptor<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.offset == self.plaintext.len() {
+            match self.read_chunk()? {
+                Some(chunk) => {
+                    self.plaintext = chunk;
+                    self.offset = 0;
+                }
+                None => return Ok(0),
+            }
+        }
+        let n = (self.plaintext.len() - self.offset).min(buf.len());
+        buf[..n].copy_from_slice(&self.plaintext[self.offset..self.offset + n]);
+        self.offset += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(algorithm: CipherType, integrity: Integrity) -> ContainerHeader {
+        ContainerHeader { version: VERSION_CHUNKED, algorithm, kdf: None, salt: Vec::new(), integrity }
+    }
+
+    fn encrypt_all(header: ContainerHeader, key: &[u8], data: &[u8], write_size: usize) -> Vec<u8> {
+        let mut encryptor = StreamEncryptor::new(Vec::new(), header, key).unwrap();
+        for piece in data.chunks(write_size) {
+            encryptor.write_all(piece).unwrap();
+        }
+        encryptor.finish().unwrap()
+    }
+
+    fn decrypt_all(sealed: &[u8], key: &[u8]) -> Result<Vec<u8>, CipherError> {
+        let mut reader = sealed;
+        let header = ContainerHeader::read_from(&mut reader)?;
+        let mut out = Vec::new();
+        StreamDecryptor::new(reader, header, key)?.read_to_end(&mut out)?;
+        Ok(out)
+    }
+
+    /// `CipherType::round_trip` chunk by chunk / `CipherType::round_trip` fragmento a fragmento
+    fn round_trip(algorithm: &CipherType, data: &[u8]) -> Vec<u8> {
+        data.chunks(CHUNK_LEN).flat_map(|chunk| algorithm.round_trip(chunk)).collect()
+    }
+
+    fn sample(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 31 % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_round_trip_every_cipher_type_across_chunks() {
+        let algorithms = [
+            CipherType::Caesar(3),
+            CipherType::XOR,
+            CipherType::Vigenere,
+            CipherType::Enigma("I II III C 01-12-26 XYZ QW ER".parse().unwrap()),
+            CipherType::Affine { a: 7, b: 3 },
+            CipherType::Atbash,
+            CipherType::Playfair,
+            CipherType::Hill { matrix: "GYBNQKURP".to_string() },
+            CipherType::RailFence(4),
+            CipherType::Columnar,
+            CipherType::ChaCha20Poly1305 { key: SecretKey::new(&[0; 32]), nonce: [1; 12] },
+            CipherType::AesEcb { key: vec![0; 16].into() },
+            CipherType::AesCbc { key: vec![0; 24].into(), iv: [2; 16] },
+            CipherType::AesCtr { key: vec![0; 32].into(), counter: [0xff; 16] },
+            CipherType::AesGcm { key: vec![0; 16].into(), nonce: [3; 12] },
+        ];
+        for len in [0, 10, CHUNK_LEN, CHUNK_LEN + 17] {
+            let data = sample(len);
+            for algorithm in algorithms.clone() {
+                let sealed = encrypt_all(header(algorithm.clone(), Integrity::Hmac), b"master", &data, 10_000);
+                assert_eq!(decrypt_all(&sealed, b"master").unwrap(), round_trip(&algorithm, &data), "{:?} / {}", algorithm, len);
+            }
+            let keyless = encrypt_all(header(CipherType::Atbash, Integrity::Checksum), b"", &data, 4096);
+            assert_eq!(decrypt_all(&keyless, b"").unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_classical_keys_come_from_the_key_not_the_header() {
+        let data = sample(100);
+        for (algorithm, other) in [
+            (CipherType::Caesar(7), CipherType::Caesar(3)),
+            (CipherType::Enigma("I II III C 01-12-26 XYZ QW ER".parse().unwrap()), CipherType::Enigma(Default::default())),
+            (CipherType::Affine { a: 5, b: 8 }, CipherType::Affine { a: 7, b: 3 }),
+            (CipherType::Hill { matrix: "GYBNQKURP".to_string() }, CipherType::Hill { matrix: "DDCFHCVTG".to_string() }),
+            (CipherType::RailFence(4), CipherType::RailFence(9)),
+        ] {
+            // Different keys, same header / Claves distintas, misma cabecera
+            let sealed = encrypt_all(header(algorithm.clone(), Integrity::Hmac), b"master", &data, 100);
+            let header_len = header(other.clone(), Integrity::Hmac).to_bytes().unwrap().len();
+            assert_eq!(sealed[..header_len], header(other, Integrity::Hmac).to_bytes().unwrap()[..], "{:?}", algorithm);
+            assert_eq!(decrypt_all(&sealed, b"master").unwrap(), round_trip(&algorithm, &data), "{:?}", algorithm);
+            assert!(matches!(decrypt_all(&sealed, b"other"), Err(CipherError::AuthenticationFailed)));
+            assert!(matches!(
+                StreamEncryptor::new(Vec::new(), header(algorithm, Integrity::Checksum), b""),
+                Err(CipherError::EmptyKey)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_positional_keystreams_ignore_write_sizes() {
+        let data = sample(CHUNK_LEN + 1000);
+        for algorithm in [CipherType::XOR, CipherType::Vigenere, CipherType::AesCtr { key: vec![0; 16].into(), counter: [0; 16] }] {
+            let small = encrypt_all(header(algorithm.clone(), Integrity::Hmac), b"k", &data, 7);
+            let large = encrypt_all(header(algorithm.clone(), Integrity::Hmac), b"k", &data, data.len());
+            assert_eq!(small, large);
+
+            // Chunk payloads concatenate to the one-shot ciphertext / Los fragmentos concatenados dan el cifrado de una pasada
+            let header = header(algorithm, Integrity::Hmac);
+            let state = ChunkState::new(&header, b"k").unwrap();
+            let one_shot = state.algorithm.clone().into_cipher(state.cipher_key.expose()).unwrap().encrypt(&data).unwrap();
+            let header_len = header.to_bytes().unwrap().len();
+            let first = &small[header_len + 5..header_len + 5 + CHUNK_LEN];
+            let second_start = header_len + 5 + CHUNK_LEN + TAG_LEN + 5;
+            let second = &small[second_start..second_start + 1000];
+            assert_eq!([first, second].concat(), one_shot);
+        }
+    }
+
+    #[test]
+    fn test_rejects_wrong_key_tampering_truncation_and_reordering() {
+        let data = sample(2 * CHUNK_LEN + 5);
+        let sealed = encrypt_all(header(CipherType::XOR, Integrity::Hmac), b"right", &data, CHUNK_LEN);
+        let header_len = header(CipherType::XOR, Integrity::Hmac).to_bytes().unwrap().len();
+        let frame_len = 5 + CHUNK_LEN + TAG_LEN;
+
+        assert!(matches!(decrypt_all(&sealed, b"wrong"), Err(CipherError::AuthenticationFailed)));
+
+        let mut tampered = sealed.clone();
+        tampered[header_len + 100] ^= 1;
+        assert!(matches!(decrypt_all(&tampered, b"right"), Err(CipherError::AuthenticationFailed)));
+
+        // Dropping the final chunk is detected / Se detecta la eliminación del fragmento final
+        let truncated = &sealed[..header_len + 2 * frame_len];
+        assert!(matches!(decrypt_all(truncated, b"right"), Err(CipherError::MalformedInput { .. })));
+
+        let mut swapped = sealed[..header_len].to_vec();
+        swapped.extend_from_slice(&sealed[header_len + frame_len..header_len + 2 * frame_len]);
+        swapped.extend_from_slice(&sealed[header_len..header_len + frame_len]);
+        swapped.extend_from_slice(&sealed[header_len + 2 * frame_len..]);
+        assert!(matches!(decrypt_all(&swapped, b"right"), Err(CipherError::AuthenticationFailed)));
+
+        let mut trailing = sealed.clone();
+        trailing.push(0);
+        assert!(matches!(decrypt_all(&trailing, b"right"), Err(CipherError::MalformedInput { .. })));
+    }
+
+    #[test]
+    fn test_chunk_nonces_are_distinct() {
+        let base = [9u8; 12];
+        assert_eq!(chunk_nonce(&base, 0), base);
+        assert_ne!(chunk_nonce(&base, 1), chunk_nonce(&base, 2));
+    }
+
+    #[test]
+    fn test_cbc_chunk_ivs_are_unpredictable() {
+        let iv = [2u8; 16];
+        let state = ChunkState::new(&header(CipherType::AesCbc { key: vec![0; 16].into(), iv }, Integrity::Hmac), b"k").unwrap();
+        let ivs: Vec<[u8; 16]> = (0..4).map(|index| state.chunk_iv(&iv, index)).collect();
+        for (index, chunk_iv) in ivs.iter().enumerate() {
+            // Not the base IV with the index mixed in / No es el IV base con el índice mezclado
+            assert_ne!(*chunk_iv, chunk_nonce(&iv, index as u64));
+            assert_eq!(ivs.iter().filter(|other| *other == chunk_iv).count(), 1);
+        }
+        let other = ChunkState::new(&header(CipherType::AesCbc { key: vec![0; 16].into(), iv }, Integrity::Hmac), b"j").unwrap();
+        assert_ne!(other.chunk_iv(&iv, 1), ivs[1]);
+    }
+
+    #[test]
+    fn test_property_round_trip_across_chunk_boundaries() {
+        use crate::property::{check, ensure_eq, CipherTypes, Ints};
+
+        // Up to two chunks and a byte, written in arbitrary pieces / Hasta dos fragmentos y un byte, escritos en trozos arbitrarios
+        let strategy = (CipherTypes, Ints { lo: 0, hi: 2 * CHUNK_LEN as u64 + 1 }, Ints { lo: 1, hi: CHUNK_LEN as u64 });
+        check("stream round-trips across chunks", 40, &strategy, |((algorithm, _), len, write_size)| {
+            let data = sample(*len as usize);
+            let mut encryptor = StreamEncryptor::new(Vec::new(), header(algorithm.clone(), Integrity::Hmac), b"master")?;
+            for piece in data.chunks(*write_size as usize) {
+                encryptor.write_all(piece)?;
+            }
+            ensure_eq(decrypt_all(&encryptor.finish()?, b"master")?, round_trip(algorithm, &data))
+        });
+    }
+}